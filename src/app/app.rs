@@ -6,13 +6,15 @@ use crate::{
     app::{
         screens::{
             ConfigScreen, HistoryScreen, ResultAction, ResultsScreen, RunningScreen, StartScreen,
+            StartScreenAction,
         },
         state::{AppState, NavigationAction, StateManager},
         tui::Tui,
     },
-    bench::worker::WorkerManager,
+    bench::{repeat, worker::WorkerManager},
     config::{persistence, BenchmarkConfig},
     error,
+    models::BenchmarkResult,
     Result,
 };
 use std::io;
@@ -38,6 +40,8 @@ pub struct App {
     worker_manager: Option<WorkerManager>,
     /// Progress receiver
     progress_rx: Option<mpsc::Receiver<crate::bench::worker::AggregatedProgress>>,
+    /// Results of the runs completed so far when repeating the benchmark
+    runs: Vec<BenchmarkResult>,
 }
 
 impl App {
@@ -56,6 +60,7 @@ impl App {
             history_loaded: false,
             worker_manager: None,
             progress_rx: None,
+            runs: Vec::new(),
         })
     }
 
@@ -78,7 +83,17 @@ impl App {
 
     /// Start a benchmark using the current configuration
     async fn start_benchmark(&mut self) -> Result<()> {
-        let mut manager = WorkerManager::new(self.config.clone())?;
+        self.running_screen = RunningScreen::new();
+        self.runs.clear();
+        self.start_run().await
+    }
+
+    /// Start the next run of the current benchmark
+    async fn start_run(&mut self) -> Result<()> {
+        self.running_screen
+            .start_run(self.runs.len() + 1, self.config.repetitions);
+        let mut manager = WorkerManager::new(self.config.clone())?
+            .with_history(persistence::ResultsStorage::new()?);
         let (tx, rx) = mpsc::channel(100);
         manager.start_benchmark(tx).await?;
         self.worker_manager = Some(manager);
@@ -88,14 +103,13 @@ impl App {
 
     /// Run the main application loop
     pub async fn run(&mut self) -> Result<()> {
-        println!("Starting application in state: {:?}", self.state_manager.current_state());
-        
         while !self.state_manager.should_quit() {
             if let Some(rx) = &mut self.progress_rx {
-                if let Ok(progress) = rx.try_recv() {
+                while let Ok(progress) = rx.try_recv() {
                     self.running_screen.update_progress(progress);
                 }
             }
+            self.poll_benchmark().await;
             self.draw()?;
             self.handle_events().await?;
         }
@@ -143,30 +157,16 @@ impl App {
     }
 
     async fn handle_start_screen_events(&mut self, key: crossterm::event::KeyEvent) -> Result<()> {
-        println!("Start screen received key: {:?}", key);
-        let action = self.start_screen.handle_key(key.code);
-        println!("Start screen action: {:?}", action);
-        
-        match action {
-            NavigationAction::Up => self.start_screen.select_previous(),
-            NavigationAction::Down => self.start_screen.select_next(),
-            NavigationAction::Select => {
-                // Start benchmark with default config for selected disk
-                self.config = BenchmarkConfig::default()
+        match self.start_screen.handle_key(key.code) {
+            StartScreenAction::StartTest => {
+                // Start benchmark with the saved config for the selected disk
+                self.config = self
+                    .config
+                    .clone()
                     .with_disk_path(self.start_screen.selected_disk().clone());
-                match self.start_benchmark().await {
-                    Ok(()) => self.state_manager.transition_to(AppState::Running),
-                    Err(e) => {
-                        let mut msg = error::user_friendly_message(&e);
-                        if let Some(fallback) = error::create_fallback_strategy(&e) {
-                            msg = format!("{}\n{}", msg, fallback);
-                        }
-                        self.running_screen.set_error(msg);
-                        self.state_manager.transition_to(AppState::Running);
-                    }
-                }
+                self.launch_benchmark().await;
             }
-            NavigationAction::Right => {
+            StartScreenAction::OpenHistory => {
                 self.load_history_results()?;
                 self.state_manager.transition_to(AppState::History);
             }
@@ -186,20 +186,9 @@ impl App {
 
     async fn handle_config_screen_events(&mut self, key: crossterm::event::KeyEvent) -> Result<()> {
         if let Some(new_screen) = self.config_screen.handle_key_event(key) {
-            if new_screen == AppState::Start {
-                // Potentially save config before exiting
-                self.config = self.config_screen.get_config();
-                match self.start_benchmark().await {
-                    Ok(()) => self.state_manager.transition_to(AppState::Running),
-                    Err(e) => {
-                        let mut msg = error::user_friendly_message(&e);
-                        if let Some(fallback) = error::create_fallback_strategy(&e) {
-                            msg = format!("{}\n{}", msg, fallback);
-                        }
-                        self.running_screen.set_error(msg);
-                        self.state_manager.transition_to(AppState::Running);
-                    }
-                }
+            self.config = self.config_screen.get_config();
+            if new_screen == AppState::Running {
+                self.launch_benchmark().await;
             } else {
                 self.state_manager.go_back();
             }
@@ -207,12 +196,74 @@ impl App {
         Ok(())
     }
 
+    /// Start a benchmark and switch to the running screen, showing any startup error there
+    async fn launch_benchmark(&mut self) {
+        if let Err(e) = self.start_benchmark().await {
+            self.running_screen.set_error(Self::error_message(&e));
+        }
+        self.state_manager.transition_to(AppState::Running);
+    }
+
+    /// Build the message shown on the running screen for a failed benchmark
+    fn error_message(e: &crate::DIOrbError) -> String {
+        let mut msg = error::user_friendly_message(e);
+        if let Some(fallback) = error::create_fallback_strategy(e) {
+            msg = format!("{}\n{}", msg, fallback);
+        }
+        msg
+    }
+
+    /// Collect the result once every worker has finished
+    async fn poll_benchmark(&mut self) {
+        let finished = match &self.worker_manager {
+            Some(manager) => manager.workers_finished().await,
+            None => return,
+        };
+        if !finished {
+            return;
+        }
+
+        let Some(manager) = self.worker_manager.take() else {
+            return;
+        };
+        self.progress_rx = None;
+
+        let outcome = match manager.wait_for_completion().await {
+            Ok(results) => manager.combine_results(results),
+            Err(e) => Err(e),
+        };
+        let outcome = match outcome {
+            Ok(result) if !self.running_screen.is_cancellation_requested() => {
+                self.runs.push(result);
+                if repeat::needs_another_run(&self.config, &self.runs) {
+                    if let Err(e) = self.start_run().await {
+                        self.running_screen.set_error(Self::error_message(&e));
+                    }
+                    return;
+                }
+                repeat::combine_runs(&self.config, std::mem::take(&mut self.runs))
+            }
+            outcome => outcome,
+        };
+        match outcome {
+            Ok(result) => {
+                self.results_screen.set_result(result);
+                self.state_manager.transition_to(AppState::Results);
+            }
+            Err(_) if self.running_screen.is_cancellation_requested() => {
+                self.state_manager.transition_to(AppState::Start);
+            }
+            Err(e) => self.running_screen.set_error(Self::error_message(&e)),
+        }
+    }
+
     async fn handle_running_screen_events(&mut self, action: NavigationAction) {
         match action {
             NavigationAction::Back => {
-                if let Some(manager) = &self.worker_manager {
+                if let Some(manager) = self.worker_manager.take() {
                     manager.cancel_all().await.ok();
                 }
+                self.progress_rx = None;
                 self.state_manager.go_back()
             }
             NavigationAction::Cancel => {
@@ -224,23 +275,7 @@ impl App {
             NavigationAction::Retry if self.running_screen.has_error() => {
                 // Retry benchmark using existing config
                 if let Err(e) = self.start_benchmark().await {
-                    let mut msg = error::user_friendly_message(&e);
-                    if let Some(fallback) = error::create_fallback_strategy(&e) {
-                        msg = format!("{}\n{}", msg, fallback);
-                    }
-                    self.running_screen.set_error(msg);
-                } else {
-                    self.running_screen.clear_error();
-                }
-            }
-            NavigationAction::Select if self.running_screen.is_completed() => {
-                if let Some(manager) = self.worker_manager.take() {
-                    if let Ok(results) = manager.wait_for_completion().await {
-                        if let Ok(final_result) = manager.combine_results(results) {
-                            self.results_screen.set_result(final_result);
-                            self.state_manager.transition_to(AppState::Results);
-                        }
-                    }
+                    self.running_screen.set_error(Self::error_message(&e));
                 }
             }
             _ => {
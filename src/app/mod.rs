@@ -3,6 +3,7 @@
 //! Contains the terminal user interface components, screen management,
 //! and application state handling.
 
+#[allow(clippy::module_inception)]
 pub mod app;
 pub mod screens;
 pub mod state;
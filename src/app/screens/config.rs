@@ -280,7 +280,7 @@ impl ConfigScreen {
 
     fn get_filesize_options(&self) -> Vec<u64> {
         vec![
-            1 * 1024 * 1024 * 1024,
+            1024 * 1024 * 1024,
             2 * 1024 * 1024 * 1024,
             4 * 1024 * 1024 * 1024,
             8 * 1024 * 1024 * 1024,
@@ -288,7 +288,7 @@ impl ConfigScreen {
     }
 
     fn get_blocksize_options(&self) -> Vec<u64> {
-        vec![512, 4 * 1024, 128 * 1024, 1 * 1024 * 1024]
+        vec![512, 4 * 1024, 128 * 1024, 1024 * 1024]
     }
 
     fn get_duration_options(&self) -> Vec<Duration> {
@@ -134,15 +134,15 @@ impl Default for HistoryScreen {
 mod tests {
     use super::*;
     use crate::config::{BenchmarkConfig, BenchmarkMode};
-    use crate::models::{BenchmarkResult, LatencyStats, PerformanceMetrics, SystemInfo};
+    use crate::models::{BenchmarkResult, LatencyStats, PerformanceMetrics, Percentile, SystemInfo};
     use chrono::Utc;
-    use std::collections::HashMap;
+    use std::collections::BTreeMap;
     use std::path::PathBuf;
     use std::time::Duration;
 
     fn create_result() -> BenchmarkResult {
-        let mut percentiles = HashMap::new();
-        percentiles.insert(50, Duration::from_millis(5));
+        let mut percentiles = BTreeMap::new();
+        percentiles.insert(Percentile::P50, Duration::from_millis(5));
         BenchmarkResult {
             timestamp: Utc::now(),
             config: BenchmarkConfig {
@@ -153,6 +153,7 @@ mod tests {
                 duration: Duration::from_secs(1),
                 thread_count: 1,
                 keep_temp_files: false,
+                ..BenchmarkConfig::default()
             },
             metrics: PerformanceMetrics {
                 bytes_processed: 1024,
@@ -165,8 +166,17 @@ mod tests {
                     max: Duration::from_millis(3),
                     percentiles,
                 },
+                ..Default::default()
             },
             system_info: SystemInfo::default(),
+            direct_io_mode: None,
+            engine_fallback: None,
+            queue_depth_source: None,
+            worker_metrics: Vec::new(),
+            timeline: None,
+            steady_state: None,
+            repetitions: None,
+            open_loop: None,
         }
     }
 
@@ -12,4 +12,4 @@ pub use config::ConfigScreen;
 pub use history::HistoryScreen;
 pub use results::{ResultAction, ResultsScreen};
 pub use running::RunningScreen;
-pub use start::StartScreen;
+pub use start::{StartScreen, StartScreenAction};
@@ -10,8 +10,9 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Row, Table},
     Frame,
 };
-use crate::models::BenchmarkResult;
+use crate::models::{BenchmarkResult, SummaryStats};
 use crate::util::units::{format_bytes, format_duration, format_throughput};
+use std::time::Duration;
 
 /// Results screen component that displays benchmark results
 #[derive(Debug)]
@@ -191,20 +192,118 @@ impl ResultsScreen {
         let file_size_str = format_bytes(result.config.file_size);
         let block_size_str = format_bytes(result.config.block_size);
         let threads_str = format!("{}", result.config.thread_count);
+        let ramp_time_str = if result.config.ramp_time.is_zero() {
+            "None".to_string()
+        } else {
+            format!("{} (not measured)", format_duration(result.config.ramp_time))
+        };
         let data_processed_str = format_bytes(metrics.bytes_processed);
         let elapsed_time_str = format_duration(metrics.elapsed_time);
         let throughput_str = format_throughput(metrics.throughput_mbps);
         let iops_str = format!("{:.0}", metrics.iops);
+        let operations_str = metrics.operations.to_string();
+        let timeline_str = result.timeline.as_ref().and_then(|timeline| {
+            let (min, max) = timeline.throughput_range()?;
+            Some(format!(
+                "{} x {}, {} - {}",
+                timeline.samples.len(),
+                format_duration(timeline.interval),
+                format_throughput(min),
+                format_throughput(max)
+            ))
+        });
+        let steady_state_str = result.steady_state.as_ref().map(|steady| {
+            format!(
+                "{}, {} within {:.1}%",
+                steady.description(),
+                steady.metric.description(),
+                steady.tolerance * 100.0
+            )
+        });
         let min_latency_str = format_duration(metrics.latency.min);
         let avg_latency_str = format_duration(metrics.latency.avg);
         let max_latency_str = format_duration(metrics.latency.max);
         let timestamp_str = result.timestamp.format("%Y-%m-%d %H:%M:%S UTC").to_string();
         let disk_path_str = result.config.disk_path.display().to_string();
+        let access_str = if result.config.mode.uses_duration() {
+            result.config.access_pattern.description()
+        } else {
+            "Sequential".to_string()
+        };
+        let seed_str = match result.config.seed {
+            Some(seed) if result.config.mode.uses_duration() => seed.to_string(),
+            _ => "-".to_string(),
+        };
+        let target_load_str = result.open_loop.as_ref().map_or_else(
+            || "Unlimited".to_string(),
+            |open_loop| format!(
+                "{:.0} IOPS, {} ({:.1}% achieved)",
+                open_loop.target_iops,
+                open_loop.arrivals.description(),
+                open_loop.achieved_fraction(metrics.iops) * 100.0
+            ),
+        );
+        // Latencies of rate-limited runs count from when requests were due
+        let open_loop_strs: Vec<(&str, String)> = result.open_loop.as_ref().map_or_else(Vec::new, |open_loop| {
+            vec![
+                ("  Issued Late:", format!("avg {} / max {}", format_duration(open_loop.mean_issue_delay), format_duration(open_loop.max_issue_delay))),
+                ("  Service Time:", format!("avg {} / p99 {}", format_duration(open_loop.service_latency.avg), format_duration(open_loop.service_latency.p99()))),
+            ]
+        });
+        let io_mode_str = result
+            .direct_io_mode
+            .map_or("Not recorded", |mode| mode.description());
 
         // Pre-format percentile strings
-        let p50_str = metrics.latency.percentiles.get(&50).map(|p| format_duration(*p));
-        let p95_str = metrics.latency.percentiles.get(&95).map(|p| format_duration(*p));
-        let p99_str = metrics.latency.percentiles.get(&99).map(|p| format_duration(*p));
+        let percentile_strs: Vec<(String, String)> = metrics
+            .latency
+            .percentiles
+            .iter()
+            .map(|(percentile, latency)| {
+                (format!("  {}th Percentile:", percentile), format_duration(*latency))
+            })
+            .collect();
+        let direction_strs: Vec<(&str, Vec<(&str, String)>)> = [
+            ("Reads", metrics.read.as_ref()),
+            ("Writes", metrics.write.as_ref()),
+        ]
+        .into_iter()
+        .filter_map(|(title, direction)| {
+            let direction = direction?;
+            Some((
+                title,
+                vec![
+                    ("  Data Processed:", format_bytes(direction.bytes_processed)),
+                    ("  Operations:", direction.operations.to_string()),
+                    ("  Throughput:", format_throughput(direction.throughput_mbps)),
+                    ("  IOPS:", format!("{:.0}", direction.iops)),
+                    ("  Avg Latency:", format_duration(direction.latency.avg)),
+                    ("  99th Percentile:", format_duration(direction.latency.p99())),
+                ],
+            ))
+        })
+        .collect();
+        let repetition_strs: Vec<(&str, String)> = result.repetitions.as_ref().map_or_else(Vec::new, |repetitions| {
+            let spread = |stats: &SummaryStats, format: &dyn Fn(f64) -> String| {
+                format!("{} ± {} (CV {:.1}%)", format(stats.mean), format(stats.ci95_margin()), stats.cv * 100.0)
+            };
+            let micros = |us: f64| format_duration(Duration::from_secs_f64(us.max(0.0) / 1e6));
+            let throughput = &repetitions.throughput_mbps;
+            vec![
+                ("  Runs:", repetitions.runs.len().to_string()),
+                ("  Throughput:", spread(throughput, &format_throughput)),
+                ("  Range:", format!("{} - {}", format_throughput(throughput.min), format_throughput(throughput.max))),
+                ("  IOPS:", spread(&repetitions.iops, &|iops| format!("{:.0}", iops))),
+                ("  Avg Latency:", spread(&repetitions.latency_avg_us, &micros)),
+                ("  99th Percentile:", spread(&repetitions.latency_p99_us, &micros)),
+                ("  Target CV:", match (repetitions.target_cv, repetitions.converged()) {
+                    (Some(target), Some(true)) => format!("{:.1}% (met)", target * 100.0),
+                    (Some(target), _) => format!("{:.1}% (not met)", target * 100.0),
+                    (None, _) => "-".to_string(),
+                }),
+                ("  Repeatable:", if repetitions.meets_accuracy { "Yes" } else { "No, outside accuracy threshold" }.to_string()),
+            ]
+        });
 
         let mut final_rows = vec![
             Row::new(vec!["Test Configuration", ""]).style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
@@ -212,12 +311,24 @@ impl ResultsScreen {
             Row::new(vec!["  File Size:", file_size_str.as_str()]),
             Row::new(vec!["  Block Size:", block_size_str.as_str()]),
             Row::new(vec!["  Threads:", threads_str.as_str()]),
+            Row::new(vec!["  Ramp-up:", ramp_time_str.as_str()]),
+            Row::new(vec!["  Access Pattern:", access_str.as_str()]),
+            Row::new(vec!["  Seed:", seed_str.as_str()]),
+            Row::new(vec!["  Target Load:", target_load_str.as_str()]),
+            Row::new(vec!["  I/O Mode:", io_mode_str]).style(if result.used_page_cache() {
+                Style::default().fg(Color::Red)
+            } else {
+                Style::default()
+            }),
             Row::new(vec!["", ""]), // Spacer
             Row::new(vec!["Performance Results", ""]).style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
             Row::new(vec!["  Data Processed:", data_processed_str.as_str()]),
             Row::new(vec!["  Elapsed Time:", elapsed_time_str.as_str()]),
+            Row::new(vec!["  Operations:", operations_str.as_str()]),
             Row::new(vec!["  Throughput:", throughput_str.as_str()]),
             Row::new(vec!["  IOPS:", iops_str.as_str()]),
+            Row::new(vec!["  Intervals:", timeline_str.as_deref().unwrap_or("-")]),
+            Row::new(vec!["  Steady State:", steady_state_str.as_deref().unwrap_or("-")]),
             Row::new(vec!["", ""]), // Spacer
             Row::new(vec!["Latency Statistics", ""]).style(Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
             Row::new(vec!["  Minimum:", min_latency_str.as_str()]),
@@ -226,14 +337,29 @@ impl ResultsScreen {
         ];
 
         // Add percentile data if available
-        if let Some(ref p50) = p50_str {
-            final_rows.push(Row::new(vec!["  50th Percentile:", p50.as_str()]));
+        for (label, latency) in &percentile_strs {
+            final_rows.push(Row::new(vec![label.as_str(), latency.as_str()]));
+        }
+        for (label, value) in &open_loop_strs {
+            final_rows.push(Row::new(vec![*label, value.as_str()]));
         }
-        if let Some(ref p95) = p95_str {
-            final_rows.push(Row::new(vec!["  95th Percentile:", p95.as_str()]));
+
+        // Reads and writes of random and mixed workloads
+        for (title, rows) in &direction_strs {
+            final_rows.push(Row::new(vec!["", ""])); // Spacer
+            final_rows.push(Row::new(vec![*title, ""]).style(Style::default().fg(Color::Blue).add_modifier(Modifier::BOLD)));
+            for (label, value) in rows {
+                final_rows.push(Row::new(vec![*label, value.as_str()]));
+            }
         }
-        if let Some(ref p99) = p99_str {
-            final_rows.push(Row::new(vec!["  99th Percentile:", p99.as_str()]));
+
+        // Spread across repeated runs, shown as mean ± 95% confidence interval
+        if !repetition_strs.is_empty() {
+            final_rows.push(Row::new(vec!["", ""])); // Spacer
+            final_rows.push(Row::new(vec!["Repetitions", ""]).style(Style::default().fg(Color::Blue).add_modifier(Modifier::BOLD)));
+            for (label, value) in &repetition_strs {
+                final_rows.push(Row::new(vec![*label, value.as_str()]));
+            }
         }
 
         // Add timestamp and system info
@@ -243,7 +369,19 @@ impl ResultsScreen {
         let system_info = &result.system_info;
         final_rows.push(Row::new(vec!["  OS:", system_info.os.as_str()]));
         final_rows.push(Row::new(vec!["  CPU:", system_info.cpu.as_str()]));
+        let memory_str = format!("{} total, {} available", format_bytes(system_info.memory_total), format_bytes(system_info.memory_available));
+        if system_info.memory_total > 0 {
+            final_rows.push(Row::new(vec!["  Memory:", memory_str.as_str()]));
+        }
         final_rows.push(Row::new(vec!["  Disk Path:", disk_path_str.as_str()]));
+        let device_str = system_info.storage_info.description();
+        if system_info.storage_info.storage_type.is_some() {
+            final_rows.push(Row::new(vec!["  Device:", device_str.as_str()]));
+        }
+        let filesystem_str = system_info.storage_info.filesystem_description();
+        if system_info.storage_info.total_space > 0 {
+            final_rows.push(Row::new(vec!["  Filesystem:", filesystem_str.as_str()]));
+        }
 
         let table = Table::new(
             final_rows,
@@ -379,17 +517,18 @@ impl Default for ResultsScreen {
 mod tests {
     use super::*;
     use crate::config::{BenchmarkConfig, BenchmarkMode};
-    use crate::models::{LatencyStats, PerformanceMetrics, SystemInfo};
+    use crate::io::DirectIoMode;
+    use crate::models::{LatencyStats, PerformanceMetrics, Percentile, SystemInfo};
     use chrono::Utc;
-    use std::collections::HashMap;
+    use std::collections::BTreeMap;
     use std::path::PathBuf;
     use std::time::Duration;
 
     fn create_test_result() -> BenchmarkResult {
-        let mut percentiles = HashMap::new();
-        percentiles.insert(50, Duration::from_millis(5));
-        percentiles.insert(95, Duration::from_millis(15));
-        percentiles.insert(99, Duration::from_millis(25));
+        let mut percentiles = BTreeMap::new();
+        percentiles.insert(Percentile::P50, Duration::from_millis(5));
+        percentiles.insert(Percentile::P95, Duration::from_millis(15));
+        percentiles.insert(Percentile::P99, Duration::from_millis(25));
 
         BenchmarkResult {
             timestamp: Utc::now(),
@@ -401,6 +540,7 @@ mod tests {
                 duration: Duration::from_secs(30),
                 thread_count: 2,
                 keep_temp_files: false,
+                ..BenchmarkConfig::default()
             },
             metrics: PerformanceMetrics {
                 bytes_processed: 1024 * 1024 * 1024,
@@ -413,14 +553,24 @@ mod tests {
                     max: Duration::from_millis(30),
                     percentiles,
                 },
+                ..Default::default()
             },
             system_info: SystemInfo {
                 os: "Linux".to_string(),
                 cpu: "Test CPU".to_string(),
+                cpu_count: 4,
                 memory_total: 8 * 1024 * 1024 * 1024, // 8 GB
                 memory_available: 4 * 1024 * 1024 * 1024,
                 storage_info: Default::default(),
             },
+            direct_io_mode: Some(DirectIoMode::Direct),
+            engine_fallback: None,
+            queue_depth_source: None,
+            worker_metrics: Vec::new(),
+            timeline: None,
+            steady_state: None,
+            repetitions: None,
+            open_loop: None,
         }
     }
 
@@ -22,6 +22,8 @@ pub struct RunningScreen {
     cancellation_requested: bool,
     /// Error message if benchmark failed
     error_message: Option<String>,
+    /// Current run and total runs when repeating the benchmark
+    repetition: Option<(usize, usize)>,
 }
 
 impl RunningScreen {
@@ -35,6 +37,12 @@ impl RunningScreen {
         self.current_progress = Some(progress);
     }
 
+    /// Move on to run `run` of `total`, clearing the previous run's progress
+    pub fn start_run(&mut self, run: usize, total: usize) {
+        self.current_progress = None;
+        self.repetition = (total > 1).then_some((run, total));
+    }
+
     /// Set an error message
     pub fn set_error(&mut self, error: String) {
         self.error_message = Some(error);
@@ -64,6 +72,13 @@ impl RunningScreen {
         }
     }
 
+    /// Check if the workers are still ramping up
+    pub fn is_ramping_up(&self) -> bool {
+        self.current_progress
+            .as_ref()
+            .is_some_and(|progress| progress.ramp_remaining.is_some())
+    }
+
     /// Check if there's an error
     pub fn has_error(&self) -> bool {
         self.error_message.is_some()
@@ -108,14 +123,18 @@ impl RunningScreen {
 
     /// Render the title section
     fn render_title(&self, f: &mut Frame, area: ratatui::layout::Rect) {
-        let title = if self.cancellation_requested {
-            "Benchmark - Cancelling..."
+        let status = if self.cancellation_requested {
+            "Cancelling..."
         } else if self.has_error() {
-            "Benchmark - Error"
+            "Error"
         } else if self.is_completed() {
-            "Benchmark - Completed"
+            "Completed"
         } else {
-            "Benchmark - Running"
+            "Running"
+        };
+        let title = match self.repetition {
+            Some((run, total)) => format!("Benchmark - {} (run {}/{})", status, run, total),
+            None => format!("Benchmark - {}", status),
         };
 
         let color = if self.has_error() {
@@ -143,18 +162,35 @@ impl RunningScreen {
     /// Render the progress bar
     fn render_progress_bar(&self, f: &mut Frame, area: ratatui::layout::Rect) {
         let (progress_ratio, progress_text) = if let Some(progress) = &self.current_progress {
-            let ratio = progress.completion_percentage();
-            let percentage = (ratio * 100.0) as u16;
-
-            let elapsed_str = format_duration(progress.elapsed);
-            let eta_str = if let Some(eta) = progress.eta {
-                format!(" | ETA: {}", format_duration(eta))
+            if let Some(remaining) = progress.ramp_remaining {
+                let text = format!(
+                    "Ramping up | Measurement starts in {}",
+                    format_duration(remaining)
+                );
+                (0.0, text)
             } else {
-                String::new()
-            };
-
-            let text = format!("{}% | Elapsed: {}{}", percentage, elapsed_str, eta_str);
-            (ratio, text)
+                let ratio = progress.completion_percentage();
+                let percentage = (ratio * 100.0) as u16;
+
+                let elapsed_str = format_duration(progress.elapsed);
+                let eta_str = if let Some(eta) = progress.eta {
+                    format!(" | ETA: {}", format_duration(eta))
+                } else {
+                    String::new()
+                };
+
+                // Time-based runs fill the bar with the clock, not the data processed
+                let basis = if progress.time_fraction.is_some() {
+                    " of duration"
+                } else {
+                    ""
+                };
+                let text = format!(
+                    "{}%{} | Elapsed: {}{}",
+                    percentage, basis, elapsed_str, eta_str
+                );
+                (ratio, text)
+            }
         } else {
             (0.0, "Starting...".to_string())
         };
@@ -175,36 +211,60 @@ impl RunningScreen {
 
     /// Render the metrics table
     fn render_metrics(&self, f: &mut Frame, area: ratatui::layout::Rect) {
-        let (throughput_str, iops_str, data_processed_str, workers_str, elapsed_str) =
-            if let Some(progress) = &self.current_progress {
-                (
-                    format_throughput(progress.avg_throughput_mbps),
-                    format!("{:.0}", progress.total_iops),
+        let (
+            throughput_str,
+            iops_str,
+            data_processed_str,
+            operations_str,
+            workers_str,
+            elapsed_str,
+            measurement_str,
+        ) = if let Some(progress) = &self.current_progress {
+            (
+                format_throughput(progress.avg_throughput_mbps),
+                format!("{:.0}", progress.total_iops),
+                // Time-based runs have no byte target
+                if progress.total_bytes_target == 0 {
+                    format_bytes(progress.total_bytes_processed)
+                } else {
                     format!(
                         "{} / {}",
                         format_bytes(progress.total_bytes_processed),
                         format_bytes(progress.total_bytes_target)
-                    ),
-                    format!("{}", progress.active_workers),
-                    format_duration(progress.elapsed),
-                )
-            } else {
-                (
-                    "0 MB/s".to_string(),
-                    "0".to_string(),
-                    "0 B".to_string(),
-                    "0".to_string(),
-                    "0s".to_string(),
-                )
-            };
+                    )
+                },
+                format!("{}", progress.total_operations),
+                format!("{}", progress.active_workers),
+                format_duration(progress.elapsed),
+                match (progress.ramp_remaining, progress.measurement_started) {
+                    (Some(remaining), _) => {
+                        format!("Ramping up, starts in {}", format_duration(remaining))
+                    }
+                    (None, Some(started)) => format!("Started at {}", format_duration(started)),
+                    (None, None) => "Starting".to_string(),
+                },
+            )
+        } else {
+            (
+                "0 MB/s".to_string(),
+                "0".to_string(),
+                "0 B".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+                "0s".to_string(),
+                String::new(),
+            )
+        };
 
         let rows = if self.current_progress.is_some() {
             vec![
                 Row::new(vec!["Throughput:", &throughput_str]),
                 Row::new(vec!["IOPS:", &iops_str]),
                 Row::new(vec!["Data Processed:", &data_processed_str]),
+                Row::new(vec!["Operations:", &operations_str]),
                 Row::new(vec!["Active Workers:", &workers_str]),
                 Row::new(vec!["Elapsed Time:", &elapsed_str]),
+                Row::new(vec!["Measurement:", &measurement_str]),
             ]
         } else {
             vec![
@@ -268,6 +328,15 @@ impl RunningScreen {
                 ],
                 Style::default().fg(Color::Green),
             )
+        } else if self.is_ramping_up() {
+            (
+                vec![
+                    Line::from(""),
+                    Line::from("Ramping up, I/O is not measured yet..."),
+                    Line::from("Press 'c' to cancel or Esc to go back."),
+                ],
+                Style::default().fg(Color::White),
+            )
         } else {
             (
                 vec![
@@ -366,11 +435,15 @@ mod tests {
         AggregatedProgress {
             total_bytes_processed: if done { 100 } else { 50 },
             total_bytes_target: 100,
+            total_operations: if done { 10 } else { 5 },
+            time_fraction: None,
             avg_throughput_mbps: 10.0,
             total_iops: 100.0,
             elapsed: Duration::from_secs(1),
             eta: None,
             active_workers: 1,
+            ramp_remaining: None,
+            measurement_started: Some(Duration::ZERO),
             worker_progress: Vec::new(),
         }
     }
@@ -381,5 +454,35 @@ mod tests {
         assert!(!screen.is_completed());
         screen.update_progress(sample_progress(true));
         assert!(screen.is_completed());
+
+        // Time-based runs finish with the clock even without a byte target
+        screen.update_progress(AggregatedProgress {
+            total_bytes_target: 0,
+            time_fraction: Some(1.0),
+            ..sample_progress(false)
+        });
+        assert!(screen.is_completed());
+
+        // Ramp-up reports no progress towards completion
+        screen.update_progress(AggregatedProgress {
+            total_bytes_processed: 0,
+            ramp_remaining: Some(Duration::from_secs(2)),
+            measurement_started: None,
+            ..sample_progress(false)
+        });
+        assert!(!screen.is_completed());
+        assert!(screen.is_ramping_up());
+    }
+
+    #[test]
+    fn test_start_run_clears_progress() {
+        let mut screen = RunningScreen::new();
+        screen.update_progress(sample_progress(true));
+        screen.start_run(2, 5);
+        assert!(!screen.is_completed());
+        assert_eq!(screen.repetition, Some((2, 5)));
+
+        screen.start_run(1, 1);
+        assert_eq!(screen.repetition, None);
     }
 }
@@ -22,14 +22,19 @@ pub struct StartScreen {
 }
 
 impl StartScreen {
-    /// Create a new start screen
+    /// Create a new start screen listing the disks detected on this system
     pub fn new() -> Self {
+        Self::with_disks(Self::detect_disks())
+    }
+
+    /// Create a start screen with an explicit disk list
+    pub fn with_disks(mut disks: Vec<PathBuf>) -> Self {
         let mut list_state = ListState::default();
         list_state.select(Some(0));
 
-        // For now, we'll mock the disk list.
-        // In a real application, this would scan the system.
-        let disks = vec![PathBuf::from("C:\\"), PathBuf::from("D:\\")];
+        if disks.is_empty() {
+            disks.push(PathBuf::from("."));
+        }
 
         Self {
             disks,
@@ -250,6 +255,15 @@ impl StartScreen {
 
     /// Render the help text
     fn render_help(&self, f: &mut Frame, area: ratatui::layout::Rect) {
+        let key_style = Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD);
+        let config_hint = if self.show_config_hint {
+            vec![Span::styled("C", key_style), Span::raw(" Configure  ")]
+        } else {
+            Vec::new()
+        };
+
         let help_text = vec![Line::from(vec![
             Span::styled(
                 "↑↓",
@@ -272,6 +286,10 @@ impl StartScreen {
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(" History  "),
+        ]
+        .into_iter()
+        .chain(config_hint)
+        .chain([
             Span::styled(
                 "Q",
                 Style::default()
@@ -279,7 +297,8 @@ impl StartScreen {
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(" Quit"),
-        ])];
+        ])
+        .collect::<Vec<_>>())];
 
         let help = Paragraph::new(help_text)
             .alignment(Alignment::Center)
@@ -304,6 +323,7 @@ impl StartScreen {
                 StartScreenAction::None
             }
             crossterm::event::KeyCode::Enter | crossterm::event::KeyCode::Char(' ') => StartScreenAction::StartTest,
+            crossterm::event::KeyCode::Right | crossterm::event::KeyCode::Char('l') => StartScreenAction::OpenHistory,
             crossterm::event::KeyCode::Char('c') | crossterm::event::KeyCode::Char('C') => StartScreenAction::OpenConfig,
             crossterm::event::KeyCode::Esc => StartScreenAction::Quit,
             _ => StartScreenAction::None,
@@ -316,6 +336,7 @@ impl StartScreen {
 pub enum StartScreenAction {
     None,
     StartTest,
+    OpenHistory,
     OpenConfig,
     Quit,
 }
@@ -330,6 +351,10 @@ impl Default for StartScreen {
 mod tests {
     use super::*;
 
+    fn two_disk_screen() -> StartScreen {
+        StartScreen::with_disks(vec![PathBuf::from("/mnt/a"), PathBuf::from("/mnt/b")])
+    }
+
     #[test]
     fn test_start_screen_creation() {
         let screen = StartScreen::new();
@@ -337,9 +362,15 @@ mod tests {
         assert!(!screen.disks.is_empty());
     }
 
+    #[test]
+    fn test_empty_disk_list_falls_back_to_current_dir() {
+        let screen = StartScreen::with_disks(Vec::new());
+        assert_eq!(screen.selected_disk(), &PathBuf::from("."));
+    }
+
     #[test]
     fn test_menu_navigation() {
-        let mut screen = StartScreen::new();
+        let mut screen = two_disk_screen();
 
         // Test moving down
         screen.select_next();
@@ -352,7 +383,7 @@ mod tests {
 
     #[test]
     fn test_menu_navigation_up() {
-        let mut screen = StartScreen::new();
+        let mut screen = two_disk_screen();
 
         // Test moving up from first item (should wrap to last)
         screen.select_previous();
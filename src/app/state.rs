@@ -6,9 +6,10 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 /// Application screens/states
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub enum AppState {
     /// Main menu screen with Start Test, View Results, Settings, Exit
+    #[default]
     Start,
     /// Configuration screen for benchmark parameters
     Config,
@@ -24,12 +25,6 @@ pub enum AppState {
     Exit,
 }
 
-impl Default for AppState {
-    fn default() -> Self {
-        Self::Start
-    }
-}
-
 /// Navigation actions that can be triggered by keyboard input
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum NavigationAction {
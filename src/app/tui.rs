@@ -4,6 +4,7 @@
 //! and keyboard event processing for the TUI application.
 
 use crossterm::{
+    cursor::Show,
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyEvent, KeyEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
@@ -23,6 +24,16 @@ pub struct Tui {
     should_quit: bool,
     last_tick: Instant,
     tick_rate: Duration,
+    initialized: bool,
+}
+
+/// Leave raw mode and the alternate screen and show the cursor
+///
+/// Used from the panic hook, where the `Tui` instance is not reachable.
+fn restore_terminal() -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, Show)?;
+    Ok(())
 }
 
 impl Tui {
@@ -36,11 +47,20 @@ impl Tui {
             should_quit: false,
             last_tick: Instant::now(),
             tick_rate: Duration::from_millis(250), // 4 FPS for responsive UI
+            initialized: false,
         })
     }
 
     /// Initialize terminal with proper setup
     pub fn init(&mut self) -> io::Result<()> {
+        // Restore the terminal before the default hook prints the panic message
+        let default_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let _ = restore_terminal();
+            default_hook(info);
+        }));
+
+        self.initialized = true;
         enable_raw_mode()?;
         execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
         self.terminal.hide_cursor()?;
@@ -50,15 +70,18 @@ impl Tui {
 
     /// Restore terminal to original state
     pub fn restore(&mut self) -> io::Result<()> {
-        disable_raw_mode()?;
-        execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+        if !self.initialized {
+            return Ok(());
+        }
+        self.initialized = false;
+        restore_terminal()?;
         self.terminal.show_cursor()?;
         Ok(())
     }
 
     /// Get terminal size for responsive layout handling
     pub fn size(&self) -> io::Result<ratatui::layout::Rect> {
-        Ok(self.terminal.size()?)
+        self.terminal.size()
     }
 
     /// Check if terminal meets minimum size requirements (80x24)
@@ -95,11 +118,8 @@ impl Tui {
         if event::poll(timeout)? {
             if let Event::Key(key) = event::read()? {
                 // Filter out key release events and other unwanted events
-                if key.kind == crossterm::event::KeyEventKind::Press {
-                    println!("TUI: Key press detected: {:?}", key);
+                if key.kind == KeyEventKind::Press {
                     return Ok(Some(key));
-                } else {
-                    println!("TUI: Ignoring key event (not press): {:?}", key);
                 }
             }
         }
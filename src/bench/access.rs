@@ -0,0 +1,253 @@
+//! Offset generation for random and mixed workloads
+//!
+//! Turns an `AccessPattern` into block-aligned file offsets, so that
+//! database and cache-tier access skew can be modelled instead of only
+//! uniformly random I/O.
+
+use crate::config::AccessPattern;
+use rand::Rng;
+
+/// Terms of the zeta sum computed exactly before switching to an integral estimate
+const ZETA_EXACT_TERMS: u64 = 1 << 16;
+
+/// Attempts at drawing a normal sample inside the file before clamping
+const NORMAL_MAX_ATTEMPTS: usize = 16;
+
+/// Draws block-aligned offsets following an access pattern
+#[derive(Debug, Clone)]
+pub struct OffsetGenerator {
+    blocks: u64,
+    block_size: u64,
+    sampler: Sampler,
+}
+
+#[derive(Debug, Clone)]
+enum Sampler {
+    Uniform,
+    Zipfian(Zipfian),
+    HotSpot { hot_ops: f64, hot_blocks: u64 },
+    Normal { center: f64, std_dev: f64 },
+}
+
+/// Zipf sampler after Gray et al., "Quickly Generating Billion-Record Synthetic Databases"
+#[derive(Debug, Clone)]
+struct Zipfian {
+    theta: f64,
+    zeta_n: f64,
+    alpha: f64,
+    eta: f64,
+}
+
+impl OffsetGenerator {
+    /// Create a generator over `blocks` blocks of `block_size` bytes
+    ///
+    /// The pattern is expected to have passed `AccessPattern::validate`.
+    pub fn new(pattern: AccessPattern, blocks: u64, block_size: u64) -> Self {
+        let blocks = blocks.max(1);
+        let sampler = match pattern {
+            AccessPattern::Uniform => Sampler::Uniform,
+            AccessPattern::Zipfian { theta } => Sampler::Zipfian(Zipfian::new(blocks, theta)),
+            AccessPattern::HotSpot { hot_ops, hot_data } => Sampler::HotSpot {
+                hot_ops,
+                hot_blocks: ((blocks as f64 * hot_data).round() as u64).clamp(1, blocks),
+            },
+            AccessPattern::Normal { center, std_dev } => Sampler::Normal { center, std_dev },
+        };
+        Self {
+            blocks,
+            block_size,
+            sampler,
+        }
+    }
+
+    /// Number of blocks offsets are drawn from
+    pub fn blocks(&self) -> u64 {
+        self.blocks
+    }
+
+    /// Draw the next block index
+    pub fn next_block<R: Rng>(&self, rng: &mut R) -> u64 {
+        match &self.sampler {
+            Sampler::Uniform => rng.gen_range(0..self.blocks),
+            Sampler::Zipfian(zipf) => zipf.sample(rng, self.blocks),
+            Sampler::HotSpot {
+                hot_ops,
+                hot_blocks,
+            } => {
+                if *hot_blocks >= self.blocks || rng.gen::<f64>() < *hot_ops {
+                    rng.gen_range(0..*hot_blocks)
+                } else {
+                    rng.gen_range(*hot_blocks..self.blocks)
+                }
+            }
+            Sampler::Normal { center, std_dev } => {
+                let mut position = *center;
+                for _ in 0..NORMAL_MAX_ATTEMPTS {
+                    position = center + std_dev * standard_normal(rng);
+                    if (0.0..1.0).contains(&position) {
+                        break;
+                    }
+                }
+                ((position.clamp(0.0, 1.0) * self.blocks as f64) as u64).min(self.blocks - 1)
+            }
+        }
+    }
+
+    /// Draw the next byte offset, always a multiple of the block size
+    pub fn next_offset<R: Rng>(&self, rng: &mut R) -> u64 {
+        self.next_block(rng) * self.block_size
+    }
+}
+
+impl Zipfian {
+    fn new(blocks: u64, theta: f64) -> Self {
+        let zeta_n = zeta(blocks, theta);
+        let zeta_2 = zeta(2, theta);
+        let eta = if blocks > 2 {
+            (1.0 - (2.0 / blocks as f64).powf(1.0 - theta)) / (1.0 - zeta_2 / zeta_n)
+        } else {
+            0.0
+        };
+        Self {
+            theta,
+            zeta_n,
+            alpha: 1.0 / (1.0 - theta),
+            eta,
+        }
+    }
+
+    /// Draw a rank in `0..blocks`, where rank 0 is the most popular
+    fn sample<R: Rng>(&self, rng: &mut R, blocks: u64) -> u64 {
+        let u: f64 = rng.gen();
+        let uz = u * self.zeta_n;
+        if uz < 1.0 || blocks == 1 {
+            return 0;
+        }
+        if uz < 1.0 + 0.5f64.powf(self.theta) || blocks == 2 {
+            return 1;
+        }
+        let rank = blocks as f64 * (self.eta * u - self.eta + 1.0).powf(self.alpha);
+        (rank as u64).min(blocks - 1)
+    }
+}
+
+/// Generalised harmonic number `sum(1 / i^theta)` for `i` in `1..=n`
+///
+/// Large files would need hundreds of millions of terms, so the tail past
+/// `ZETA_EXACT_TERMS` is estimated with the integral of `x^-theta`.
+fn zeta(n: u64, theta: f64) -> f64 {
+    let exact = n.min(ZETA_EXACT_TERMS);
+    let mut sum: f64 = (1..=exact).map(|i| (i as f64).powf(-theta)).sum();
+    if n > exact {
+        let antiderivative = |x: f64| x.powf(1.0 - theta) / (1.0 - theta);
+        sum += antiderivative(n as f64 + 0.5) - antiderivative(exact as f64 + 0.5);
+    }
+    sum
+}
+
+/// Standard normal sample using the Box-Muller transform
+fn standard_normal<R: Rng>(rng: &mut R) -> f64 {
+    // Map [0, 1) to (0, 1] so the logarithm stays finite
+    let u1 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    const BLOCKS: u64 = 1000;
+    const SAMPLES: usize = 100_000;
+
+    fn histogram(pattern: AccessPattern) -> Vec<u64> {
+        let generator = OffsetGenerator::new(pattern, BLOCKS, 4096);
+        let mut rng = SmallRng::seed_from_u64(7);
+        let mut counts = vec![0u64; BLOCKS as usize];
+        for _ in 0..SAMPLES {
+            let offset = generator.next_offset(&mut rng);
+            assert_eq!(offset % 4096, 0);
+            counts[(offset / 4096) as usize] += 1;
+        }
+        counts
+    }
+
+    fn share(counts: &[u64]) -> f64 {
+        counts.iter().sum::<u64>() as f64 / SAMPLES as f64
+    }
+
+    #[test]
+    fn test_uniform_covers_file() {
+        let counts = histogram(AccessPattern::Uniform);
+        assert!((share(&counts[..500]) - 0.5).abs() < 0.02);
+        assert!(counts.iter().all(|&c| c > 0));
+    }
+
+    #[test]
+    fn test_hot_spot_share() {
+        let counts = histogram(AccessPattern::HotSpot {
+            hot_ops: 0.8,
+            hot_data: 0.2,
+        });
+        assert!((share(&counts[..200]) - 0.8).abs() < 0.02);
+        assert!(counts[200..].iter().all(|&c| c > 0));
+    }
+
+    #[test]
+    fn test_zipfian_skew() {
+        let counts = histogram(AccessPattern::Zipfian { theta: 0.99 });
+        // Rank popularity falls off roughly as 1 / rank
+        assert!(counts[0] > counts[1]);
+        assert!(counts[1] > counts[10]);
+        assert!(counts[10] > counts[500]);
+        // With theta 0.99 over 1000 items the top 10% take well over half the accesses
+        assert!(share(&counts[..100]) > 0.6);
+    }
+
+    #[test]
+    fn test_normal_around_center() {
+        let counts = histogram(AccessPattern::Normal {
+            center: 0.25,
+            std_dev: 0.05,
+        });
+        // Two standard deviations either side of the center hold ~95% of samples
+        assert!(share(&counts[150..350]) > 0.93);
+        let mean = counts
+            .iter()
+            .enumerate()
+            .map(|(block, &count)| block as f64 * count as f64)
+            .sum::<f64>()
+            / SAMPLES as f64;
+        assert!((mean - 250.0).abs() < 5.0);
+    }
+
+    #[test]
+    fn test_single_block_file() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        for pattern in [
+            AccessPattern::Uniform,
+            AccessPattern::Zipfian { theta: 0.5 },
+            AccessPattern::HotSpot {
+                hot_ops: 0.9,
+                hot_data: 0.1,
+            },
+            AccessPattern::Normal {
+                center: 1.0,
+                std_dev: 0.5,
+            },
+        ] {
+            let generator = OffsetGenerator::new(pattern, 1, 512);
+            for _ in 0..100 {
+                assert_eq!(generator.next_offset(&mut rng), 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_zeta_tail_estimate() {
+        let n = ZETA_EXACT_TERMS * 4;
+        let exact: f64 = (1..=n).map(|i| (i as f64).powf(-0.9)).sum();
+        assert!((zeta(n, 0.9) - exact).abs() / exact < 1e-4);
+    }
+}
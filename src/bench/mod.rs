@@ -3,11 +3,15 @@
 //! Contains the core benchmarking logic, worker management,
 //! and different benchmark mode implementations.
 
+pub mod access;
 pub mod random;
+pub mod rate;
+pub mod repeat;
 pub mod sequential;
 pub mod worker;
 
 // Re-export commonly used types
+pub use access::OffsetGenerator;
 pub use random::RandomBenchmark;
 pub use sequential::{ProgressUpdate, SequentialBenchmark};
 pub use worker::{AggregatedProgress, WorkerInfo, WorkerManager, WorkerStatus};
@@ -3,16 +3,21 @@
 //! Implements random read/write benchmarks with configurable read ratio
 //! and duration-based execution.
 
+use crate::bench::access::OffsetGenerator;
+use crate::bench::rate::OpenLoop;
 use crate::bench::sequential::ProgressUpdate;
 use crate::{
     config::BenchmarkConfig,
     io::buffer::BufferPool,
     io::disk::{DiskIO, PlatformDiskIO},
-    models::{BenchmarkResult, LatencyStats, PerformanceMetrics},
+    io::engine::{create_engine, IoCompletion, IoEngine, IoOp, IoRequest},
+    models::{
+        BenchmarkResult, DirectionMetrics, LatencyHistogram, PerformanceMetrics,
+        SteadyStateDetector, TimelineRecorder,
+    },
     DIOrbError, Result,
 };
 use rand::{rngs::SmallRng, Rng, SeedableRng};
-use std::io::SeekFrom;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
@@ -29,7 +34,12 @@ impl RandomBenchmark {
     pub fn new(config: BenchmarkConfig) -> Result<Self> {
         config.validate()?;
         let disk_io = PlatformDiskIO::new();
-        let buffer_pool = Arc::new(BufferPool::new(config.block_size as usize, 4)?);
+        let alignment = disk_io.get_alignment(&config.disk_path)?;
+        let buffer_pool = Arc::new(BufferPool::with_alignment(
+            config.block_size as usize,
+            4,
+            alignment,
+        )?);
         Ok(Self {
             config,
             disk_io,
@@ -43,8 +53,6 @@ impl RandomBenchmark {
         read_ratio: f32,
         progress_tx: mpsc::Sender<ProgressUpdate>,
     ) -> Result<BenchmarkResult> {
-        let start_time = Instant::now();
-
         // Create temp file and fill with pattern
         let mut temp_file = self
             .disk_io
@@ -54,82 +62,152 @@ impl RandomBenchmark {
             temp_file.keep_on_drop();
         }
 
-        // Fill file with data so reads are valid
+        // Fill file with whole blocks so reads are valid; direct I/O cannot
+        // transfer partial blocks, so any remainder of the file size is dropped
+        let blocks = self.config.file_size / self.config.block_size;
+        if blocks == 0 {
+            return Err(DIOrbError::ConfigError(
+                "File size must be at least one block for random operations".to_string(),
+            ));
+        }
         let mut buffer = self.buffer_pool.get_buffer().await?;
         let pattern = create_test_pattern(buffer.len());
         buffer.copy_from_slice(&pattern);
-        let mut bytes_written = 0u64;
-        while bytes_written < self.config.file_size {
-            let remaining = self.config.file_size - bytes_written;
-            let write_size = std::cmp::min(remaining, self.config.block_size);
-            let write_buf = &buffer[..write_size as usize];
+        for _ in 0..blocks {
             temp_file
                 .file
-                .write_direct(write_buf)
+                .write_direct(&buffer)
                 .map_err(|e| DIOrbError::BenchmarkError(format!("Write failed: {}", e)))?;
-            bytes_written += write_size;
         }
         temp_file
             .file
             .sync_all()
             .map_err(|e| DIOrbError::BenchmarkError(format!("Sync failed: {}", e)))?;
 
-        let mut read_file = self
-            .disk_io
-            .open_direct_read(temp_file.path())
-            .map_err(|e| DIOrbError::BenchmarkError(format!("Open read failed: {}", e)))?;
-        let mut write_file = self
+        let file = self
             .disk_io
-            .open_direct_write(temp_file.path())
-            .map_err(|e| DIOrbError::BenchmarkError(format!("Open write failed: {}", e)))?;
+            .open_direct_read_write(temp_file.path())
+            .map_err(|e| DIOrbError::BenchmarkError(format!("Open failed: {}", e)))?;
+        let io_mode = file.direct_io_mode();
+        self.config.check_direct_io(io_mode)?;
+        let mut engine = create_engine(
+            self.config.io_engine,
+            file,
+            self.config.block_size as usize,
+            self.queue_depth(),
+            self.buffer_pool.alignment(),
+        )
+        .map_err(|e| DIOrbError::BenchmarkError(format!("Engine setup failed: {}", e)))?;
+
+        let offsets =
+            OffsetGenerator::new(self.config.access_pattern, blocks, self.config.block_size);
+        let seed = self.config.seed.unwrap_or_else(rand::random);
+        let mut rng = SmallRng::seed_from_u64(seed);
+        let mut completions = Vec::with_capacity(engine.queue_depth());
+
+        // Ramp-up: run the load as measured, on its own schedule when rate
+        // limited, but discard whatever completes
+        let ramp_time = self.config.ramp_time;
+        let ramp_start = Instant::now();
+        let mut ramp_loop = self
+            .config
+            .rate_limit
+            .map(|rate| OpenLoop::new(rate, self.config.block_size, seed, ramp_start));
+        let mut last_update = ramp_start;
+        while ramp_start.elapsed() < ramp_time {
+            step(
+                engine.as_mut(),
+                ramp_loop.as_mut(),
+                &offsets,
+                &mut rng,
+                read_ratio,
+                &mut completions,
+            )
+            .await?;
+            for completion in completions.drain(..) {
+                if let Some(ramp_loop) = ramp_loop.as_mut() {
+                    ramp_loop.complete(completion.offset, completion.latency);
+                }
+            }
+
+            if last_update.elapsed() >= Duration::from_millis(200) {
+                let elapsed = ramp_start.elapsed();
+                let update = ProgressUpdate::ramping(
+                    ramp_time.saturating_sub(elapsed),
+                    elapsed,
+                    0,
+                    Some(self.config.run_time_limit()),
+                );
+                if progress_tx.send(update).await.is_err() {
+                    return Err(DIOrbError::CancellationError(
+                        "Receiver dropped".to_string(),
+                    ));
+                }
+                last_update = Instant::now();
+            }
+        }
+        // Drain the ramp-up requests so none straddles the start of the measurement
+        engine
+            .reap(engine.in_flight(), &mut completions)
+            .map_err(|e| DIOrbError::BenchmarkError(format!("I/O failed: {}", e)))?;
+        completions.clear();
+        let ramp_ops = engine.completed_ops();
+        let ramp_busy = engine.busy_time();
 
-        let mut rng = SmallRng::from_entropy();
-        let mut bytes_processed = 0u64;
-        let mut operations = 0u64;
-        let mut latency_samples = Vec::new();
+        let start_time = Instant::now();
+        let mut measured = Measured::new(self.config.timeline_recorder());
+        let mut steady_state = self
+            .config
+            .steady_state
+            .map(|steady| SteadyStateDetector::new(steady, self.config.sample_interval));
         let mut last_update = Instant::now();
-        let duration_ns = self.config.duration.as_nanos();
-
-        while start_time.elapsed() < self.config.duration {
-            let offset = rng.gen_range(0..(self.config.file_size - self.config.block_size));
-            let is_read = rng.gen::<f32>() < read_ratio;
-            let op_start = Instant::now();
-            if is_read {
-                read_file
-                    .seek_direct(SeekFrom::Start(offset))
-                    .map_err(|e| DIOrbError::BenchmarkError(format!("Seek failed: {}", e)))?;
-                read_file
-                    .read_direct(&mut buffer[..self.config.block_size as usize])
-                    .map_err(|e| DIOrbError::BenchmarkError(format!("Read failed: {}", e)))?;
-            } else {
-                write_file
-                    .seek_direct(SeekFrom::Start(offset))
-                    .map_err(|e| DIOrbError::BenchmarkError(format!("Seek failed: {}", e)))?;
-                write_file
-                    .write_direct(&buffer[..self.config.block_size as usize])
-                    .map_err(|e| DIOrbError::BenchmarkError(format!("Write failed: {}", e)))?;
+        let run_limit = self.config.run_time_limit();
+        let duration_ns = run_limit.as_nanos();
+        let mut open_loop = self
+            .config
+            .rate_limit
+            .map(|rate| OpenLoop::new(rate, self.config.block_size, seed, start_time));
+
+        while start_time.elapsed() < run_limit {
+            step(
+                engine.as_mut(),
+                open_loop.as_mut(),
+                &offsets,
+                &mut rng,
+                read_ratio,
+                &mut completions,
+            )
+            .await?;
+            for completion in completions.drain(..) {
+                measured.record(completion, open_loop.as_mut());
+            }
+            // Validation guarantees sampling is on when waiting for steady state
+            if let (Some(detector), Some(timeline)) =
+                (steady_state.as_mut(), measured.timeline.as_ref())
+            {
+                if detector.check(timeline.samples()) {
+                    break;
+                }
             }
-            latency_samples.push(op_start.elapsed());
-            bytes_processed += self.config.block_size;
-            operations += 1;
 
             if last_update.elapsed() >= Duration::from_millis(200) {
                 let elapsed = start_time.elapsed();
                 let ratio = (elapsed.as_nanos() as f64 / duration_ns as f64).min(1.0);
+                let operations = engine.completed_ops() - ramp_ops;
+                let bytes_processed = measured.bytes_processed();
                 let update = ProgressUpdate {
-                    bytes_processed: (ratio * 1000.0) as u64,
-                    total_bytes: 1000,
+                    bytes_processed,
+                    total_bytes: 0,
+                    operations,
+                    time_fraction: Some(ratio),
                     throughput_mbps: (bytes_processed as f64)
                         / (1024.0 * 1024.0)
                         / elapsed.as_secs_f64(),
                     iops: operations as f64 / elapsed.as_secs_f64(),
                     elapsed,
-                    eta: if ratio >= 1.0 {
-                        Some(Duration::ZERO)
-                    } else {
-                        let remaining = self.config.duration - elapsed;
-                        Some(remaining)
-                    },
+                    eta: Some(run_limit.saturating_sub(elapsed)),
+                    ramp_remaining: None,
+                    measurement_start: Some(start_time),
                 };
                 if progress_tx.send(update).await.is_err() {
                     return Err(DIOrbError::CancellationError(
@@ -140,62 +218,211 @@ impl RandomBenchmark {
             }
         }
 
-        let total_elapsed = start_time.elapsed();
-        let latency = if !latency_samples.is_empty() {
-            let mut sorted = latency_samples.clone();
-            sorted.sort();
-            let min = sorted[0];
-            let max = sorted[sorted.len() - 1];
-            let avg = Duration::from_nanos(
-                (sorted.iter().map(|d| d.as_nanos()).sum::<u128>() / sorted.len() as u128) as u64,
-            );
-            let mut percentiles = std::collections::HashMap::new();
-            percentiles.insert(50, sorted[sorted.len() * 50 / 100]);
-            percentiles.insert(95, sorted[sorted.len() * 95 / 100]);
-            percentiles.insert(99, sorted[sorted.len() * 99 / 100]);
-            LatencyStats {
-                min,
-                avg,
-                max,
-                percentiles,
-            }
-        } else {
-            LatencyStats {
-                min: Duration::ZERO,
-                avg: Duration::ZERO,
-                max: Duration::ZERO,
-                percentiles: std::collections::HashMap::new(),
-            }
-        };
+        // Requests still in flight when time ran out count towards the totals
+        engine
+            .reap(engine.in_flight(), &mut completions)
+            .map_err(|e| DIOrbError::BenchmarkError(format!("I/O failed: {}", e)))?;
+        for completion in completions.drain(..) {
+            measured.record(completion, open_loop.as_mut());
+        }
 
+        let total_elapsed = start_time.elapsed();
+        // Little's law over the measured requests only, leaving out the ramp-up
+        let achieved_queue_depth =
+            (engine.busy_time() - ramp_busy).as_secs_f64() / total_elapsed.as_secs_f64();
         let metrics = PerformanceMetrics {
-            bytes_processed,
-            elapsed_time: total_elapsed,
-            throughput_mbps: if total_elapsed.as_secs_f64() > 0.0 {
-                (bytes_processed as f64) / (1024.0 * 1024.0) / total_elapsed.as_secs_f64()
-            } else {
-                0.0
-            },
-            iops: if total_elapsed.as_secs_f64() > 0.0 {
-                operations as f64 / total_elapsed.as_secs_f64()
-            } else {
-                0.0
-            },
-            latency,
+            achieved_queue_depth: Some(achieved_queue_depth),
+            ..PerformanceMetrics::from_directions(
+                total_elapsed,
+                DirectionMetrics::from_histogram(
+                    measured.read_bytes,
+                    total_elapsed,
+                    measured.read_latency,
+                    &self.config.percentiles,
+                ),
+                DirectionMetrics::from_histogram(
+                    measured.write_bytes,
+                    total_elapsed,
+                    measured.write_latency,
+                    &self.config.percentiles,
+                ),
+                &self.config.percentiles,
+            )
         };
+        debug_assert_eq!(metrics.operations, engine.completed_ops() - ramp_ops);
 
         let final_update = ProgressUpdate {
-            bytes_processed: 1000,
-            total_bytes: 1000,
+            bytes_processed: metrics.bytes_processed,
+            total_bytes: 0,
+            operations: metrics.operations,
+            time_fraction: Some(1.0),
             throughput_mbps: metrics.throughput_mbps,
             iops: metrics.iops,
             elapsed: total_elapsed,
             eta: Some(Duration::ZERO),
+            ramp_remaining: None,
+            measurement_start: Some(start_time),
         };
         let _ = progress_tx.send(final_update).await;
 
-        Ok(BenchmarkResult::new(self.config.clone(), metrics))
+        // Record the engine and depth actually used, which may differ after a
+        // fallback, and the seed so the request sequence can be replayed
+        let mut run_config = self.config.clone();
+        run_config.io_engine = engine.kind();
+        run_config.queue_depth = Some(engine.queue_depth());
+        run_config.seed = Some(seed);
+
+        let mut result = BenchmarkResult::new(run_config, metrics).with_direct_io_mode(io_mode);
+        result.timeline = measured.timeline.map(TimelineRecorder::finish);
+        result.steady_state = steady_state.map(|detector| detector.outcome());
+        result.open_loop = open_loop.map(|open_loop| open_loop.summary(&self.config.percentiles));
+        result.engine_fallback = engine.fallback_reason().map(str::to_string);
+        Ok(result)
+    }
+
+    /// Queue depth to request from the engine
+    ///
+    /// The worker manager settles the depth once for all workers; without
+    /// one, requests go one at a time.
+    fn queue_depth(&self) -> usize {
+        if !self.config.io_engine.supports_queue_depth() {
+            return 1;
+        }
+        self.config.queue_depth.unwrap_or(1)
+    }
+}
+
+/// Completed requests of the measured run
+///
+/// Reads and writes are tracked apart since their latencies differ widely.
+struct Measured {
+    read_bytes: u64,
+    write_bytes: u64,
+    read_latency: LatencyHistogram,
+    write_latency: LatencyHistogram,
+    timeline: Option<TimelineRecorder>,
+}
+
+impl Measured {
+    fn new(timeline: Option<TimelineRecorder>) -> Self {
+        Self {
+            read_bytes: 0,
+            write_bytes: 0,
+            read_latency: LatencyHistogram::new(),
+            write_latency: LatencyHistogram::new(),
+            timeline,
+        }
+    }
+
+    /// Count a completed request, correcting its latency when rate limited
+    fn record(&mut self, completion: IoCompletion, open_loop: Option<&mut OpenLoop>) {
+        let latency = match open_loop {
+            Some(open_loop) => open_loop.complete(completion.offset, completion.latency),
+            None => completion.latency,
+        };
+        let bytes = completion.bytes as u64;
+        match completion.op {
+            IoOp::Read => {
+                self.read_latency.record(latency);
+                self.read_bytes += bytes;
+            }
+            IoOp::Write => {
+                self.write_latency.record(latency);
+                self.write_bytes += bytes;
+            }
+        }
+        if let Some(timeline) = self.timeline.as_mut() {
+            timeline.record(bytes, latency);
+        }
+    }
+
+    fn bytes_processed(&self) -> u64 {
+        self.read_bytes + self.write_bytes
+    }
+}
+
+/// Issue the next requests and reap whatever completes
+///
+/// Closed-loop keeps the queue full and waits for at least one request to
+/// finish. Open-loop issues the requests that are due; those that find the
+/// queue full stay due and count their wait as latency.
+async fn step(
+    engine: &mut dyn IoEngine,
+    open_loop: Option<&mut OpenLoop>,
+    offsets: &OffsetGenerator,
+    rng: &mut SmallRng,
+    read_ratio: f32,
+    completions: &mut Vec<IoCompletion>,
+) -> Result<()> {
+    match open_loop {
+        None => {
+            fill_queue(engine, offsets, rng, read_ratio)?;
+            engine
+                .reap(1, completions)
+                .map_err(|e| DIOrbError::BenchmarkError(format!("I/O failed: {}", e)))?;
+        }
+        Some(open_loop) => {
+            issue_due(engine, open_loop, offsets, rng, read_ratio)?;
+            let full = engine.in_flight() >= engine.queue_depth();
+            engine
+                .reap(usize::from(full), completions)
+                .map_err(|e| DIOrbError::BenchmarkError(format!("I/O failed: {}", e)))?;
+            if completions.is_empty() {
+                open_loop.idle(engine.in_flight() > 0).await;
+            }
+        }
     }
+    Ok(())
+}
+
+/// Submit requests until the engine's queue is full
+fn fill_queue(
+    engine: &mut dyn IoEngine,
+    offsets: &OffsetGenerator,
+    rng: &mut SmallRng,
+    read_ratio: f32,
+) -> Result<()> {
+    while engine.in_flight() < engine.queue_depth() {
+        submit_request(engine, offsets, rng, read_ratio)?;
+    }
+    Ok(())
+}
+
+/// Submit the requests that are due, as far as the engine's queue has room
+fn issue_due(
+    engine: &mut dyn IoEngine,
+    open_loop: &mut OpenLoop,
+    offsets: &OffsetGenerator,
+    rng: &mut SmallRng,
+    read_ratio: f32,
+) -> Result<()> {
+    while engine.in_flight() < engine.queue_depth() {
+        let Some(delay) = open_loop.take_due(Instant::now()) else {
+            break;
+        };
+        let offset = submit_request(engine, offsets, rng, read_ratio)?;
+        open_loop.issued(offset, delay);
+    }
+    Ok(())
+}
+
+/// Submit one request at a random offset, returning the offset
+fn submit_request(
+    engine: &mut dyn IoEngine,
+    offsets: &OffsetGenerator,
+    rng: &mut SmallRng,
+    read_ratio: f32,
+) -> Result<u64> {
+    let offset = offsets.next_offset(rng);
+    let op = if rng.gen::<f32>() < read_ratio {
+        IoOp::Read
+    } else {
+        IoOp::Write
+    };
+    engine
+        .submit(IoRequest { op, offset })
+        .map_err(|e| DIOrbError::BenchmarkError(format!("Submit failed: {}", e)))?;
+    Ok(offset)
 }
 
 fn create_test_pattern(size: usize) -> Vec<u8> {
@@ -209,6 +436,7 @@ fn create_test_pattern(size: usize) -> Vec<u8> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{ArrivalProcess, RateLimitConfig, RateTarget, SteadyStateConfig};
     use tempfile::tempdir;
     use tokio::sync::mpsc;
 
@@ -230,6 +458,104 @@ mod tests {
         let result = handle.await.unwrap().unwrap();
         assert!(result.metrics.bytes_processed > 0);
         assert!(!updates.is_empty());
-        assert_eq!(updates.last().unwrap().bytes_processed, 1000);
+        let timeline = result.timeline.as_ref().unwrap();
+        assert_eq!(
+            timeline.samples.iter().map(|s| s.operations).sum::<u64>(),
+            result.metrics.operations
+        );
+        let last = updates.last().unwrap();
+        assert_eq!(last.time_fraction, Some(1.0));
+        assert_eq!(last.bytes_processed, result.metrics.bytes_processed);
+        assert_eq!(last.operations, result.metrics.operations);
+        // Bytes grow with the work done rather than with the clock
+        assert!(updates
+            .windows(2)
+            .all(|w| w[0].bytes_processed <= w[1].bytes_processed));
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_random_benchmark_until_steady() {
+        let temp_dir = tempdir().unwrap();
+        let config = BenchmarkConfig::random_read_write()
+            .with_disk_path(temp_dir.path().to_path_buf())
+            .with_file_size(512 * 1024)
+            .with_sample_interval(Duration::from_millis(50))
+            .with_steady_state(Some(SteadyStateConfig {
+                window: Duration::from_millis(200),
+                tolerance: Some(0.9),
+                max_duration: Duration::from_secs(5),
+                ..SteadyStateConfig::default()
+            }));
+        let bench = RandomBenchmark::new(config).unwrap();
+        let (tx, mut rx) = mpsc::channel(100);
+        let handle = tokio::spawn(async move { bench.run(0.5, tx).await });
+        while rx.recv().await.is_some() {}
+        let result = handle.await.unwrap().unwrap();
+
+        // A loose band settles long before the max duration
+        let steady = result.steady_state.unwrap();
+        assert!(steady.reached());
+        assert!(steady.reached_at.unwrap() >= Duration::from_millis(200));
+        assert!(result.metrics.elapsed_time < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_random_benchmark_at_target_rate() {
+        let temp_dir = tempdir().unwrap();
+        let config = BenchmarkConfig::random_read_write()
+            .with_disk_path(temp_dir.path().to_path_buf())
+            .with_duration(Duration::from_secs(1))
+            .with_file_size(512 * 1024)
+            .with_rate_limit(Some(RateLimitConfig {
+                target: RateTarget::Iops(200.0),
+                arrivals: ArrivalProcess::Poisson,
+            }));
+        let bench = RandomBenchmark::new(config).unwrap();
+        let (tx, mut rx) = mpsc::channel(100);
+        let handle = tokio::spawn(async move { bench.run(0.5, tx).await });
+        while rx.recv().await.is_some() {}
+        let result = handle.await.unwrap().unwrap();
+
+        // A temp file keeps up with 200 IOPS easily, so the run follows the schedule
+        let open_loop = result.open_loop.unwrap();
+        assert_eq!(open_loop.issued, result.metrics.operations);
+        assert!((open_loop.achieved_fraction(result.metrics.iops) - 1.0).abs() < 0.25);
+        // Corrected latency never undercuts the service time
+        assert!(result.metrics.latency.avg >= open_loop.service_latency.avg);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_rate_limited_ramp_follows_schedule() {
+        let temp_dir = tempdir().unwrap();
+        let config = BenchmarkConfig::random_read_write()
+            .with_disk_path(temp_dir.path().to_path_buf())
+            .with_duration(Duration::from_millis(500))
+            .with_ramp_time(Duration::from_millis(500))
+            .with_file_size(512 * 1024)
+            .with_rate_limit(Some(RateLimitConfig {
+                target: RateTarget::Iops(200.0),
+                arrivals: ArrivalProcess::Fixed,
+            }));
+        let bench = RandomBenchmark::new(config).unwrap();
+        let (tx, mut rx) = mpsc::channel(100);
+        let handle = tokio::spawn(async move { bench.run(0.5, tx).await });
+        let mut ramp_updates = 0;
+        while let Some(update) = rx.recv().await {
+            if update.ramp_remaining.is_some() {
+                ramp_updates += 1;
+            }
+        }
+        let result = handle.await.unwrap().unwrap();
+
+        // The ramp runs at the target rate too, so the measurement starts
+        // without a backlog: every request goes out close to when it is due
+        assert!(ramp_updates > 0);
+        let open_loop = result.open_loop.unwrap();
+        assert_eq!(open_loop.issued, result.metrics.operations);
+        assert!(open_loop.max_issue_delay < Duration::from_millis(50));
+        assert!((open_loop.achieved_fraction(result.metrics.iops) - 1.0).abs() < 0.25);
     }
 }
@@ -0,0 +1,272 @@
+//! Open-loop load generation
+//!
+//! A closed-loop benchmark issues the next request when a previous one
+//! finishes, so a device stall also stalls the load and the requests that
+//! would have queued up behind it are never measured. An `OpenLoop` issues
+//! requests on an arrival schedule instead and measures each request's
+//! latency from when it was due, correcting for that coordinated omission.
+
+use crate::config::{ArrivalProcess, RateLimitConfig};
+use crate::models::{LatencyHistogram, OpenLoopSummary, Percentile};
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Longest single sleep while waiting for a request to become due, so
+/// callers still get to check their time limit and report progress
+const MAX_SLEEP: Duration = Duration::from_millis(10);
+
+/// Mixed into the run's seed to seed the arrival schedule
+const ARRIVAL_SEED_MASK: u64 = 0xa076_1d64_78bd_642f;
+
+/// Arrival schedule and latency correction for one worker's rate-limited run
+#[derive(Debug)]
+pub struct OpenLoop {
+    target_iops: f64,
+    arrivals: ArrivalProcess,
+    rng: SmallRng,
+    start: Instant,
+    /// Due time of the next request, relative to `start`
+    next_due: Duration,
+    /// Issue delays of requests in flight, by offset in issue order
+    pending: HashMap<u64, VecDeque<Duration>>,
+    total_issue_delay: Duration,
+    max_issue_delay: Duration,
+    service_latency: LatencyHistogram,
+}
+
+impl OpenLoop {
+    /// Schedule requests of `block_size` bytes from `start` at the configured rate
+    ///
+    /// `seed` is the run's seed and drives Poisson arrivals, so a seeded run
+    /// replays the same schedule.
+    pub fn new(config: RateLimitConfig, block_size: u64, seed: u64, start: Instant) -> Self {
+        Self {
+            target_iops: config.target_iops(block_size),
+            arrivals: config.arrivals,
+            // Kept apart from the stream the run draws offsets from
+            rng: SmallRng::seed_from_u64(seed ^ ARRIVAL_SEED_MASK),
+            start,
+            next_due: Duration::ZERO,
+            pending: HashMap::new(),
+            total_issue_delay: Duration::ZERO,
+            max_issue_delay: Duration::ZERO,
+            service_latency: LatencyHistogram::new(),
+        }
+    }
+
+    /// Time the next request is due
+    pub fn next_due(&self) -> Instant {
+        self.start + self.next_due
+    }
+
+    /// Take the next request if it is due by `now`, returning how late it is
+    pub fn take_due(&mut self, now: Instant) -> Option<Duration> {
+        let due = self.next_due();
+        if due > now {
+            return None;
+        }
+        self.advance();
+        Some(now - due)
+    }
+
+    /// Wait until the next request is due, then take it, returning how late it is
+    ///
+    /// For engines that issue one blocking request at a time, where the
+    /// returned delay is how long the previous request held up this one.
+    /// Sleeps at most `MAX_SLEEP` at a time and yields to the runtime in
+    /// between, so other workers keep running through long gaps at low rates.
+    /// Returns `None` once `cancelled` does.
+    pub async fn wait_for_next(&mut self, cancelled: impl Fn() -> bool) -> Option<Duration> {
+        loop {
+            let now = Instant::now();
+            if let Some(delay) = self.take_due(now) {
+                return Some(delay);
+            }
+            if cancelled() {
+                return None;
+            }
+            std::thread::sleep((self.next_due() - now).min(MAX_SLEEP));
+            tokio::task::yield_now().await;
+        }
+    }
+
+    /// Wait briefly for the next request to become due
+    ///
+    /// Requests in flight can complete at any moment, so with `in_flight`
+    /// set this only yields, keeping completion times accurate for engines
+    /// that timestamp completions when they are reaped.
+    pub async fn idle(&self, in_flight: bool) {
+        if !in_flight {
+            let wait = self.next_due().saturating_duration_since(Instant::now());
+            std::thread::sleep(wait.min(MAX_SLEEP));
+        }
+        tokio::task::yield_now().await;
+    }
+
+    /// Note that the request at `offset` was issued `delay` after it was due
+    pub fn issued(&mut self, offset: u64, delay: Duration) {
+        self.pending.entry(offset).or_default().push_back(delay);
+    }
+
+    /// Correct the latency of a completed request at `offset` to count from when it was due
+    ///
+    /// Requests for the same offset are matched in issue order.
+    pub fn complete(&mut self, offset: u64, latency: Duration) -> Duration {
+        let delay = match self.pending.get_mut(&offset) {
+            Some(delays) => {
+                let delay = delays.pop_front().unwrap_or_default();
+                if delays.is_empty() {
+                    self.pending.remove(&offset);
+                }
+                delay
+            }
+            None => Duration::ZERO,
+        };
+        self.record(delay, latency)
+    }
+
+    /// Record a request issued `delay` after it was due that took `latency`
+    /// once issued, returning the corrected latency
+    pub fn record(&mut self, delay: Duration, latency: Duration) -> Duration {
+        self.total_issue_delay += delay;
+        self.max_issue_delay = self.max_issue_delay.max(delay);
+        self.service_latency.record(latency);
+        delay + latency
+    }
+
+    /// Summarise the run
+    pub fn summary(self, percentiles: &[Percentile]) -> OpenLoopSummary {
+        OpenLoopSummary::new(
+            self.target_iops,
+            self.arrivals,
+            self.total_issue_delay,
+            self.max_issue_delay,
+            self.service_latency,
+            percentiles,
+        )
+    }
+
+    /// Move the schedule on to the following request
+    fn advance(&mut self) {
+        let mean_gap = 1.0 / self.target_iops;
+        let gap = match self.arrivals {
+            ArrivalProcess::Fixed => mean_gap,
+            // Inverse transform sampling of the exponential distribution
+            ArrivalProcess::Poisson => -(1.0 - self.rng.gen::<f64>()).ln() * mean_gap,
+        };
+        self.next_due += Duration::from_secs_f64(gap);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RateTarget;
+
+    fn schedule(iops: f64, arrivals: ArrivalProcess, start: Instant) -> OpenLoop {
+        let config = RateLimitConfig {
+            target: RateTarget::Iops(iops),
+            arrivals,
+        };
+        OpenLoop::new(config, 4096, 42, start)
+    }
+
+    #[test]
+    fn test_fixed_arrivals() {
+        let start = Instant::now();
+        let mut open_loop = schedule(1000.0, ArrivalProcess::Fixed, start);
+
+        // The first request is due straight away, the next one a millisecond later
+        assert_eq!(open_loop.take_due(start), Some(Duration::ZERO));
+        assert_eq!(open_loop.take_due(start), None);
+        assert_eq!(open_loop.next_due(), start + Duration::from_millis(1));
+
+        // Falling behind leaves every missed request due, each later than the last
+        let late = start + Duration::from_millis(5);
+        let delays: Vec<Duration> = std::iter::from_fn(|| open_loop.take_due(late)).collect();
+        assert_eq!(delays.len(), 5);
+        assert_eq!(delays[0], Duration::from_millis(4));
+        assert_eq!(delays[4], Duration::ZERO);
+    }
+
+    #[test]
+    fn test_poisson_arrivals_average_the_target_rate() {
+        let start = Instant::now();
+        let mut open_loop = schedule(1000.0, ArrivalProcess::Poisson, start);
+        let far = start + Duration::from_secs(3600);
+        let mut gaps = Vec::new();
+        let mut last = open_loop.next_due();
+        for _ in 0..10_000 {
+            open_loop.take_due(far).unwrap();
+            gaps.push((open_loop.next_due() - last).as_secs_f64());
+            last = open_loop.next_due();
+        }
+        let mean = gaps.iter().sum::<f64>() / gaps.len() as f64;
+        assert!((mean - 0.001).abs() < 0.00005, "mean gap {}", mean);
+        // Exponential gaps vary, unlike fixed ones
+        assert!(gaps.iter().any(|&gap| gap < 0.0002));
+        assert!(gaps.iter().any(|&gap| gap > 0.003));
+
+        // The same seed gives the same schedule
+        let mut replay = schedule(500.0, ArrivalProcess::Poisson, start);
+        let mut original = schedule(500.0, ArrivalProcess::Poisson, start);
+        for _ in 0..100 {
+            replay.take_due(far);
+            original.take_due(far);
+        }
+        assert_eq!(replay.next_due(), original.next_due());
+    }
+
+    #[test]
+    fn test_latency_counts_from_due_time() {
+        let start = Instant::now();
+        let mut open_loop = schedule(1000.0, ArrivalProcess::Fixed, start);
+        open_loop.issued(4096, Duration::from_micros(300));
+        open_loop.issued(8192, Duration::ZERO);
+        open_loop.issued(4096, Duration::from_micros(700));
+
+        // Out-of-order completions find their own delays
+        assert_eq!(
+            open_loop.complete(8192, Duration::from_micros(100)),
+            Duration::from_micros(100)
+        );
+        assert_eq!(
+            open_loop.complete(4096, Duration::from_micros(100)),
+            Duration::from_micros(400)
+        );
+        assert_eq!(
+            open_loop.complete(4096, Duration::from_micros(100)),
+            Duration::from_micros(800)
+        );
+        assert!(open_loop.pending.is_empty());
+
+        let summary = open_loop.summary(&Percentile::DEFAULTS);
+        assert_eq!(summary.issued, 3);
+        assert_eq!(summary.max_issue_delay, Duration::from_micros(700));
+        assert_eq!(summary.mean_issue_delay, Duration::from_nanos(333_333));
+        assert_eq!(summary.target_iops, 1000.0);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_next() {
+        let start = Instant::now();
+        let mut open_loop = schedule(100.0, ArrivalProcess::Fixed, start);
+        assert!(open_loop.wait_for_next(|| false).await.unwrap() < Duration::from_millis(10));
+        open_loop.wait_for_next(|| false).await.unwrap();
+        // The second request is due 10ms in
+        assert!(start.elapsed() >= Duration::from_millis(10));
+
+        // A 10s gap is abandoned as soon as the run is cancelled
+        let mut slow = schedule(0.1, ArrivalProcess::Fixed, Instant::now());
+        slow.wait_for_next(|| false).await.unwrap();
+        let waiting = Instant::now();
+        assert!(slow.wait_for_next(|| true).await.is_none());
+        let cancelled_at = waiting + Duration::from_millis(30);
+        assert!(slow
+            .wait_for_next(|| Instant::now() >= cancelled_at)
+            .await
+            .is_none());
+        assert!(waiting.elapsed() < Duration::from_secs(1));
+    }
+}
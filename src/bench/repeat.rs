@@ -0,0 +1,219 @@
+//! Repeated benchmark runs
+//!
+//! Runs one configuration several times, either a fixed number of times or
+//! until the throughput of the runs agrees closely enough, and groups the
+//! results into one so repeatability can be judged from the spread.
+
+use crate::config::{BenchmarkConfig, MIN_RUNS_FOR_CV};
+use crate::io::IOMetrics;
+use crate::models::{
+    BenchmarkResult, DirectionMetrics, LatencyHistogram, LatencyStats, OpenLoopSummary,
+    PerformanceMetrics, RepetitionSummary, SummaryStats,
+};
+use crate::{DIOrbError, Result};
+
+/// Check whether `config` asks for another run after `runs`
+pub fn needs_another_run(config: &BenchmarkConfig, runs: &[BenchmarkResult]) -> bool {
+    if runs.len() >= config.repetitions {
+        return false;
+    }
+    let Some(target_cv) = config.target_cv else {
+        return true;
+    };
+    if runs.len() < MIN_RUNS_FOR_CV {
+        return true;
+    }
+    let throughputs: Vec<f64> = runs.iter().map(|r| r.metrics.throughput_mbps).collect();
+    SummaryStats::from_values(&throughputs).is_none_or(|stats| stats.cv > target_cv)
+}
+
+/// Group the results of repeated runs of `config` into one result
+///
+/// A single run is returned unchanged. Otherwise bytes, operations and time
+/// add up over the runs, latency histograms are merged, and each run's
+/// metrics and the spread between them go in `repetitions`. Timelines and
+/// steady-state outcomes describe a single run and are not carried over.
+pub fn combine_runs(
+    config: &BenchmarkConfig,
+    runs: Vec<BenchmarkResult>,
+) -> Result<BenchmarkResult> {
+    if runs.len() <= 1 {
+        return runs
+            .into_iter()
+            .next()
+            .ok_or_else(|| DIOrbError::BenchmarkError("No runs to combine".to_string()));
+    }
+
+    let percentiles = &config.percentiles;
+    let mut bytes_processed = 0u64;
+    let mut operations = 0u64;
+    let mut elapsed = std::time::Duration::ZERO;
+    let mut busy_secs = 0.0;
+    let mut any_queue_depth = false;
+    let mut merged_latency = LatencyHistogram::new();
+    for run in &runs {
+        bytes_processed += run.metrics.bytes_processed;
+        operations += run.metrics.operations;
+        elapsed += run.metrics.elapsed_time;
+        // Runs follow one another, so in-flight averages weigh by run time
+        if let Some(depth) = run.metrics.achieved_queue_depth {
+            busy_secs += depth * run.metrics.elapsed_time.as_secs_f64();
+            any_queue_depth = true;
+        }
+        if let Some(histogram) = &run.metrics.latency_histogram {
+            merged_latency.merge(histogram);
+        }
+    }
+    let totals = IOMetrics::new(bytes_processed, elapsed, operations);
+
+    let metrics = PerformanceMetrics {
+        bytes_processed,
+        elapsed_time: elapsed,
+        throughput_mbps: totals.throughput_mbps,
+        iops: totals.iops,
+        operations,
+        latency: LatencyStats::from_histogram(&merged_latency, percentiles),
+        achieved_queue_depth: any_queue_depth
+            .then(|| busy_secs / elapsed.as_secs_f64().max(f64::EPSILON)),
+        latency_histogram: Some(merged_latency),
+        read: DirectionMetrics::combine(
+            runs.iter().filter_map(|r| r.metrics.read.as_ref()),
+            elapsed,
+            percentiles,
+        ),
+        write: DirectionMetrics::combine(
+            runs.iter().filter_map(|r| r.metrics.write.as_ref()),
+            elapsed,
+            percentiles,
+        ),
+    };
+
+    let (last, others) = runs.split_last().expect("at least two runs");
+    let meets_accuracy = last.meets_accuracy_requirements(others);
+    let direct_io_mode = runs.iter().filter_map(|r| r.direct_io_mode).max();
+    // Runs follow one another at the same rate, so the target is their average
+    let open_loop = OpenLoopSummary::combine(
+        runs.iter().filter_map(|r| r.open_loop.as_ref()),
+        percentiles,
+    )
+    .map(|mut open_loop| {
+        open_loop.target_iops /= runs.len() as f64;
+        open_loop
+    });
+
+    let mut combined = runs[0].clone();
+    // Each run drew its own seed unless one was configured
+    combined.config.seed = config.seed;
+    combined.metrics = metrics;
+    combined.direct_io_mode = direct_io_mode;
+    combined.worker_metrics = Vec::new();
+    combined.timeline = None;
+    combined.steady_state = None;
+    combined.open_loop = open_loop;
+    combined.repetitions = RepetitionSummary::from_runs(
+        runs.into_iter().map(|run| run.metrics).collect(),
+        config.target_cv,
+        meets_accuracy,
+    );
+    Ok(combined)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SystemInfo;
+    use std::time::Duration;
+
+    fn run(throughput_mbps: f64) -> BenchmarkResult {
+        // 100 one-MiB operations at the given rate
+        let elapsed = Duration::from_secs_f64(100.0 / throughput_mbps);
+        let mut histogram = LatencyHistogram::new();
+        for _ in 0..100 {
+            histogram.record(Duration::from_millis(1));
+        }
+        let metrics = PerformanceMetrics::from_histogram(
+            100 * 1024 * 1024,
+            elapsed,
+            histogram,
+            &BenchmarkConfig::default().percentiles,
+        );
+        BenchmarkResult::with_system_info(
+            BenchmarkConfig::default(),
+            metrics,
+            SystemInfo::default(),
+        )
+    }
+
+    #[test]
+    fn test_fixed_repetitions() {
+        let config = BenchmarkConfig::default().with_repetitions(3);
+        assert!(needs_another_run(&config, &[]));
+        assert!(needs_another_run(&config, &[run(100.0), run(100.0)]));
+        assert!(!needs_another_run(
+            &config,
+            &[run(100.0), run(100.0), run(100.0)]
+        ));
+
+        assert!(!needs_another_run(
+            &BenchmarkConfig::default(),
+            &[run(100.0)]
+        ));
+    }
+
+    #[test]
+    fn test_repeat_until_target_cv() {
+        let config = BenchmarkConfig::default()
+            .with_repetitions(10)
+            .with_target_cv(Some(0.02));
+
+        // The CV is only judged once there are enough runs
+        assert!(needs_another_run(&config, &[run(100.0), run(100.0)]));
+        assert!(!needs_another_run(
+            &config,
+            &[run(100.0), run(101.0), run(99.0)]
+        ));
+        assert!(needs_another_run(
+            &config,
+            &[run(100.0), run(120.0), run(80.0)]
+        ));
+
+        // The repetition count caps the runs even if the runs never agree
+        let noisy: Vec<BenchmarkResult> = (0..10).map(|i| run(50.0 + 10.0 * i as f64)).collect();
+        assert!(!needs_another_run(&config, &noisy));
+    }
+
+    #[test]
+    fn test_combine_runs() {
+        let config = BenchmarkConfig::default().with_repetitions(3);
+        let combined = combine_runs(&config, vec![run(100.0), run(200.0), run(100.0)]).unwrap();
+
+        // 300 MiB over 1 + 0.5 + 1 seconds
+        assert_eq!(combined.metrics.bytes_processed, 300 * 1024 * 1024);
+        assert_eq!(combined.metrics.operations, 300);
+        assert_eq!(combined.metrics.elapsed_time, Duration::from_millis(2500));
+        assert!((combined.metrics.throughput_mbps - 120.0).abs() < 1e-9);
+        assert_eq!(
+            combined.metrics.latency_histogram.as_ref().unwrap().count(),
+            300
+        );
+
+        let repetitions = combined.repetitions.unwrap();
+        assert_eq!(repetitions.runs.len(), 3);
+        assert!(repetitions
+            .runs
+            .iter()
+            .all(|m| m.latency_histogram.is_none()));
+        assert!((repetitions.throughput_mbps.mean - 133.333).abs() < 0.001);
+        assert_eq!(repetitions.throughput_mbps.max, 200.0);
+        assert!(!repetitions.meets_accuracy);
+        assert_eq!(repetitions.converged(), None);
+    }
+
+    #[test]
+    fn test_combine_single_run() {
+        let config = BenchmarkConfig::default();
+        let combined = combine_runs(&config, vec![run(100.0)]).unwrap();
+        assert!(combined.repetitions.is_none());
+        assert!(combine_runs(&config, Vec::new()).is_err());
+    }
+}
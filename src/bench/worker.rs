@@ -5,10 +5,13 @@
 //! and cleanup handling, and thread pool coordination for multiple workers.
 
 use crate::bench::sequential::{ProgressUpdate, SequentialBenchmark};
-use crate::config::{BenchmarkConfig, BenchmarkMode};
-use crate::io::buffer::BufferPool;
-use crate::io::disk::PlatformDiskIO;
-use crate::models::{BenchmarkResult, LatencyStats, PerformanceMetrics};
+use crate::config::persistence::ResultsStorage;
+use crate::config::{BenchmarkConfig, BenchmarkMode, QueueDepthSource};
+use crate::io::{detect_storage_type, IOMetrics};
+use crate::models::{
+    BenchmarkResult, DirectionMetrics, LatencyHistogram, LatencyStats, OpenLoopSummary,
+    PerformanceMetrics, SteadyState, Timeline,
+};
 use crate::{DIOrbError, Result};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -76,8 +79,12 @@ impl WorkerInfo {
 pub struct AggregatedProgress {
     /// Total bytes processed across all workers
     pub total_bytes_processed: u64,
-    /// Total bytes to process across all workers
+    /// Total bytes to process across all workers (0 for time-based modes)
     pub total_bytes_target: u64,
+    /// Total operations completed across all workers
+    pub total_operations: u64,
+    /// Share of the configured duration elapsed for time-based modes, from the slowest worker
+    pub time_fraction: Option<f64>,
     /// Average throughput across all workers (MB/s)
     pub avg_throughput_mbps: f64,
     /// Total IOPS across all workers
@@ -88,6 +95,10 @@ pub struct AggregatedProgress {
     pub eta: Option<Duration>,
     /// Number of active workers
     pub active_workers: usize,
+    /// Longest ramp-up time left across workers (`None` once every worker is measuring)
+    pub ramp_remaining: Option<Duration>,
+    /// Time since benchmark start at which the last worker started measuring
+    pub measurement_started: Option<Duration>,
     /// Individual worker progress updates
     pub worker_progress: Vec<ProgressUpdate>,
 }
@@ -95,7 +106,9 @@ pub struct AggregatedProgress {
 impl AggregatedProgress {
     /// Calculate overall completion percentage (0.0 to 1.0)
     pub fn completion_percentage(&self) -> f64 {
-        if self.total_bytes_target == 0 {
+        if let Some(fraction) = self.time_fraction {
+            fraction
+        } else if self.total_bytes_target == 0 {
             0.0
         } else {
             (self.total_bytes_processed as f64) / (self.total_bytes_target as f64)
@@ -106,9 +119,13 @@ impl AggregatedProgress {
 /// Benchmark worker manager for coordinating multiple workers
 pub struct WorkerManager {
     config: BenchmarkConfig,
+    /// Run seed that per-worker seeds are derived from
+    seed: u64,
+    /// Results history searched for a measured queue depth
+    history: Option<ResultsStorage>,
+    /// Where the queue depth shared by the workers came from
+    queue_depth_source: Option<QueueDepthSource>,
     workers: Arc<Mutex<Vec<WorkerInfo>>>,
-    disk_io: PlatformDiskIO,
-    buffer_pool: Arc<BufferPool>,
     start_time: Option<Instant>,
 }
 
@@ -116,24 +133,30 @@ impl WorkerManager {
     /// Create a new worker manager
     pub fn new(config: BenchmarkConfig) -> Result<Self> {
         config.validate()?;
-
-        let disk_io = PlatformDiskIO::new();
-        let buffer_pool = Arc::new(BufferPool::new(config.block_size as usize, 16)?);
+        let seed = config.seed.unwrap_or_else(rand::random);
 
         Ok(Self {
             config,
+            seed,
+            history: None,
+            queue_depth_source: None,
             workers: Arc::new(Mutex::new(Vec::new())),
-            disk_io,
-            buffer_pool,
             start_time: None,
         })
     }
 
+    /// Look up unconfigured queue depths in the saturation runs of `history`
+    pub fn with_history(mut self, history: ResultsStorage) -> Self {
+        self.history = Some(history);
+        self
+    }
+
     /// Start the benchmark with the configured number of workers
     pub async fn start_benchmark(
         &mut self,
         progress_tx: mpsc::Sender<AggregatedProgress>,
     ) -> Result<()> {
+        self.resolve_queue_depth().await;
         self.start_time = Some(Instant::now());
 
         // Initialize workers
@@ -151,6 +174,49 @@ impl WorkerManager {
         Ok(())
     }
 
+    /// Settle one queue depth for all workers of a random or mixed run
+    ///
+    /// A configured depth is kept. Otherwise the knee of the latest saturation
+    /// run in the history on the same path, engine and thread count is used,
+    /// and failing that the guess for the storage type.
+    async fn resolve_queue_depth(&mut self) {
+        if !self.config.mode.uses_duration() || !self.config.io_engine.supports_queue_depth() {
+            return;
+        }
+        if self.config.queue_depth.is_some() {
+            self.queue_depth_source = Some(QueueDepthSource::Configured);
+            return;
+        }
+        let measured = match self.history.clone() {
+            Some(history) => {
+                let config = self.config.clone();
+                tokio::task::spawn_blocking(move || {
+                    history.measured_queue_depth(
+                        &config.disk_path,
+                        config.io_engine,
+                        config.thread_count,
+                    )
+                })
+                .await
+                .ok()
+                .and_then(|measured| measured.ok().flatten())
+            }
+            None => None,
+        };
+        let (depth, source) = match measured {
+            Some(depth) => (depth, QueueDepthSource::Measured),
+            None => {
+                let depth = detect_storage_type(&self.config.disk_path)
+                    .await
+                    .map(|storage| storage.optimal_queue_depth())
+                    .unwrap_or(1);
+                (depth, QueueDepthSource::StorageType)
+            }
+        };
+        self.config.queue_depth = Some(depth);
+        self.queue_depth_source = Some(source);
+    }
+
     /// Spawn worker tasks based on benchmark mode
     async fn spawn_workers(&self, progress_tx: mpsc::Sender<AggregatedProgress>) -> Result<()> {
         let mut workers = self.workers.lock().await;
@@ -244,6 +310,7 @@ impl WorkerManager {
         if self.config.thread_count > 1 {
             worker_config.file_size = self.config.file_size / self.config.thread_count as u64;
         }
+        worker_config.seed = Some(worker_seed(self.seed, worker_id));
         let benchmark = crate::bench::random::RandomBenchmark::new(worker_config)?;
 
         let handle = tokio::spawn(async move {
@@ -273,6 +340,7 @@ impl WorkerManager {
         if self.config.thread_count > 1 {
             worker_config.file_size = self.config.file_size / self.config.thread_count as u64;
         }
+        worker_config.seed = Some(worker_seed(self.seed, worker_id));
         let benchmark = crate::bench::random::RandomBenchmark::new(worker_config)?;
 
         let handle = tokio::spawn(async move {
@@ -352,20 +420,36 @@ impl WorkerManager {
     ) -> AggregatedProgress {
         let mut total_bytes_processed = 0u64;
         let mut total_bytes_target = 0u64;
+        let mut total_operations = 0u64;
+        let mut time_fraction: Option<f64> = None;
+        let mut time_eta: Option<Duration> = None;
         let mut total_throughput = 0.0;
         let mut total_iops = 0.0;
         let mut active_workers = 0;
+        let mut ramp_remaining: Option<Duration> = None;
+        // Measurement only counts as started once every worker has reported it
+        let mut measurement_start = Some(start_time);
         let mut valid_progress = Vec::new();
 
-        for progress_opt in worker_progress {
-            if let Some(progress) = progress_opt {
-                total_bytes_processed += progress.bytes_processed;
-                total_bytes_target += progress.total_bytes;
-                total_throughput += progress.throughput_mbps;
-                total_iops += progress.iops;
-                active_workers += 1;
-                valid_progress.push(progress.clone());
+        for progress in worker_progress.iter().flatten() {
+            total_bytes_processed += progress.bytes_processed;
+            total_bytes_target += progress.total_bytes;
+            total_operations += progress.operations;
+            if let Some(fraction) = progress.time_fraction {
+                time_fraction = Some(time_fraction.map_or(fraction, |f| f.min(fraction)));
+                time_eta = time_eta.max(progress.eta);
             }
+            total_throughput += progress.throughput_mbps;
+            total_iops += progress.iops;
+            active_workers += 1;
+            ramp_remaining = ramp_remaining.max(progress.ramp_remaining);
+            measurement_start = measurement_start
+                .zip(progress.measurement_start)
+                .map(|(a, b)| a.max(b));
+            valid_progress.push(progress.clone());
+        }
+        if active_workers < worker_progress.len() {
+            measurement_start = None;
         }
 
         let elapsed = start_time.elapsed();
@@ -375,7 +459,10 @@ impl WorkerManager {
             0.0
         };
 
-        let eta = if total_bytes_processed > 0 && total_throughput > 0.0 {
+        // Time-based runs end with their duration, whatever the byte rate
+        let eta = if time_fraction.is_some() {
+            time_eta
+        } else if total_bytes_processed > 0 && total_throughput > 0.0 {
             let remaining_bytes = total_bytes_target.saturating_sub(total_bytes_processed);
             let rate_bytes_per_sec = total_throughput * 1024.0 * 1024.0; // Convert MB/s to bytes/s
             Some(Duration::from_secs_f64(
@@ -388,15 +475,25 @@ impl WorkerManager {
         AggregatedProgress {
             total_bytes_processed,
             total_bytes_target,
+            total_operations,
+            time_fraction,
             avg_throughput_mbps,
             total_iops,
             elapsed,
             eta,
             active_workers,
+            ramp_remaining,
+            measurement_started: measurement_start
+                .map(|start| start.saturating_duration_since(start_time)),
             worker_progress: valid_progress,
         }
     }
 
+    /// Seed of this run, recorded in the combined result
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
     /// Cancel all running workers
     pub async fn cancel_all(&self) -> Result<()> {
         let mut workers = self.workers.lock().await;
@@ -443,6 +540,14 @@ impl WorkerManager {
         Ok(results)
     }
 
+    /// Check whether every worker task has exited, without collecting results
+    pub async fn workers_finished(&self) -> bool {
+        let workers = self.workers.lock().await;
+        workers
+            .iter()
+            .all(|w| w.handle.as_ref().is_none_or(|h| h.is_finished()))
+    }
+
     /// Get current worker statuses
     pub async fn get_worker_statuses(&self) -> Vec<(usize, WorkerStatus)> {
         let workers = self.workers.lock().await;
@@ -469,95 +574,111 @@ impl WorkerManager {
             ));
         }
 
-        // Use the first result as the base
+        // Use the first result as the base, but with the run's configuration:
+        // workers only see their share of the file, and the engine and depth
+        // they record are the ones actually used after any fallback
         let mut combined = results[0].clone();
+        combined.config = BenchmarkConfig {
+            io_engine: combined.config.io_engine,
+            queue_depth: combined.config.queue_depth,
+            ..self.config.clone()
+        };
 
         // Aggregate metrics from all workers
-        let mut total_bytes = 0u64;
-        let mut max_elapsed = Duration::ZERO;
-        let mut all_latency_samples = Vec::new();
+        let mut io_metrics = Vec::with_capacity(results.len());
+        let mut merged_latency = LatencyHistogram::new();
+        let mut achieved_queue_depth: Option<f64> = None;
+        // A single buffered worker is enough to make the combined figure suspect
+        let direct_io_mode = results.iter().filter_map(|r| r.direct_io_mode).max();
 
         for result in &results {
-            total_bytes += result.metrics.bytes_processed;
-            max_elapsed = max_elapsed.max(result.metrics.elapsed_time);
-
-            // Collect latency samples (simplified - in real implementation would need actual samples)
-            all_latency_samples.push(result.metrics.latency.min);
-            all_latency_samples.push(result.metrics.latency.avg);
-            all_latency_samples.push(result.metrics.latency.max);
-        }
-
-        // Calculate combined latency stats
-        all_latency_samples.sort();
-        let combined_latency = if !all_latency_samples.is_empty() {
-            let min = all_latency_samples[0];
-            let max = all_latency_samples[all_latency_samples.len() - 1];
-            let avg = Duration::from_nanos(
-                (all_latency_samples
-                    .iter()
-                    .map(|d| d.as_nanos())
-                    .sum::<u128>()
-                    / all_latency_samples.len() as u128) as u64,
-            );
-
-            let mut percentiles = std::collections::HashMap::new();
-            percentiles.insert(
-                50,
-                all_latency_samples[all_latency_samples.len() * 50 / 100],
-            );
-            percentiles.insert(
-                95,
-                all_latency_samples[all_latency_samples.len() * 95 / 100],
-            );
-            percentiles.insert(
-                99,
-                all_latency_samples[all_latency_samples.len() * 99 / 100],
-            );
-
-            LatencyStats {
-                min,
-                avg,
-                max,
-                percentiles,
+            io_metrics.push(IOMetrics::new(
+                result.metrics.bytes_processed,
+                result.metrics.elapsed_time,
+                result.metrics.operations,
+            ));
+            // Workers run side by side, so their in-flight averages add up
+            if let Some(depth) = result.metrics.achieved_queue_depth {
+                *achieved_queue_depth.get_or_insert(0.0) += depth;
             }
-        } else {
-            LatencyStats {
-                min: Duration::ZERO,
-                avg: Duration::ZERO,
-                max: Duration::ZERO,
-                percentiles: std::collections::HashMap::new(),
+
+            // Percentiles only combine through the full distributions
+            if let Some(histogram) = &result.metrics.latency_histogram {
+                merged_latency.merge(histogram);
             }
-        };
+        }
 
-        // Calculate combined performance metrics
-        let elapsed_secs = max_elapsed.as_secs_f64();
-        let throughput_mbps = if elapsed_secs > 0.0 {
-            (total_bytes as f64) / (1024.0 * 1024.0) / elapsed_secs
-        } else {
-            0.0
-        };
+        let combined_latency =
+            LatencyStats::from_histogram(&merged_latency, &self.config.percentiles);
 
-        let iops = if elapsed_secs > 0.0 && !combined_latency.avg.is_zero() {
-            (total_bytes as f64 / self.config.block_size as f64) / elapsed_secs
-        } else {
-            0.0
-        };
+        // Completed operations and bytes add up over the longest worker's
+        // wall time, so short final blocks and queue depth don't skew IOPS
+        let totals = IOMetrics::combine(&io_metrics);
+        let max_elapsed = totals.elapsed_time;
 
         combined.metrics = PerformanceMetrics {
-            bytes_processed: total_bytes,
+            bytes_processed: totals.bytes_processed,
             elapsed_time: max_elapsed,
-            throughput_mbps,
-            iops,
+            throughput_mbps: totals.throughput_mbps,
+            iops: totals.iops,
+            operations: totals.operations_count,
             latency: combined_latency,
+            achieved_queue_depth,
+            latency_histogram: Some(merged_latency),
+            read: DirectionMetrics::combine(
+                results.iter().filter_map(|r| r.metrics.read.as_ref()),
+                max_elapsed,
+                &self.config.percentiles,
+            ),
+            write: DirectionMetrics::combine(
+                results.iter().filter_map(|r| r.metrics.write.as_ref()),
+                max_elapsed,
+                &self.config.percentiles,
+            ),
         };
+        combined.timeline = Timeline::combine(results.iter().filter_map(|r| r.timeline.as_ref()));
+        combined.steady_state =
+            SteadyState::combine(results.iter().filter_map(|r| r.steady_state.as_ref()));
+        // Each worker runs at the target rate, so the combined target is their sum
+        combined.open_loop = OpenLoopSummary::combine(
+            results.iter().filter_map(|r| r.open_loop.as_ref()),
+            &self.config.percentiles,
+        );
+        // The merged histograms hold every worker's distribution, so the
+        // breakdown only keeps each worker's summary
+        if results.len() > 1 {
+            combined.worker_metrics = results
+                .into_iter()
+                .map(|result| result.metrics.without_histograms())
+                .collect();
+        }
+        combined.direct_io_mode = direct_io_mode;
+        combined.queue_depth_source = self.queue_depth_source;
+        // Worker seeds are derived from the run seed, so it alone replays the run
+        if self.config.mode.uses_duration() {
+            combined.config.seed = Some(self.seed);
+        }
 
         Ok(combined)
     }
 }
 
+/// Derive a distinct, reproducible seed for one worker from the run seed
+///
+/// Uses the SplitMix64 finaliser so that neighbouring worker ids produce
+/// unrelated random streams.
+fn worker_seed(seed: u64, worker_id: usize) -> u64 {
+    let mut z = seed.wrapping_add((worker_id as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{IoEngineKind, SaturationConfig};
+    use crate::models::{Percentile, SaturationResult, SaturationStep, SystemInfo};
     use tempfile::tempdir;
     use tokio::time::timeout;
 
@@ -751,19 +872,27 @@ mod tests {
         let progress1 = ProgressUpdate {
             bytes_processed: 500,
             total_bytes: 1000,
+            operations: 5,
+            time_fraction: None,
             throughput_mbps: 10.0,
             iops: 100.0,
             elapsed: Duration::from_secs(1),
             eta: Some(Duration::from_secs(1)),
+            ramp_remaining: None,
+            measurement_start: Some(start_time),
         };
 
         let progress2 = ProgressUpdate {
             bytes_processed: 750,
             total_bytes: 1000,
+            operations: 8,
+            time_fraction: None,
             throughput_mbps: 15.0,
             iops: 150.0,
             elapsed: Duration::from_secs(1),
             eta: Some(Duration::from_secs(1)),
+            ramp_remaining: None,
+            measurement_start: Some(start_time),
         };
 
         let worker_progress = vec![Some(progress1), Some(progress2), None];
@@ -775,6 +904,291 @@ mod tests {
         assert_eq!(aggregated.active_workers, 2);
         assert_eq!(aggregated.avg_throughput_mbps, 12.5); // (10 + 15) / 2
         assert_eq!(aggregated.completion_percentage(), 0.625); // 1250 / 2000
+        assert_eq!(aggregated.total_operations, 13);
+        // The third worker has not reported yet
+        assert_eq!(aggregated.measurement_started, None);
+    }
+
+    #[test]
+    fn test_aggregated_progress_ramp_up() {
+        let start_time = Instant::now();
+        let ramping = ProgressUpdate::ramping(
+            Duration::from_secs(2),
+            Duration::from_secs(1),
+            0,
+            Some(Duration::from_secs(10)),
+        );
+        let measuring = ProgressUpdate {
+            bytes_processed: 4096,
+            operations: 1,
+            time_fraction: Some(0.1),
+            ramp_remaining: None,
+            measurement_start: Some(start_time + Duration::from_secs(3)),
+            ..ramping.clone()
+        };
+
+        let aggregated = WorkerManager::aggregate_progress(
+            &[Some(ramping), Some(measuring.clone())],
+            start_time,
+        );
+        assert_eq!(aggregated.ramp_remaining, Some(Duration::from_secs(2)));
+        assert_eq!(aggregated.measurement_started, None);
+        assert_eq!(aggregated.completion_percentage(), 0.0);
+
+        // Measurement starts with the last worker to finish its ramp-up
+        let later = ProgressUpdate {
+            measurement_start: Some(start_time + Duration::from_secs(4)),
+            ..measuring.clone()
+        };
+        let aggregated =
+            WorkerManager::aggregate_progress(&[Some(measuring), Some(later)], start_time);
+        assert_eq!(aggregated.ramp_remaining, None);
+        assert_eq!(aggregated.measurement_started, Some(Duration::from_secs(4)));
+    }
+
+    #[test]
+    fn test_aggregated_progress_time_based() {
+        let timed = |bytes_processed, time_fraction, eta_secs| ProgressUpdate {
+            bytes_processed,
+            total_bytes: 0,
+            operations: bytes_processed / 4096,
+            time_fraction: Some(time_fraction),
+            throughput_mbps: 10.0,
+            iops: 100.0,
+            elapsed: Duration::from_secs(1),
+            eta: Some(Duration::from_secs(eta_secs)),
+            ramp_remaining: None,
+            measurement_start: Some(Instant::now()),
+        };
+
+        let worker_progress = vec![Some(timed(40960, 0.5, 2)), Some(timed(8192, 0.25, 3))];
+        let aggregated = WorkerManager::aggregate_progress(&worker_progress, Instant::now());
+
+        // Real bytes and ops, with completion following the slowest worker's clock
+        assert_eq!(aggregated.total_bytes_processed, 49152);
+        assert_eq!(aggregated.total_operations, 12);
+        assert_eq!(aggregated.time_fraction, Some(0.25));
+        assert_eq!(aggregated.completion_percentage(), 0.25);
+        assert_eq!(aggregated.eta, Some(Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn test_worker_seeds() {
+        let seeds: Vec<u64> = (0..8).map(|id| worker_seed(42, id)).collect();
+        assert_eq!(
+            seeds,
+            (0..8).map(|id| worker_seed(42, id)).collect::<Vec<_>>()
+        );
+        for (i, a) in seeds.iter().enumerate() {
+            assert!(seeds[i + 1..].iter().all(|b| a != b));
+        }
+        assert_ne!(worker_seed(42, 0), worker_seed(43, 0));
+
+        let temp_dir = tempdir().unwrap();
+        let config = BenchmarkConfig::random_read_write()
+            .with_disk_path(temp_dir.path().to_path_buf())
+            .with_seed(7);
+        assert_eq!(WorkerManager::new(config).unwrap().seed(), 7);
+    }
+
+    #[test]
+    fn test_combine_results_merges_latency() {
+        let temp_dir = tempdir().unwrap();
+        let config = BenchmarkConfig::random_read_write()
+            .with_disk_path(temp_dir.path().to_path_buf())
+            .with_thread_count(2);
+        let manager = WorkerManager::new(config.clone()).unwrap();
+
+        // One fast worker and one with a slow tail
+        let mut fast = LatencyHistogram::new();
+        let mut slow = LatencyHistogram::new();
+        for _ in 0..900 {
+            fast.record(Duration::from_micros(100));
+        }
+        for _ in 0..100 {
+            slow.record(Duration::from_millis(10));
+        }
+        let elapsed = Duration::from_secs(1);
+        let results = vec![
+            BenchmarkResult::new(
+                config.clone(),
+                PerformanceMetrics::from_histogram(
+                    900 * 4096,
+                    elapsed,
+                    fast,
+                    &Percentile::DEFAULTS,
+                ),
+            ),
+            BenchmarkResult::new(
+                config,
+                PerformanceMetrics::from_histogram(
+                    100 * 4096,
+                    elapsed,
+                    slow,
+                    &Percentile::DEFAULTS,
+                ),
+            ),
+        ];
+
+        let combined = manager.combine_results(results).unwrap();
+        let latency = &combined.metrics.latency;
+        assert_eq!(
+            combined.metrics.latency_histogram.as_ref().unwrap().count(),
+            1000
+        );
+        assert_eq!(combined.metrics.operations, 1000);
+        assert_eq!(latency.min, Duration::from_micros(100));
+        assert_eq!(latency.max, Duration::from_millis(10));
+        // 10% of all operations are slow, so p95 and p99 both land in the tail
+        assert!(latency.p99() >= Duration::from_millis(9));
+        assert!(latency.p95() >= Duration::from_millis(9));
+        assert!(latency.percentiles[&Percentile::P50] < Duration::from_micros(101));
+
+        assert_eq!(combined.worker_metrics.len(), 2);
+        assert!(combined
+            .worker_metrics
+            .iter()
+            .all(|m| m.latency_histogram.is_none()));
+        assert_eq!(
+            combined.worker_metrics[1].latency.max,
+            Duration::from_millis(10)
+        );
+    }
+
+    #[test]
+    fn test_combine_results_counts_operations() {
+        let temp_dir = tempdir().unwrap();
+        let config = BenchmarkConfig::sequential_write()
+            .with_disk_path(temp_dir.path().to_path_buf())
+            .with_block_size(4096)
+            .with_thread_count(2);
+        let manager = WorkerManager::new(config.clone()).unwrap();
+        let latency = LatencyStats::new(
+            Duration::from_micros(50),
+            Duration::from_micros(100),
+            Duration::from_micros(200),
+        );
+
+        // The second worker ends on a short block, so bytes / block size would
+        // undercount its operations
+        let results = vec![
+            BenchmarkResult::new(
+                config.clone(),
+                PerformanceMetrics::new(10 * 4096, Duration::from_secs(1), 10, latency.clone()),
+            ),
+            BenchmarkResult::new(
+                config,
+                PerformanceMetrics::new(9 * 4096 + 100, Duration::from_secs(2), 10, latency),
+            ),
+        ];
+
+        let combined = manager.combine_results(results).unwrap();
+        assert_eq!(combined.metrics.operations, 20);
+        assert_eq!(combined.metrics.elapsed_time, Duration::from_secs(2));
+        assert!((combined.metrics.iops - 10.0).abs() < 0.001);
+        assert_eq!(combined.metrics.bytes_processed, 19 * 4096 + 100);
+    }
+
+    #[test]
+    fn test_combine_results_keeps_run_config() {
+        let config = BenchmarkConfig::random_read_write()
+            .with_file_size(64 * 1024 * 1024)
+            .with_thread_count(4)
+            .with_io_engine(IoEngineKind::IoUring);
+        let manager = WorkerManager::new(config.clone()).unwrap();
+
+        // Each worker ran over a quarter of the file, after falling back to sync
+        let mut worker_config = config.clone().with_io_engine(IoEngineKind::Sync);
+        worker_config.file_size = config.file_size / 4;
+        worker_config.queue_depth = Some(1);
+        let results = (0..4)
+            .map(|worker_id| {
+                let mut worker_config = worker_config.clone();
+                worker_config.seed = Some(worker_seed(manager.seed, worker_id));
+                let metrics = PerformanceMetrics::new(
+                    4096,
+                    Duration::from_secs(1),
+                    1,
+                    LatencyStats::default(),
+                );
+                BenchmarkResult::with_system_info(worker_config, metrics, SystemInfo::default())
+            })
+            .collect();
+
+        let combined = manager.combine_results(results).unwrap();
+        assert_eq!(combined.config.file_size, 64 * 1024 * 1024);
+        assert_eq!(combined.config.thread_count, 4);
+        assert_eq!(combined.config.seed, Some(manager.seed));
+        assert_eq!(combined.config.io_engine, IoEngineKind::Sync);
+        assert_eq!(combined.config.queue_depth, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_queue_depth_resolution() {
+        let temp_dir = tempdir().unwrap();
+        let history = ResultsStorage::with_path(temp_dir.path().join("results.json"));
+        let config = BenchmarkConfig::random_read_write()
+            .with_disk_path(temp_dir.path().to_path_buf())
+            .with_thread_count(2)
+            .with_io_engine(IoEngineKind::ThreadPool);
+        let resolve = |config: BenchmarkConfig| {
+            let history = history.clone();
+            async move {
+                let mut manager = WorkerManager::new(config).unwrap().with_history(history);
+                manager.resolve_queue_depth().await;
+                (manager.config.queue_depth, manager.queue_depth_source)
+            }
+        };
+
+        // Without a saturation run the storage type decides
+        let (depth, source) = resolve(config.clone()).await;
+        assert!(depth.is_some());
+        assert_eq!(source, Some(QueueDepthSource::StorageType));
+
+        // A knee measured with the same thread count wins, latency being flat
+        let saturation = SaturationConfig::queue_depths(vec![1, 16]);
+        let steps = saturation
+            .expand(&config)
+            .into_iter()
+            .map(|step| {
+                let level = step.queue_depth.unwrap() as f64;
+                let metrics = PerformanceMetrics::new(
+                    4096,
+                    Duration::from_secs(1),
+                    1,
+                    LatencyStats::default(),
+                );
+                SaturationStep {
+                    level,
+                    result: BenchmarkResult::with_system_info(step, metrics, SystemInfo::default()),
+                }
+            })
+            .collect();
+        history
+            .append_saturation(SaturationResult::new(config.clone(), saturation, steps))
+            .unwrap();
+        assert_eq!(
+            resolve(config.clone()).await,
+            (Some(16), Some(QueueDepthSource::Measured))
+        );
+        let (_, source) = resolve(config.clone().with_thread_count(4)).await;
+        assert_eq!(source, Some(QueueDepthSource::StorageType));
+
+        // An explicit depth is kept, and sequential and sync runs have none
+        let mut configured = config.clone();
+        configured.queue_depth = Some(4);
+        assert_eq!(
+            resolve(configured).await,
+            (Some(4), Some(QueueDepthSource::Configured))
+        );
+        assert_eq!(
+            resolve(config.clone().with_io_engine(IoEngineKind::Sync)).await,
+            (None, None)
+        );
+        assert_eq!(
+            resolve(BenchmarkConfig::sequential_read()).await,
+            (None, None)
+        );
     }
 
     #[test]
@@ -801,28 +1215,28 @@ mod tests {
 
     #[tokio::test]
     #[ignore]
-    async fn test_unsupported_benchmark_modes() {
+    async fn test_random_and_mixed_modes() {
         let temp_dir = tempdir().unwrap();
 
-        // Test random mode (not implemented yet)
-        let config =
-            BenchmarkConfig::random_read_write().with_disk_path(temp_dir.path().to_path_buf());
-
-        let mut manager = WorkerManager::new(config).unwrap();
-        let (progress_tx, _progress_rx) = mpsc::channel(100);
-
-        manager.start_benchmark(progress_tx).await.unwrap();
-        let result = manager.wait_for_completion().await;
-        assert!(result.is_err());
-
-        // Test mixed mode (not implemented yet)
-        let config = BenchmarkConfig::mixed(0.7).with_disk_path(temp_dir.path().to_path_buf());
-
-        let mut manager = WorkerManager::new(config).unwrap();
-        let (progress_tx, _progress_rx) = mpsc::channel(100);
-
-        manager.start_benchmark(progress_tx).await.unwrap();
-        let result = manager.wait_for_completion().await;
-        assert!(result.is_err());
+        for config in [
+            BenchmarkConfig::random_read_write(),
+            BenchmarkConfig::mixed(0.7),
+        ] {
+            let config = config
+                .with_disk_path(temp_dir.path().to_path_buf())
+                .with_file_size(1024 * 1024)
+                .with_duration(Duration::from_millis(300))
+                .with_thread_count(2);
+            let mut manager = WorkerManager::new(config).unwrap();
+            let (progress_tx, _progress_rx) = mpsc::channel(100);
+
+            manager.start_benchmark(progress_tx).await.unwrap();
+            let results = manager.wait_for_completion().await.unwrap();
+            assert_eq!(results.len(), 2);
+            let combined = manager.combine_results(results).unwrap();
+            assert!(combined.metrics.operations > 0);
+            assert!(combined.metrics.bytes_processed > 0);
+            assert_eq!(combined.config.file_size, 1024 * 1024);
+        }
     }
 }
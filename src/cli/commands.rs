@@ -0,0 +1,710 @@
+//! Command execution for the non-interactive CLI
+
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use super::{
+    AutotuneArgs, Command, ConfigCommand, HistoryArgs, RunArgs, SaturateArgs, SweepArgs, USAGE,
+};
+use crate::app::App;
+use crate::bench::repeat;
+use crate::bench::worker::{AggregatedProgress, WorkerManager};
+use crate::config::persistence::ResultsStorage;
+use crate::config::{
+    BenchmarkConfig, ConfigFile, LoadLadder, Profile, SweepConfig, SweepPoint, WorkloadTuning,
+};
+use crate::models::{
+    BenchmarkResult, RepetitionSummary, SaturationResult, SaturationStep, SweepEntry, SweepMatrix,
+    SweepMetric, SweepResult,
+};
+use crate::util::units::{
+    format_bytes, format_duration, format_iops, format_latency, format_throughput,
+};
+use crate::{DIOrbError, Result};
+
+/// Execute a parsed command
+///
+/// `Command::Interactive` is handled by the binary, since the prompt flow
+/// lives there; it is rejected here.
+pub async fn execute(command: Command) -> Result<()> {
+    match command {
+        Command::Tui => tui().await,
+        Command::Run(args) => run(*args).await,
+        Command::Sweep(args) => sweep(*args).await,
+        Command::Saturate(args) => saturate(*args).await,
+        Command::Autotune(args) => autotune(*args).await,
+        Command::History(args) => history(args),
+        Command::Config(sub) => config(sub),
+        Command::Help => {
+            print!("{}", USAGE);
+            Ok(())
+        }
+        Command::Version => {
+            println!("{} {}", crate::APP_NAME, env!("CARGO_PKG_VERSION"));
+            Ok(())
+        }
+        Command::Interactive => Err(DIOrbError::ConfigError(
+            "Interactive mode is not available from the CLI executor".to_string(),
+        )),
+    }
+}
+
+async fn tui() -> Result<()> {
+    let mut app = App::new()?;
+    app.init()?;
+    // Dropping the app restores the terminal before any error is reported
+    app.run().await
+}
+
+async fn run(args: RunArgs) -> Result<()> {
+    let config = args.overrides.resolve(&ConfigFile::load()?)?;
+    args.overrides.check_engine(&config)?;
+    args.overrides.check_arrivals(&config)?;
+    config.validate()?;
+
+    let result = run_benchmark(config).await?;
+
+    // Progress and diagnostics go to stderr, so stdout only carries the result
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else {
+        print_result(&result);
+    }
+
+    if let Some(path) = &args.output {
+        std::fs::write(path, serde_json::to_string_pretty(&result)?)?;
+    }
+
+    if args.save {
+        ResultsStorage::new()?.append_result(result)?;
+    }
+
+    Ok(())
+}
+
+async fn sweep(args: SweepArgs) -> Result<()> {
+    let base = args.run.overrides.resolve(&ConfigFile::load()?)?;
+    args.run.overrides.check_engine(&base)?;
+    args.run.overrides.check_arrivals(&base)?;
+    args.sweep.validate(&base)?;
+
+    let result = run_sweep(base, args.sweep).await?;
+
+    if args.run.json {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else {
+        let metrics = match args.metric {
+            Some(metric) => vec![metric],
+            None => vec![SweepMetric::Throughput, SweepMetric::Iops],
+        };
+        for metric in metrics {
+            for matrix in result.matrices(metric) {
+                print_matrix(&matrix);
+            }
+        }
+    }
+
+    if let Some(path) = &args.run.output {
+        std::fs::write(path, serde_json::to_string_pretty(&result)?)?;
+    }
+
+    if args.run.save {
+        ResultsStorage::new()?.append_sweep(result)?;
+    }
+
+    Ok(())
+}
+
+async fn saturate(args: SaturateArgs) -> Result<()> {
+    let base = args.run.overrides.resolve(&ConfigFile::load()?)?;
+    args.run.overrides.check_engine(&base)?;
+    let saturation = args.saturation;
+    // A rate ladder supplies the rate that arrivals shape
+    let arrivals = args.run.overrides.arrivals;
+    if !matches!(saturation.ladder, LoadLadder::TargetIops(_)) {
+        args.run.overrides.check_arrivals(&base)?;
+    }
+    saturation.validate(&base)?;
+
+    let ladder = &saturation.ladder;
+    let mut configs = saturation.expand(&base);
+    for config in &mut configs {
+        if let (Some(arrivals), Some(rate)) = (arrivals, config.rate_limit.as_mut()) {
+            rate.arrivals = arrivals;
+        }
+    }
+    let total = configs.len();
+    let mut steps = Vec::with_capacity(total);
+    for (index, config) in configs.into_iter().enumerate() {
+        let level = ladder.level(index).unwrap_or_default();
+        eprintln!(
+            "[{}/{}] {} {}",
+            index + 1,
+            total,
+            ladder.description(),
+            ladder.format_level(level)
+        );
+        let step = SaturationStep {
+            level,
+            result: run_benchmark(config).await?,
+        };
+        let p99 = step.p99();
+        steps.push(step);
+        if saturation.passes_limit(p99) {
+            eprintln!(
+                "p99 latency {} passed the {} limit, stopping",
+                format_latency(p99),
+                format_latency(saturation.latency_limit)
+            );
+            break;
+        }
+    }
+    let result = SaturationResult::new(base, saturation, steps);
+
+    if args.run.json {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else {
+        print_saturation(&result);
+    }
+
+    if let Some(path) = &args.run.output {
+        std::fs::write(path, serde_json::to_string_pretty(&result)?)?;
+    }
+
+    if args.run.save {
+        if let Some(depth) = result.measured_queue_depth() {
+            if !args.run.json {
+                println!(
+                    "Saved: runs on this path with the {} engine, {} threads and no --queue-depth now use queue depth {}",
+                    result.base.io_engine.description(),
+                    result.base.thread_count,
+                    depth
+                );
+            }
+        }
+        ResultsStorage::new()?.append_saturation(result)?;
+    }
+
+    Ok(())
+}
+
+async fn autotune(args: AutotuneArgs) -> Result<()> {
+    let mut file = ConfigFile::load()?;
+    let config = args.overrides.resolve(&file)?;
+    args.overrides.check_arrivals(&config)?;
+    let autotune = args.autotune;
+    autotune.validate(&config)?;
+
+    eprintln!("Probing sequential reads on {}", config.disk_path.display());
+    let base = autotune.sequential_base(&config);
+    let sequential = run_sweep(base, autotune.sequential.clone()).await?;
+    eprintln!(
+        "Probing random reads and writes on {}",
+        config.disk_path.display()
+    );
+    let base = autotune.random_base(&config);
+    let sweep = autotune.random_sweep(&base);
+    let random = run_sweep(base, sweep).await?;
+
+    for result in [&sequential, &random] {
+        for matrix in result.matrices(SweepMetric::Throughput) {
+            print_matrix(&matrix);
+        }
+    }
+
+    let profile = Profile {
+        disk_path: config.disk_path.clone(),
+        io_engine: config.io_engine,
+        sequential: autotune.recommend(&sequential),
+        random: autotune.recommend(&random),
+    };
+    println!("Recommended settings for {}", profile.disk_path.display());
+    print_tuning("Sequential", profile.sequential.as_ref());
+    print_tuning("Random", profile.random.as_ref());
+
+    if args.save {
+        file.profiles.insert(args.name.clone(), profile);
+        file.save()?;
+        println!(
+            "Saved profile {} to {}; use it with --profile {}",
+            args.name,
+            BenchmarkConfig::config_file_path()?.display(),
+            args.name
+        );
+    }
+
+    Ok(())
+}
+
+/// Run every configuration of a validated sweep, one after another
+async fn run_sweep(base: BenchmarkConfig, sweep: SweepConfig) -> Result<SweepResult> {
+    let parameters = sweep.parameters();
+    let configs = sweep.expand(&base);
+    let total = configs.len();
+    let mut entries = Vec::with_capacity(total);
+    for (index, config) in configs.into_iter().enumerate() {
+        let point = SweepPoint::of(&config);
+        eprintln!(
+            "[{}/{}] {}",
+            index + 1,
+            total,
+            point.description(&parameters)
+        );
+        let result = run_benchmark(config).await?;
+        entries.push(SweepEntry { point, result });
+    }
+    Ok(SweepResult::new(base, sweep, entries))
+}
+
+/// Run a benchmark with all configured workers, showing a progress bar
+///
+/// Repeated runs happen one after another and are grouped into one result.
+pub async fn run_benchmark(config: BenchmarkConfig) -> Result<BenchmarkResult> {
+    let mut runs = Vec::new();
+    while repeat::needs_another_run(&config, &runs) {
+        let label = (config.repetitions > 1)
+            .then(|| format!("run {}/{} | ", runs.len() + 1, config.repetitions));
+        runs.push(run_once(config.clone(), label.unwrap_or_default()).await?);
+    }
+    repeat::combine_runs(&config, runs)
+}
+
+/// Run the benchmark once, prefixing progress messages with `label`
+async fn run_once(config: BenchmarkConfig, label: String) -> Result<BenchmarkResult> {
+    let mut manager = WorkerManager::new(config)?.with_history(ResultsStorage::new()?);
+    let (tx, mut rx) = mpsc::channel::<AggregatedProgress>(100);
+
+    let pb = indicatif::ProgressBar::new(1000);
+    pb.set_style(
+        indicatif::ProgressStyle::with_template(
+            "{spinner} [{bar:30}] {percent:>3}% {elapsed} {msg}",
+        )
+        .unwrap()
+        .progress_chars("=> "),
+    );
+
+    let progress_bar = pb.clone();
+    let progress = tokio::spawn(async move {
+        while let Some(update) = rx.recv().await {
+            let fraction = update.completion_percentage().clamp(0.0, 1.0);
+            progress_bar.set_position((fraction * 1000.0) as u64);
+            if let Some(remaining) = update.ramp_remaining {
+                progress_bar.set_message(format!(
+                    "{}ramping up, measuring in {}",
+                    label,
+                    format_duration(remaining)
+                ));
+                continue;
+            }
+            progress_bar.set_message(format!(
+                "{}{} | {} | {}",
+                label,
+                format_bytes(update.total_bytes_processed),
+                format_throughput(update.avg_throughput_mbps),
+                format_iops(update.total_iops)
+            ));
+        }
+    });
+
+    manager.start_benchmark(tx).await?;
+    let results = manager.wait_for_completion().await;
+
+    // The aggregator keeps polling until every worker reports completion,
+    // so stop listening explicitly once the workers have been joined.
+    progress.abort();
+    pb.finish_and_clear();
+
+    manager.combine_results(results?)
+}
+
+/// Print a human-readable report for a single result
+fn print_result(result: &BenchmarkResult) {
+    let metrics = &result.metrics;
+    let latency = &metrics.latency;
+
+    println!("Mode:       {}", result.config.mode.description());
+    println!("Path:       {}", result.config.disk_path.display());
+    let storage = &result.system_info.storage_info;
+    if storage.storage_type.is_some() {
+        println!("Device:     {}", storage.description());
+    }
+    if storage.total_space > 0 {
+        println!("Filesystem: {}", storage.filesystem_description());
+    }
+    if result.config.mode.uses_duration() {
+        println!("Access:     {}", result.config.access_pattern.description());
+        if let Some(seed) = result.config.seed {
+            println!("Seed:       {}", seed);
+        }
+    }
+    if let Some(mode) = result.direct_io_mode {
+        println!("I/O mode:   {}", mode.description());
+    }
+    if !result.config.ramp_time.is_zero() {
+        println!(
+            "Ramp-up:    {} (not measured)",
+            format_duration(result.config.ramp_time)
+        );
+    }
+    println!(
+        "Processed:  {} in {} ({} ops)",
+        format_bytes(metrics.bytes_processed),
+        format_duration(metrics.elapsed_time),
+        metrics.operations
+    );
+    println!("Throughput: {}", format_throughput(metrics.throughput_mbps));
+    println!("IOPS:       {}", format_iops(metrics.iops));
+    if let (Some(achieved), Some(configured)) =
+        (metrics.achieved_queue_depth, result.config.queue_depth)
+    {
+        // The achieved depth is summed over workers, so compare against the total
+        println!(
+            "Queue:      {} engine, {:.1} achieved / {} configured",
+            result.config.io_engine.description(),
+            achieved,
+            configured * result.config.thread_count
+        );
+        if let Some(source) = result.queue_depth_source {
+            println!(
+                "Depth:      {} per worker, {}",
+                configured,
+                source.description()
+            );
+        }
+    }
+    if let Some(reason) = &result.engine_fallback {
+        println!("Fallback:   sync engine used, {}", reason);
+    }
+    println!(
+        "Latency:    min {} / avg {} / max {}",
+        format_latency(latency.min),
+        format_latency(latency.avg),
+        format_latency(latency.max)
+    );
+    if !latency.percentiles.is_empty() {
+        let percentiles: Vec<String> = latency
+            .percentiles
+            .iter()
+            .map(|(percentile, value)| format!("{} {}", percentile.label(), format_latency(*value)))
+            .collect();
+        println!("            {}", percentiles.join(" / "));
+    }
+    if let Some(open_loop) = &result.open_loop {
+        // The latencies above count from when each request was due
+        println!(
+            "Load:       {} target ({}), {:.1}% achieved, issued late avg {} / max {}",
+            format_iops(open_loop.target_iops),
+            open_loop.arrivals.description().to_lowercase(),
+            open_loop.achieved_fraction(metrics.iops) * 100.0,
+            format_latency(open_loop.mean_issue_delay),
+            format_latency(open_loop.max_issue_delay)
+        );
+        println!(
+            "Service:    avg {} / p99 {} (from issue, without correction)",
+            format_latency(open_loop.service_latency.avg),
+            format_latency(open_loop.service_latency.p99())
+        );
+    }
+    if let Some(timeline) = &result.timeline {
+        if let Some((min, max)) = timeline.throughput_range() {
+            println!(
+                "Intervals:  {} x {}, throughput {} - {}",
+                timeline.samples.len(),
+                format_duration(timeline.interval),
+                format_throughput(min),
+                format_throughput(max)
+            );
+        }
+    }
+    if let Some(steady) = &result.steady_state {
+        println!(
+            "Steady:     {} ({} within {:.1}% over {})",
+            steady.description(),
+            steady.metric.description(),
+            steady.tolerance * 100.0,
+            format_duration(steady.window)
+        );
+    }
+    if let Some(repetitions) = &result.repetitions {
+        print_repetitions(repetitions);
+    }
+    for (label, direction) in [("Reads:", &metrics.read), ("Writes:", &metrics.write)] {
+        if let Some(direction) = direction {
+            println!(
+                "{:<11} {} / {} / avg {} / p99 {}",
+                label,
+                format_throughput(direction.throughput_mbps),
+                format_iops(direction.iops),
+                format_latency(direction.latency.avg),
+                format_latency(direction.latency.p99())
+            );
+        }
+    }
+    for (worker_id, worker) in result.worker_metrics.iter().enumerate() {
+        println!(
+            "  Worker {}: {} / {} / p99 {}",
+            worker_id,
+            format_throughput(worker.throughput_mbps),
+            format_iops(worker.iops),
+            format_latency(worker.latency.p99())
+        );
+    }
+}
+
+/// Print the spread of repeated runs
+fn print_repetitions(repetitions: &RepetitionSummary) {
+    let throughput = &repetitions.throughput_mbps;
+    let iops = &repetitions.iops;
+    let latency = &repetitions.latency_avg_us;
+    let micros = |us: f64| format_latency(Duration::from_secs_f64(us.max(0.0) / 1e6));
+    let convergence = match repetitions.converged() {
+        Some(true) => ", target CV met",
+        Some(false) => ", target CV not met",
+        None => "",
+    };
+    println!(
+        "Runs:       {}, throughput CV {:.1}%{}{}",
+        repetitions.runs.len(),
+        throughput.cv * 100.0,
+        convergence,
+        if repetitions.meets_accuracy {
+            ""
+        } else {
+            " (outside accuracy threshold)"
+        }
+    );
+    println!(
+        "  Throughput: {} ± {} (95% CI), range {} - {}",
+        format_throughput(throughput.mean),
+        format_throughput(throughput.ci95_margin()),
+        format_throughput(throughput.min),
+        format_throughput(throughput.max)
+    );
+    println!(
+        "  IOPS:       {} ± {} (95% CI), CV {:.1}%",
+        format_iops(iops.mean),
+        format_iops(iops.ci95_margin()),
+        iops.cv * 100.0
+    );
+    println!(
+        "  Latency:    avg {} ± {} (95% CI), CV {:.1}%",
+        micros(latency.mean),
+        micros(latency.ci95_margin()),
+        latency.cv * 100.0
+    );
+}
+
+/// Print a sweep matrix as an aligned table
+fn print_matrix(matrix: &SweepMatrix) {
+    let (corner, header): (String, Vec<String>) = match matrix.column_parameter {
+        Some(column) => (
+            format!(
+                "{} \\ {}",
+                matrix.row_parameter.description(),
+                column.description()
+            ),
+            matrix
+                .columns
+                .iter()
+                .map(|&value| column.format_value(value))
+                .collect(),
+        ),
+        None => (
+            matrix.row_parameter.description().to_string(),
+            vec![matrix.metric.description().to_string()],
+        ),
+    };
+    let rows: Vec<(String, Vec<String>)> = matrix
+        .rows
+        .iter()
+        .zip(&matrix.cells)
+        .map(|(&row, cells)| {
+            let cells = cells
+                .iter()
+                .map(|cell| cell.map_or_else(|| "-".to_string(), |v| matrix.metric.format(v)))
+                .collect();
+            (matrix.row_parameter.format_value(row), cells)
+        })
+        .collect();
+
+    let width = |text: &String| text.chars().count();
+    let label_width = rows
+        .iter()
+        .map(|(label, _)| width(label))
+        .chain([width(&corner)])
+        .max()
+        .unwrap_or(0);
+    let widths: Vec<usize> = header
+        .iter()
+        .enumerate()
+        .map(|(column, title)| {
+            rows.iter()
+                .map(|(_, cells)| width(&cells[column]))
+                .chain([width(title)])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let print_row = |label: &String, cells: &[String]| {
+        let cells: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{:>width$}", cell))
+            .collect();
+        println!("  {:<label_width$}  {}", label, cells.join("  "));
+    };
+
+    println!("{}", matrix.title());
+    print_row(&corner, &header);
+    for (label, cells) in &rows {
+        print_row(label, cells);
+    }
+    println!();
+}
+
+/// Print the steps of a saturation run as a table, marking the knee
+fn print_saturation(result: &SaturationResult) {
+    let ladder = &result.config.ladder;
+    let header = [
+        ladder.description().to_string(),
+        "Throughput".to_string(),
+        "IOPS".to_string(),
+        "p50".to_string(),
+        "p99".to_string(),
+    ];
+    let rows: Vec<[String; 5]> = result
+        .steps
+        .iter()
+        .map(|step| {
+            [
+                ladder.format_level(step.level),
+                format_throughput(step.result.metrics.throughput_mbps),
+                format_iops(step.iops()),
+                format_latency(step.p50()),
+                format_latency(step.p99()),
+            ]
+        })
+        .collect();
+    let widths: Vec<usize> = (0..header.len())
+        .map(|column| {
+            rows.iter()
+                .chain([&header])
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let format_row = |row: &[String; 5]| {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{:>width$}", cell))
+            .collect();
+        format!("  {}", cells.join("  "))
+    };
+
+    println!("{} saturation", result.base.mode.description());
+    println!("{}", format_row(&header));
+    let knee = result.knee.map(|knee| knee.step);
+    for (index, row) in rows.iter().enumerate() {
+        let marker = if Some(index) == knee { "  <- knee" } else { "" };
+        println!("{}{}", format_row(row), marker);
+    }
+    println!();
+
+    match (result.knee, result.knee_step()) {
+        (Some(knee), Some(step)) => println!(
+            "Knee:       {} {}, {} at p99 {} ({})",
+            ladder.description(),
+            ladder.format_level(step.level),
+            format_iops(step.iops()),
+            format_latency(step.p99()),
+            knee.reason.description()
+        ),
+        _ => println!(
+            "Knee:       none, p99 latency passed {} at the lowest level",
+            format_latency(result.config.latency_limit)
+        ),
+    }
+}
+
+/// Print one workload's recommended settings
+fn print_tuning(workload: &str, tuning: Option<&WorkloadTuning>) {
+    let Some(tuning) = tuning else {
+        println!("  {:<11} no probe finished", format!("{}:", workload));
+        return;
+    };
+    let queue_depth = tuning
+        .queue_depth
+        .map(|depth| format!(", queue depth {}", depth))
+        .unwrap_or_default();
+    println!(
+        "  {:<11} {} blocks, {} threads{} ({}, {})",
+        format!("{}:", workload),
+        format_bytes(tuning.block_size),
+        tuning.thread_count,
+        queue_depth,
+        format_throughput(tuning.throughput_mbps),
+        format_iops(tuning.iops)
+    );
+}
+
+fn history(args: HistoryArgs) -> Result<()> {
+    let storage = ResultsStorage::new()?;
+    let results = match args.limit {
+        Some(limit) => storage.get_recent_results(limit)?,
+        None => storage.load_results()?,
+    };
+    let sweeps = storage.load_sweeps()?;
+    let saturations = storage.load_saturations()?;
+
+    if results.is_empty() && sweeps.is_empty() && saturations.is_empty() {
+        println!("No saved results");
+        return Ok(());
+    }
+
+    for result in &results {
+        println!("{}", result.summary());
+    }
+    // Sweeps and saturation runs are grouped entries listed after the single results
+    let skip = args
+        .limit
+        .map_or(0, |limit| sweeps.len().saturating_sub(limit));
+    for sweep in &sweeps[skip..] {
+        println!("{}", sweep.summary());
+    }
+    let skip = args
+        .limit
+        .map_or(0, |limit| saturations.len().saturating_sub(limit));
+    for saturation in &saturations[skip..] {
+        println!("{}", saturation.summary());
+    }
+    Ok(())
+}
+
+fn config(command: ConfigCommand) -> Result<()> {
+    match command {
+        ConfigCommand::Show => {
+            let file = ConfigFile::load()?;
+            print!("{}", toml::to_string_pretty(&file)?);
+        }
+        ConfigCommand::Path => {
+            println!("{}", BenchmarkConfig::config_file_path()?.display());
+        }
+        ConfigCommand::Set(overrides) => {
+            let config = overrides.resolve(&ConfigFile::load()?)?;
+            overrides.check_arrivals(&config)?;
+            config.save()?;
+            println!(
+                "Saved configuration to {}",
+                BenchmarkConfig::config_file_path()?.display()
+            );
+        }
+        ConfigCommand::Reset => {
+            BenchmarkConfig::default().save()?;
+            println!("Restored default configuration");
+        }
+    }
+    Ok(())
+}
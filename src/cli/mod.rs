@@ -0,0 +1,1636 @@
+//! Command-line interface module
+//!
+//! Parses non-interactive subcommands and flags so benchmarks can be
+//! scripted from CI jobs or over SSH without answering prompts.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::config::{
+    AccessPattern, ArrivalProcess, AutotuneConfig, BenchmarkConfig, BenchmarkMode, ConfigFile,
+    IoEngineKind, LoadLadder, RateLimitConfig, RateTarget, SaturationConfig, SteadyStateMetric,
+    SweepConfig, MIN_RUNS_FOR_CV,
+};
+use crate::models::{Percentile, SweepMetric};
+use crate::util::units::{parse_bytes, parse_duration};
+use crate::{DIOrbError, Result};
+
+pub mod commands;
+
+pub use commands::execute;
+
+/// Run cap when `--target-cv` is given without `--repetitions`
+const DEFAULT_MAX_REPETITIONS: usize = 10;
+
+/// Profile name `diorb autotune` saves to when `--name` is not given
+pub const DEFAULT_PROFILE_NAME: &str = "autotune";
+
+/// Usage text printed for `diorb --help`
+pub const USAGE: &str = "\
+diorb - Disk IO Rust Bench
+
+USAGE:
+    diorb                      Start the interactive prompt
+    diorb tui                  Start the full-screen terminal UI
+    diorb run [OPTIONS]        Run a benchmark non-interactively
+    diorb sweep [OPTIONS]      Run a benchmark over a grid of parameters
+    diorb saturate [OPTIONS]   Raise load step by step to find the latency knee
+    diorb autotune [OPTIONS]   Probe block sizes and concurrency and save the best as a profile
+    diorb history [--limit N]  List saved benchmark results
+    diorb config [SUBCOMMAND]  Show or change the saved configuration
+
+RUN OPTIONS:
+    -p, --path <DIR>           Directory to benchmark
+    -m, --mode <MODE>          seq-write, seq-read, random or mixed
+        --read-ratio <RATIO>   Read ratio for mixed mode (0.0 - 1.0, implies mixed)
+    -s, --file-size <SIZE>     Test file size, e.g. 1GiB
+    -b, --block-size <SIZE>    I/O block size, e.g. 4KiB
+    -d, --duration <TIME>      Duration for time-based modes, e.g. 30s
+        --ramp-time <TIME>     Warm-up time excluded from measurements, e.g. 5s
+        --steady-window <TIME> Run random/mixed modes until steady over this window,
+                               e.g. 30s, or off (the --steady-* flags imply it)
+        --steady-tolerance <PERCENT>
+                               Allowed deviation from the window mean (default: by speed)
+        --steady-max <TIME>    Give up on steady state after this long (default: 10m)
+        --steady-metric <M>    Metric that has to settle: throughput or iops
+        --repetitions <N>      Run the benchmark N times and summarize the spread
+        --target-cv <PERCENT>  Stop repeating once throughput varies less than this,
+                               e.g. 2% (up to --repetitions runs, default 10)
+        --rate <RATE>          Issue requests at a fixed rate per worker instead of as
+                               fast as possible, e.g. 5000iops, 200MiB/s, or off
+        --arrivals <PROCESS>   Spacing of rate-limited requests: fixed or poisson
+    -t, --threads <N>          Number of concurrent workers
+        --profile <NAME>       Take block size, threads and queue depth for the mode
+                               from a profile saved by autotune (flags still win)
+        --keep-temp-files      Keep the test file after the run
+        --no-keep-temp-files   Delete the test file even if the saved config keeps it
+        --engine <ENGINE>      I/O engine for random/mixed modes: sync, io-uring or thread-pool
+        --queue-depth <N>      Requests in flight per worker (default: by storage type)
+        --strict-direct-io     Fail instead of falling back to buffered I/O
+        --access <PATTERN>     Offset distribution for random/mixed modes: uniform,
+                               zipf[:THETA], hotspot[:OPS/DATA] or normal[:CENTER,STDDEV]
+        --seed <N>             Seed for random/mixed modes, to replay a saved run
+        --percentiles <LIST>   Latency percentiles to report, e.g. 50,99,99.9,99.99
+        --sample-interval <TIME>
+                               Timeline sampling interval, e.g. 100ms, or off
+        --no-save              Do not append the result to history
+        --json                 Print the full result as JSON on stdout
+    -o, --output <FILE>        Also write the full result as JSON to FILE
+
+SWEEP OPTIONS (plus run options for the base configuration):
+        --block-sizes <LIST>   Block sizes, e.g. 4KiB,64KiB,1MiB or 4KiB..1MiB (doubling)
+        --thread-counts <LIST> Thread counts, e.g. 1,4,16 or 1..32
+        --queue-depths <LIST>  Queue depths for random/mixed modes, e.g. 1..64
+        --metric <M>           Matrix to print: throughput, iops, latency or p99
+                               (default: throughput and iops)
+
+SATURATE OPTIONS (plus run options for the base random/mixed configuration):
+        --queue-depths <LIST>  Raise requests in flight per worker, e.g. 1..256 (default)
+        --thread-counts <LIST> Raise the number of workers instead, e.g. 1..32
+        --rates <LIST>         Raise the target IOPS per worker instead, e.g. 1000..64000
+        --latency-limit <TIME> Stop once p99 latency passes this (default: 100ms)
+        --knee-factor <X>      Count latency as degrading once it grows X times as
+                               fast as IOPS between steps (default: 1.5)
+
+AUTOTUNE OPTIONS (plus -p, --path, --engine and other run options for the probes):
+        --name <NAME>          Profile to save the recommendation as (default: autotune)
+        --probe-size <SIZE>    Test file size for each probe (default: 64MiB)
+        --probe-time <TIME>    Duration of each random probe (default: 2s)
+        --tolerance <PERCENT>  Prefer less load among settings within this much of the
+                               best throughput (default: 5%)
+        --no-save              Print the recommendation without saving the profile
+
+CONFIG SUBCOMMANDS:
+    show                       Print the saved configuration and profiles (default)
+    path                       Print the configuration file location
+    set [RUN OPTIONS]          Update and save the configuration
+    reset                      Restore the default configuration
+
+    -h, --help                 Print this help
+    -V, --version              Print version information
+";
+
+/// Parsed top-level command
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// No arguments given: run the interactive prompt flow
+    Interactive,
+    /// Start the full-screen terminal UI
+    Tui,
+    /// Run a benchmark non-interactively
+    Run(Box<RunArgs>),
+    /// Run a benchmark over a grid of parameters
+    Sweep(Box<SweepArgs>),
+    /// Raise the load step by step until latency degrades
+    Saturate(Box<SaturateArgs>),
+    /// Probe block sizes and concurrency and save the best as a profile
+    Autotune(Box<AutotuneArgs>),
+    /// List saved results
+    History(HistoryArgs),
+    /// Inspect or modify the saved configuration
+    Config(ConfigCommand),
+    /// Print usage information
+    Help,
+    /// Print version information
+    Version,
+}
+
+/// Arguments for `diorb run`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunArgs {
+    /// Configuration overrides applied on top of the saved configuration
+    pub overrides: ConfigOverrides,
+    /// Whether to append the result to the history file
+    pub save: bool,
+    /// Print the result as JSON instead of the human-readable report
+    pub json: bool,
+    /// File to write the JSON result to
+    pub output: Option<PathBuf>,
+}
+
+/// Arguments for `diorb sweep`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SweepArgs {
+    /// Run options for the base configuration
+    pub run: RunArgs,
+    /// Values to sweep
+    pub sweep: SweepConfig,
+    /// Metric to print matrices of (`None` prints throughput and IOPS)
+    pub metric: Option<SweepMetric>,
+}
+
+/// Arguments for `diorb saturate`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SaturateArgs {
+    /// Run options for the base configuration
+    pub run: RunArgs,
+    /// Load levels and stopping rules
+    pub saturation: SaturationConfig,
+}
+
+/// Arguments for `diorb autotune`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutotuneArgs {
+    /// Configuration overrides for the path and I/O settings to probe with
+    pub overrides: ConfigOverrides,
+    /// Profile to save the recommendation as
+    pub name: String,
+    /// Probe settings
+    pub autotune: AutotuneConfig,
+    /// Whether to save the profile to the config file
+    pub save: bool,
+}
+
+impl Default for AutotuneArgs {
+    fn default() -> Self {
+        Self {
+            overrides: ConfigOverrides::default(),
+            name: DEFAULT_PROFILE_NAME.to_string(),
+            autotune: AutotuneConfig::default(),
+            save: true,
+        }
+    }
+}
+
+/// Arguments for `diorb history`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistoryArgs {
+    /// Maximum number of results to list (most recent)
+    pub limit: Option<usize>,
+}
+
+/// `diorb config` subcommands
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigCommand {
+    /// Print the saved configuration
+    Show,
+    /// Print the configuration file path
+    Path,
+    /// Apply overrides and save the configuration
+    Set(Box<ConfigOverrides>),
+    /// Save the default configuration
+    Reset,
+}
+
+/// Optional overrides for every `BenchmarkConfig` field
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigOverrides {
+    pub disk_path: Option<PathBuf>,
+    pub mode: Option<BenchmarkMode>,
+    pub read_ratio: Option<f32>,
+    pub file_size: Option<u64>,
+    pub block_size: Option<u64>,
+    pub duration: Option<Duration>,
+    pub ramp_time: Option<Duration>,
+    pub thread_count: Option<usize>,
+    pub keep_temp_files: Option<bool>,
+    pub io_engine: Option<IoEngineKind>,
+    pub queue_depth: Option<usize>,
+    pub strict_direct_io: Option<bool>,
+    pub access_pattern: Option<AccessPattern>,
+    pub seed: Option<u64>,
+    pub percentiles: Option<Vec<Percentile>>,
+    pub sample_interval: Option<Duration>,
+    pub steady_window: Option<Duration>,
+    pub steady_tolerance: Option<f64>,
+    pub steady_max_duration: Option<Duration>,
+    pub steady_metric: Option<SteadyStateMetric>,
+    pub repetitions: Option<usize>,
+    pub target_cv: Option<f64>,
+    /// Target load per worker (`Some(None)` turns rate limiting off)
+    pub rate: Option<Option<RateTarget>>,
+    pub arrivals: Option<ArrivalProcess>,
+    /// Saved profile to take block size and concurrency from
+    pub profile: Option<String>,
+}
+
+impl ConfigOverrides {
+    /// Apply the overrides to a base configuration
+    ///
+    /// Changing the mode resets block size and thread count to the mode
+    /// defaults first, mirroring the TUI config screen, so that explicit
+    /// `--block-size`/`--threads` flags still win.
+    pub fn apply(&self, mut config: BenchmarkConfig) -> BenchmarkConfig {
+        let mode = match (&self.mode, self.read_ratio) {
+            (_, Some(read_ratio)) => Some(BenchmarkMode::Mixed { read_ratio }),
+            (Some(mode), None) => Some(mode.clone()),
+            (None, None) => None,
+        };
+        if let Some(mode) = mode {
+            config.block_size = mode.default_block_size();
+            config.thread_count = mode.default_thread_count();
+            config.mode = mode;
+        }
+        if let Some(path) = &self.disk_path {
+            config.disk_path = path.clone();
+        }
+        if let Some(size) = self.file_size {
+            config.file_size = size;
+        }
+        if let Some(size) = self.block_size {
+            config.block_size = size;
+        }
+        if let Some(duration) = self.duration {
+            config.duration = duration;
+        }
+        if let Some(ramp_time) = self.ramp_time {
+            config.ramp_time = ramp_time;
+        }
+        if let Some(threads) = self.thread_count {
+            config.thread_count = threads;
+        }
+        if let Some(keep) = self.keep_temp_files {
+            config.keep_temp_files = keep;
+        }
+        if let Some(engine) = self.io_engine {
+            config.io_engine = engine;
+        }
+        if let Some(depth) = self.queue_depth {
+            config.queue_depth = Some(depth);
+        }
+        if let Some(strict) = self.strict_direct_io {
+            config.strict_direct_io = strict;
+        }
+        if let Some(pattern) = self.access_pattern {
+            config.access_pattern = pattern;
+        }
+        if let Some(seed) = self.seed {
+            config.seed = Some(seed);
+        }
+        if let Some(percentiles) = &self.percentiles {
+            config.percentiles = percentiles.clone();
+        }
+        if let Some(interval) = self.sample_interval {
+            config.sample_interval = interval;
+        }
+        if self.steady_window == Some(Duration::ZERO) {
+            config.steady_state = None;
+        } else if self.steady_window.is_some()
+            || self.steady_tolerance.is_some()
+            || self.steady_max_duration.is_some()
+            || self.steady_metric.is_some()
+        {
+            let mut steady = config.steady_state.unwrap_or_default();
+            if let Some(window) = self.steady_window {
+                steady.window = window;
+            }
+            if let Some(tolerance) = self.steady_tolerance {
+                steady.tolerance = Some(tolerance);
+            }
+            if let Some(max_duration) = self.steady_max_duration {
+                steady.max_duration = max_duration;
+            }
+            if let Some(metric) = self.steady_metric {
+                steady.metric = metric;
+            }
+            config.steady_state = Some(steady);
+        }
+        if let Some(repetitions) = self.repetitions {
+            config.repetitions = repetitions;
+        }
+        if let Some(target_cv) = self.target_cv {
+            config.target_cv = Some(target_cv);
+            // A target needs room for several runs unless a cap was given
+            if self.repetitions.is_none() && config.repetitions < MIN_RUNS_FOR_CV {
+                config.repetitions = DEFAULT_MAX_REPETITIONS;
+            }
+        }
+        match self.rate {
+            Some(None) => config.rate_limit = None,
+            Some(Some(target)) => {
+                let arrivals = config
+                    .rate_limit
+                    .map(|rate| rate.arrivals)
+                    .unwrap_or_default();
+                config.rate_limit = Some(RateLimitConfig { target, arrivals });
+            }
+            None => {}
+        }
+        if let (Some(arrivals), Some(rate)) = (self.arrivals, config.rate_limit.as_mut()) {
+            rate.arrivals = arrivals;
+        }
+        config
+    }
+
+    /// Apply the overrides to the saved configuration in `file`
+    ///
+    /// A named profile applies after the mode is settled, so its settings
+    /// for that mode are used; explicit block size, thread count, engine and
+    /// queue depth flags still win.
+    pub fn resolve(&self, file: &ConfigFile) -> Result<BenchmarkConfig> {
+        let mut config = self.apply(file.config.clone());
+        let Some(name) = &self.profile else {
+            return Ok(config);
+        };
+        file.profile(name)?.apply(&mut config);
+        if let Some(size) = self.block_size {
+            config.block_size = size;
+        }
+        if let Some(threads) = self.thread_count {
+            config.thread_count = threads;
+        }
+        if let Some(engine) = self.io_engine {
+            config.io_engine = engine;
+        }
+        if let Some(depth) = self.queue_depth {
+            config.queue_depth = Some(depth);
+        }
+        Ok(config)
+    }
+
+    /// Reject an explicit engine or queue depth for a sequential `config`
+    ///
+    /// Sequential modes always issue one blocking request at a time, so the
+    /// flags would be ignored and the result would not match them.
+    pub fn check_engine(&self, config: &BenchmarkConfig) -> Result<()> {
+        if config.mode.uses_duration() {
+            return Ok(());
+        }
+        let engine = self
+            .io_engine
+            .filter(|&engine| engine != IoEngineKind::Sync);
+        if engine.is_some() || self.queue_depth.is_some_and(|depth| depth > 1) {
+            return Err(DIOrbError::ConfigError(format!(
+                "--engine and --queue-depth only apply to random and mixed modes; {} issues one request at a time",
+                config.mode.description()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reject `--arrivals` when `config` has no rate limit for it to shape
+    pub fn check_arrivals(&self, config: &BenchmarkConfig) -> Result<()> {
+        if self.arrivals.is_some() && config.rate_limit.is_none() {
+            return Err(DIOrbError::ConfigError(
+                "--arrivals only applies to rate-limited runs; set a rate with --rate".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Reject `--read-ratio` alongside a mode other than mixed
+    fn check_read_ratio(&self) -> Result<()> {
+        match (&self.mode, self.read_ratio) {
+            (Some(mode), Some(_)) if !matches!(mode, BenchmarkMode::Mixed { .. }) => {
+                Err(DIOrbError::ConfigError(format!(
+                    "--read-ratio only applies to mixed mode, not {}",
+                    mode.description()
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Try to consume a configuration flag, returning false if the flag is unknown
+    fn parse_flag(&mut self, flag: &str, args: &mut ArgCursor) -> Result<bool> {
+        match flag {
+            "-p" | "--path" => self.disk_path = Some(PathBuf::from(args.value(flag)?)),
+            "-m" | "--mode" => {
+                self.mode = Some(parse_mode(&args.value(flag)?)?);
+                self.check_read_ratio()?;
+            }
+            "--read-ratio" => {
+                let value = args.value(flag)?;
+                let ratio: f32 = value.parse().map_err(|_| {
+                    DIOrbError::ConfigError(format!("Invalid read ratio: {}", value))
+                })?;
+                self.read_ratio = Some(ratio);
+                self.check_read_ratio()?;
+            }
+            "-s" | "--file-size" => self.file_size = Some(parse_size(flag, &args.value(flag)?)?),
+            "-b" | "--block-size" => self.block_size = Some(parse_size(flag, &args.value(flag)?)?),
+            "-d" | "--duration" => {
+                let value = args.value(flag)?;
+                let duration = parse_duration(&value).map_err(|e| {
+                    DIOrbError::ConfigError(format!("Invalid value for {}: {}", flag, e))
+                })?;
+                self.duration = Some(duration);
+            }
+            "--ramp-time" => {
+                let value = args.value(flag)?;
+                let ramp_time = parse_duration(&value).map_err(|e| {
+                    DIOrbError::ConfigError(format!("Invalid value for {}: {}", flag, e))
+                })?;
+                self.ramp_time = Some(ramp_time);
+            }
+            "--steady-window" => {
+                let value = args.value(flag)?;
+                let window = if value.eq_ignore_ascii_case("off") {
+                    Duration::ZERO
+                } else {
+                    parse_duration(&value).map_err(|e| {
+                        DIOrbError::ConfigError(format!("Invalid value for {}: {}", flag, e))
+                    })?
+                };
+                self.steady_window = Some(window);
+            }
+            "--steady-tolerance" => {
+                let value = args.value(flag)?;
+                let percent: f64 = value.trim_end_matches('%').parse().map_err(|_| {
+                    DIOrbError::ConfigError(format!("Invalid value for {}: {}", flag, value))
+                })?;
+                self.steady_tolerance = Some(percent / 100.0);
+            }
+            "--steady-max" => {
+                let value = args.value(flag)?;
+                let max_duration = parse_duration(&value).map_err(|e| {
+                    DIOrbError::ConfigError(format!("Invalid value for {}: {}", flag, e))
+                })?;
+                self.steady_max_duration = Some(max_duration);
+            }
+            "--steady-metric" => {
+                self.steady_metric = Some(parse_steady_metric(&args.value(flag)?)?)
+            }
+            "--repetitions" => self.repetitions = Some(parse_number(flag, &args.value(flag)?)?),
+            "--target-cv" => {
+                let value = args.value(flag)?;
+                let percent: f64 = value.trim_end_matches('%').parse().map_err(|_| {
+                    DIOrbError::ConfigError(format!("Invalid value for {}: {}", flag, value))
+                })?;
+                self.target_cv = Some(percent / 100.0);
+            }
+            "--rate" => self.rate = Some(parse_rate(&args.value(flag)?)?),
+            "--arrivals" => self.arrivals = Some(parse_arrivals(&args.value(flag)?)?),
+            "--profile" => self.profile = Some(args.value(flag)?),
+            "-t" | "--threads" => self.thread_count = Some(parse_number(flag, &args.value(flag)?)?),
+            "--keep-temp-files" => self.keep_temp_files = Some(true),
+            "--no-keep-temp-files" => self.keep_temp_files = Some(false),
+            "--engine" => self.io_engine = Some(parse_engine(&args.value(flag)?)?),
+            "--queue-depth" => self.queue_depth = Some(parse_number(flag, &args.value(flag)?)?),
+            "--strict-direct-io" => self.strict_direct_io = Some(true),
+            "--seed" => self.seed = Some(parse_number(flag, &args.value(flag)?)?),
+            "--access" => self.access_pattern = Some(parse_access_pattern(&args.value(flag)?)?),
+            "--percentiles" => self.percentiles = Some(parse_percentiles(&args.value(flag)?)?),
+            "--sample-interval" => {
+                let value = args.value(flag)?;
+                let interval = if value.eq_ignore_ascii_case("off") {
+                    Duration::ZERO
+                } else {
+                    parse_duration(&value).map_err(|e| {
+                        DIOrbError::ConfigError(format!("Invalid value for {}: {}", flag, e))
+                    })?
+                };
+                self.sample_interval = Some(interval);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+/// Cursor over command-line arguments supporting `--flag value` and `--flag=value`
+struct ArgCursor<'a> {
+    args: &'a [String],
+    pos: usize,
+    pending_value: Option<String>,
+}
+
+impl<'a> ArgCursor<'a> {
+    fn new(args: &'a [String]) -> Self {
+        Self {
+            args,
+            pos: 0,
+            pending_value: None,
+        }
+    }
+
+    /// Get the next flag, splitting off an inline `=value` if present
+    fn next_flag(&mut self) -> Result<Option<String>> {
+        if let Some(value) = self.pending_value.take() {
+            return Err(DIOrbError::ConfigError(format!(
+                "Unexpected value: {}",
+                value
+            )));
+        }
+        let Some(arg) = self.args.get(self.pos) else {
+            return Ok(None);
+        };
+        self.pos += 1;
+        match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                self.pending_value = Some(value.to_string());
+                Ok(Some(flag.to_string()))
+            }
+            _ => Ok(Some(arg.clone())),
+        }
+    }
+
+    /// Get the value for the flag that was just returned
+    fn value(&mut self, flag: &str) -> Result<String> {
+        if let Some(value) = self.pending_value.take() {
+            return Ok(value);
+        }
+        let value = self
+            .args
+            .get(self.pos)
+            .ok_or_else(|| DIOrbError::ConfigError(format!("Missing value for {}", flag)))?;
+        self.pos += 1;
+        Ok(value.clone())
+    }
+}
+
+/// Parse command-line arguments (excluding the program name)
+pub fn parse_args(args: &[String]) -> Result<Command> {
+    let Some(first) = args.first() else {
+        return Ok(Command::Interactive);
+    };
+    let rest = &args[1..];
+
+    match first.as_str() {
+        "-h" | "--help" | "help" => Ok(Command::Help),
+        "-V" | "--version" => Ok(Command::Version),
+        "tui" => match rest.first() {
+            Some(arg) => Err(unknown_option(arg)),
+            None => Ok(Command::Tui),
+        },
+        "run" => parse_run(rest).map(|run| Command::Run(Box::new(run))),
+        "sweep" => parse_sweep(rest).map(|sweep| Command::Sweep(Box::new(sweep))),
+        "saturate" => parse_saturate(rest).map(|saturate| Command::Saturate(Box::new(saturate))),
+        "autotune" => parse_autotune(rest).map(|autotune| Command::Autotune(Box::new(autotune))),
+        "history" => parse_history(rest).map(Command::History),
+        "config" => parse_config(rest).map(Command::Config),
+        other => Err(DIOrbError::ConfigError(format!(
+            "Unknown command: {}",
+            other
+        ))),
+    }
+}
+
+fn parse_run(args: &[String]) -> Result<RunArgs> {
+    let mut run = RunArgs {
+        save: true,
+        ..RunArgs::default()
+    };
+    let mut cursor = ArgCursor::new(args);
+    while let Some(flag) = cursor.next_flag()? {
+        if !run.parse_flag(&flag, &mut cursor)? {
+            return Err(unknown_option(&flag));
+        }
+    }
+    Ok(run)
+}
+
+fn parse_sweep(args: &[String]) -> Result<SweepArgs> {
+    let mut sweep = SweepArgs {
+        run: RunArgs {
+            save: true,
+            ..RunArgs::default()
+        },
+        ..SweepArgs::default()
+    };
+    let mut cursor = ArgCursor::new(args);
+    while let Some(flag) = cursor.next_flag()? {
+        if sweep.run.parse_flag(&flag, &mut cursor)? {
+            continue;
+        }
+        match flag.as_str() {
+            "--block-sizes" => {
+                sweep.sweep.block_sizes =
+                    parse_sweep_values(&flag, &cursor.value(&flag)?, |v| parse_size(&flag, v))?
+            }
+            "--thread-counts" => {
+                sweep.sweep.thread_counts =
+                    parse_sweep_values(&flag, &cursor.value(&flag)?, |v| parse_number(&flag, v))?
+            }
+            "--queue-depths" => {
+                sweep.sweep.queue_depths =
+                    parse_sweep_values(&flag, &cursor.value(&flag)?, |v| parse_number(&flag, v))?
+            }
+            "--metric" => sweep.metric = Some(parse_sweep_metric(&cursor.value(&flag)?)?),
+            _ => return Err(unknown_option(&flag)),
+        }
+    }
+    Ok(sweep)
+}
+
+fn parse_saturate(args: &[String]) -> Result<SaturateArgs> {
+    let mut saturate = SaturateArgs {
+        run: RunArgs {
+            save: true,
+            ..RunArgs::default()
+        },
+        ..SaturateArgs::default()
+    };
+    let mut ladder_flag: Option<String> = None;
+    let mut cursor = ArgCursor::new(args);
+    while let Some(flag) = cursor.next_flag()? {
+        if saturate.run.parse_flag(&flag, &mut cursor)? {
+            continue;
+        }
+        let ladder = match flag.as_str() {
+            "--queue-depths" => Some(LoadLadder::QueueDepths(parse_sweep_values(
+                &flag,
+                &cursor.value(&flag)?,
+                |v| parse_number(&flag, v),
+            )?)),
+            "--thread-counts" => Some(LoadLadder::Threads(parse_sweep_values(
+                &flag,
+                &cursor.value(&flag)?,
+                |v| parse_number(&flag, v),
+            )?)),
+            "--rates" => {
+                let rates: Vec<u64> = parse_sweep_values(&flag, &cursor.value(&flag)?, |v| {
+                    parse_number(&flag, v.trim_end_matches("iops"))
+                })?;
+                Some(LoadLadder::TargetIops(
+                    rates.into_iter().map(|rate| rate as f64).collect(),
+                ))
+            }
+            "--latency-limit" => {
+                let value = cursor.value(&flag)?;
+                saturate.saturation.latency_limit = parse_duration(&value).map_err(|e| {
+                    DIOrbError::ConfigError(format!("Invalid value for {}: {}", flag, e))
+                })?;
+                None
+            }
+            "--knee-factor" => {
+                saturate.saturation.knee_factor = parse_number(&flag, &cursor.value(&flag)?)?;
+                None
+            }
+            _ => return Err(unknown_option(&flag)),
+        };
+        if let Some(ladder) = ladder {
+            if let Some(previous) = ladder_flag.replace(flag.clone()) {
+                return Err(DIOrbError::ConfigError(format!(
+                    "{} and {} both set the load levels; give only one",
+                    previous, flag
+                )));
+            }
+            saturate.saturation.ladder = ladder;
+        }
+    }
+    Ok(saturate)
+}
+
+fn parse_autotune(args: &[String]) -> Result<AutotuneArgs> {
+    let mut autotune = AutotuneArgs::default();
+    let mut cursor = ArgCursor::new(args);
+    while let Some(flag) = cursor.next_flag()? {
+        if autotune.overrides.parse_flag(&flag, &mut cursor)? {
+            continue;
+        }
+        match flag.as_str() {
+            "--name" => {
+                let name = cursor.value(&flag)?;
+                if name.trim().is_empty() {
+                    return Err(DIOrbError::ConfigError(
+                        "Profile name must not be empty".to_string(),
+                    ));
+                }
+                autotune.name = name;
+            }
+            "--probe-size" => {
+                autotune.autotune.probe_file_size = parse_size(&flag, &cursor.value(&flag)?)?
+            }
+            "--probe-time" => {
+                let value = cursor.value(&flag)?;
+                autotune.autotune.probe_duration = parse_duration(&value).map_err(|e| {
+                    DIOrbError::ConfigError(format!("Invalid value for {}: {}", flag, e))
+                })?;
+            }
+            "--tolerance" => {
+                let value = cursor.value(&flag)?;
+                let percent: f64 = parse_number(&flag, value.trim_end_matches('%'))?;
+                autotune.autotune.tolerance = percent / 100.0;
+            }
+            "--no-save" => autotune.save = false,
+            _ => return Err(unknown_option(&flag)),
+        }
+    }
+    Ok(autotune)
+}
+
+impl RunArgs {
+    /// Try to consume a run flag, returning false if the flag is unknown
+    fn parse_flag(&mut self, flag: &str, args: &mut ArgCursor) -> Result<bool> {
+        if self.overrides.parse_flag(flag, args)? {
+            return Ok(true);
+        }
+        match flag {
+            "--no-save" => self.save = false,
+            "--json" => self.json = true,
+            "-o" | "--output" => self.output = Some(PathBuf::from(args.value(flag)?)),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+fn parse_history(args: &[String]) -> Result<HistoryArgs> {
+    let mut history = HistoryArgs::default();
+    let mut cursor = ArgCursor::new(args);
+    while let Some(flag) = cursor.next_flag()? {
+        match flag.as_str() {
+            "-n" | "--limit" => history.limit = Some(parse_number(&flag, &cursor.value(&flag)?)?),
+            _ => return Err(unknown_option(&flag)),
+        }
+    }
+    Ok(history)
+}
+
+fn parse_config(args: &[String]) -> Result<ConfigCommand> {
+    let Some(sub) = args.first() else {
+        return Ok(ConfigCommand::Show);
+    };
+    let rest = &args[1..];
+    let no_extra_args = |command: ConfigCommand| match rest.first() {
+        Some(arg) => Err(unknown_option(arg)),
+        None => Ok(command),
+    };
+
+    match sub.as_str() {
+        "show" => no_extra_args(ConfigCommand::Show),
+        "path" => no_extra_args(ConfigCommand::Path),
+        "reset" => no_extra_args(ConfigCommand::Reset),
+        "set" => {
+            let mut overrides = ConfigOverrides::default();
+            let mut cursor = ArgCursor::new(rest);
+            while let Some(flag) = cursor.next_flag()? {
+                if !overrides.parse_flag(&flag, &mut cursor)? {
+                    return Err(unknown_option(&flag));
+                }
+            }
+            Ok(ConfigCommand::Set(Box::new(overrides)))
+        }
+        other => Err(DIOrbError::ConfigError(format!(
+            "Unknown config subcommand: {}",
+            other
+        ))),
+    }
+}
+
+/// Parse a benchmark mode name
+pub fn parse_mode(value: &str) -> Result<BenchmarkMode> {
+    match value.to_lowercase().as_str() {
+        "seq-write" | "sequential-write" | "write" => Ok(BenchmarkMode::SequentialWrite),
+        "seq-read" | "sequential-read" | "read" => Ok(BenchmarkMode::SequentialRead),
+        "random" | "random-rw" => Ok(BenchmarkMode::RandomReadWrite),
+        "mixed" => Ok(BenchmarkMode::Mixed { read_ratio: 0.7 }),
+        _ => Err(DIOrbError::ConfigError(format!(
+            "Unknown mode: {} (expected seq-write, seq-read, random or mixed)",
+            value
+        ))),
+    }
+}
+
+/// Parse an I/O engine name
+pub fn parse_engine(value: &str) -> Result<IoEngineKind> {
+    match value.to_lowercase().as_str() {
+        "sync" => Ok(IoEngineKind::Sync),
+        "io-uring" | "io_uring" | "uring" => Ok(IoEngineKind::IoUring),
+        "thread-pool" | "thread_pool" | "threads" => Ok(IoEngineKind::ThreadPool),
+        _ => Err(DIOrbError::ConfigError(format!(
+            "Unknown engine: {} (expected sync, io-uring or thread-pool)",
+            value
+        ))),
+    }
+}
+
+/// Parse the metric steady-state detection watches
+pub fn parse_steady_metric(value: &str) -> Result<SteadyStateMetric> {
+    match value.to_lowercase().as_str() {
+        "throughput" | "mbps" => Ok(SteadyStateMetric::Throughput),
+        "iops" => Ok(SteadyStateMetric::Iops),
+        _ => Err(DIOrbError::ConfigError(format!(
+            "Unknown steady-state metric: {} (expected throughput or iops)",
+            value
+        ))),
+    }
+}
+
+/// Parse a target load: `off`, IOPS such as `5000` or `5000iops`, or bandwidth such as `200MiB/s`
+pub fn parse_rate(value: &str) -> Result<Option<RateTarget>> {
+    let trimmed = value.trim();
+    if trimmed.eq_ignore_ascii_case("off") {
+        return Ok(None);
+    }
+    let invalid = || DIOrbError::ConfigError(format!("Invalid rate: {}", value));
+    if let Some(bytes) = trimmed.strip_suffix("/s") {
+        let bytes = parse_bytes(bytes).map_err(|_| invalid())?;
+        return Ok(Some(RateTarget::Bandwidth(bytes)));
+    }
+    let lower = trimmed.to_lowercase();
+    let iops: f64 = lower
+        .strip_suffix("iops")
+        .unwrap_or(&lower)
+        .trim()
+        .parse()
+        .map_err(|_| invalid())?;
+    Ok(Some(RateTarget::Iops(iops)))
+}
+
+/// Parse the arrival process of rate-limited requests
+pub fn parse_arrivals(value: &str) -> Result<ArrivalProcess> {
+    match value.to_lowercase().as_str() {
+        "fixed" | "uniform" => Ok(ArrivalProcess::Fixed),
+        "poisson" | "exponential" => Ok(ArrivalProcess::Poisson),
+        _ => Err(DIOrbError::ConfigError(format!(
+            "Unknown arrival process: {} (expected fixed or poisson)",
+            value
+        ))),
+    }
+}
+
+/// Parse the metric a sweep matrix shows
+pub fn parse_sweep_metric(value: &str) -> Result<SweepMetric> {
+    match value.to_lowercase().as_str() {
+        "throughput" | "mbps" => Ok(SweepMetric::Throughput),
+        "iops" => Ok(SweepMetric::Iops),
+        "latency" | "avg-latency" => Ok(SweepMetric::AvgLatency),
+        "p99" | "p99-latency" => Ok(SweepMetric::P99Latency),
+        _ => Err(DIOrbError::ConfigError(format!(
+            "Unknown sweep metric: {} (expected throughput, iops, latency or p99)",
+            value
+        ))),
+    }
+}
+
+/// Parse sweep values given as a list such as `4KiB,64KiB` or a doubling range such as `1..32`
+///
+/// A range starts at its first value and doubles up to the last, which is
+/// included when it is reached exactly. Values are sorted and duplicates dropped.
+fn parse_sweep_values<T>(
+    flag: &str,
+    value: &str,
+    parse: impl Fn(&str) -> Result<T>,
+) -> Result<Vec<T>>
+where
+    T: Copy + Ord + TryFrom<u64> + TryInto<u64>,
+{
+    let mut values = match value.split_once("..") {
+        Some((start, end)) => {
+            let invalid = || {
+                DIOrbError::ConfigError(format!(
+                    "Invalid range for {}: {} (expected START..END with 0 < START <= END)",
+                    flag, value
+                ))
+            };
+            let start: u64 = parse(start.trim())?.try_into().map_err(|_| invalid())?;
+            let end: u64 = parse(end.trim())?.try_into().map_err(|_| invalid())?;
+            if start == 0 || start > end {
+                return Err(invalid());
+            }
+            std::iter::successors(Some(start), |v| v.checked_mul(2))
+                .take_while(|v| *v <= end)
+                .filter_map(|v| T::try_from(v).ok())
+                .collect()
+        }
+        None => value
+            .split(',')
+            .map(|v| parse(v.trim()))
+            .collect::<Result<Vec<T>>>()?,
+    };
+    values.sort_unstable();
+    values.dedup();
+    Ok(values)
+}
+
+/// Parse a comma-separated percentile list such as `50,99,p99.9`
+///
+/// The list is sorted and duplicates are dropped.
+pub fn parse_percentiles(value: &str) -> Result<Vec<Percentile>> {
+    let mut percentiles = value
+        .split(',')
+        .map(str::parse)
+        .collect::<Result<Vec<Percentile>>>()?;
+    percentiles.sort();
+    percentiles.dedup();
+    Ok(percentiles)
+}
+
+/// Parse an access pattern such as `zipf:0.99`, `hotspot:80/20` or `normal:0.5,0.1`
+///
+/// Parameters are optional and default to zipf 0.99, hotspot 80/20 and
+/// normal centered on the file with a 0.1 standard deviation. Hot spot
+/// shares may be given as percentages or fractions.
+pub fn parse_access_pattern(value: &str) -> Result<AccessPattern> {
+    let invalid = || {
+        DIOrbError::ConfigError(format!(
+            "Invalid access pattern: {} (expected uniform, zipf[:THETA], hotspot[:OPS/DATA] or normal[:CENTER,STDDEV])",
+            value
+        ))
+    };
+    let (name, params) = match value.split_once(':') {
+        Some((name, params)) => (name, Some(params)),
+        None => (value, None),
+    };
+    let number = |s: &str| s.trim().parse::<f64>().map_err(|_| invalid());
+    let pair = |params: &str, separator: char| -> Result<(f64, f64)> {
+        let (a, b) = params.split_once(separator).ok_or_else(invalid)?;
+        Ok((number(a)?, number(b)?))
+    };
+    let fraction = |share: f64| if share > 1.0 { share / 100.0 } else { share };
+
+    let pattern = match (name.to_lowercase().as_str(), params) {
+        ("uniform", None) => AccessPattern::Uniform,
+        ("zipf" | "zipfian", None) => AccessPattern::Zipfian { theta: 0.99 },
+        ("zipf" | "zipfian", Some(theta)) => AccessPattern::Zipfian {
+            theta: number(theta)?,
+        },
+        ("hotspot" | "pareto", None) => AccessPattern::HotSpot {
+            hot_ops: 0.8,
+            hot_data: 0.2,
+        },
+        ("hotspot" | "pareto", Some(params)) => {
+            let (ops, data) = pair(params, '/')?;
+            AccessPattern::HotSpot {
+                hot_ops: fraction(ops),
+                hot_data: fraction(data),
+            }
+        }
+        ("normal", None) => AccessPattern::Normal {
+            center: 0.5,
+            std_dev: 0.1,
+        },
+        ("normal", Some(params)) => {
+            let (center, std_dev) = pair(params, ',')?;
+            AccessPattern::Normal { center, std_dev }
+        }
+        _ => return Err(invalid()),
+    };
+    pattern.validate()?;
+    Ok(pattern)
+}
+
+fn parse_size(flag: &str, value: &str) -> Result<u64> {
+    parse_bytes(value)
+        .map_err(|e| DIOrbError::ConfigError(format!("Invalid value for {}: {}", flag, e)))
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T> {
+    value
+        .parse()
+        .map_err(|_| DIOrbError::ConfigError(format!("Invalid value for {}: {}", flag, value)))
+}
+
+fn unknown_option(flag: &str) -> DIOrbError {
+    DIOrbError::ConfigError(format!("Unknown option: {}", flag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Profile, SteadyStateConfig, WorkloadTuning};
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_no_args_is_interactive() {
+        assert_eq!(parse_args(&[]).unwrap(), Command::Interactive);
+    }
+
+    #[test]
+    fn test_parse_tui() {
+        assert_eq!(parse_args(&args(&["tui"])).unwrap(), Command::Tui);
+        assert!(parse_args(&args(&["tui", "--path", "/"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_run_flags() {
+        let cmd = parse_args(&args(&[
+            "run",
+            "--path",
+            "/mnt/x",
+            "--mode",
+            "random",
+            "--block-size",
+            "4KiB",
+            "--duration",
+            "30s",
+            "--threads=4",
+            "--file-size",
+            "256 MiB",
+        ]))
+        .unwrap();
+
+        let Command::Run(run) = cmd else {
+            panic!("Expected run command");
+        };
+        assert!(run.save);
+
+        let config = run.overrides.apply(BenchmarkConfig::default());
+        assert_eq!(config.disk_path, PathBuf::from("/mnt/x"));
+        assert!(matches!(config.mode, BenchmarkMode::RandomReadWrite));
+        assert_eq!(config.block_size, 4096);
+        assert_eq!(config.duration, Duration::from_secs(30));
+        assert_eq!(config.thread_count, 4);
+        assert_eq!(config.file_size, 256 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_parse_json_output_flags() {
+        let Command::Run(run) =
+            parse_args(&args(&["run", "--json", "--output=result.json"])).unwrap()
+        else {
+            panic!("Expected run command");
+        };
+        assert!(run.json);
+        assert_eq!(run.output, Some(PathBuf::from("result.json")));
+    }
+
+    #[test]
+    fn test_parse_engine_flags() {
+        let Command::Run(run) = parse_args(&args(&[
+            "run",
+            "--engine",
+            "io-uring",
+            "--queue-depth",
+            "32",
+            "--strict-direct-io",
+        ]))
+        .unwrap() else {
+            panic!("Expected run command");
+        };
+        let config = run.overrides.apply(BenchmarkConfig::default());
+        assert_eq!(config.io_engine, IoEngineKind::IoUring);
+        assert_eq!(config.queue_depth, Some(32));
+        assert!(config.strict_direct_io);
+        assert_eq!(
+            parse_engine("thread-pool").unwrap(),
+            IoEngineKind::ThreadPool
+        );
+        assert!(parse_args(&args(&["run", "--engine", "aio"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_access_pattern() {
+        assert_eq!(
+            parse_access_pattern("uniform").unwrap(),
+            AccessPattern::Uniform
+        );
+        assert_eq!(
+            parse_access_pattern("zipf:0.8").unwrap(),
+            AccessPattern::Zipfian { theta: 0.8 }
+        );
+        assert_eq!(
+            parse_access_pattern("hotspot:80/20").unwrap(),
+            AccessPattern::HotSpot {
+                hot_ops: 0.8,
+                hot_data: 0.2
+            }
+        );
+        assert_eq!(
+            parse_access_pattern("hotspot:0.9/0.1").unwrap(),
+            AccessPattern::HotSpot {
+                hot_ops: 0.9,
+                hot_data: 0.1
+            }
+        );
+        assert_eq!(
+            parse_access_pattern("normal:0.25,0.05").unwrap(),
+            AccessPattern::Normal {
+                center: 0.25,
+                std_dev: 0.05
+            }
+        );
+        assert!(parse_access_pattern("zipf:1.5").is_err());
+        assert!(parse_access_pattern("uniform:3").is_err());
+        assert!(parse_access_pattern("normal:0.5").is_err());
+        assert!(parse_access_pattern("sequential").is_err());
+
+        let Command::Run(run) =
+            parse_args(&args(&["run", "--access", "zipf", "--seed", "42"])).unwrap()
+        else {
+            panic!("Expected run command");
+        };
+        let config = run.overrides.apply(BenchmarkConfig::default());
+        assert_eq!(config.seed, Some(42));
+        assert_eq!(
+            config.access_pattern,
+            AccessPattern::Zipfian { theta: 0.99 }
+        );
+    }
+
+    #[test]
+    fn test_parse_percentiles() {
+        assert_eq!(
+            parse_percentiles("99.99,50,p99.9,99.9").unwrap(),
+            vec![Percentile::P50, Percentile::P99_9, Percentile::P99_99]
+        );
+        assert!(parse_percentiles("50,").is_err());
+        assert!(parse_percentiles("100.5").is_err());
+
+        let Command::Run(run) = parse_args(&args(&["run", "--percentiles", "99,99.999"])).unwrap()
+        else {
+            panic!("Expected run command");
+        };
+        let config = run.overrides.apply(BenchmarkConfig::default());
+        assert_eq!(
+            config.percentiles,
+            vec![Percentile::P99, Percentile::new(99.999).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_sample_interval_flag() {
+        let config_for = |value: &str| {
+            let Command::Run(run) =
+                parse_args(&args(&["run", "--sample-interval", value])).unwrap()
+            else {
+                panic!("Expected run command");
+            };
+            run.overrides.apply(BenchmarkConfig::default())
+        };
+        assert_eq!(
+            config_for("100ms").sample_interval,
+            Duration::from_millis(100)
+        );
+        assert_eq!(config_for("off").sample_interval, Duration::ZERO);
+        assert!(parse_args(&args(&["run", "--sample-interval", "soon"])).is_err());
+    }
+
+    #[test]
+    fn test_ramp_time_flag() {
+        let Command::Run(run) = parse_args(&args(&["run", "--ramp-time", "5s"])).unwrap() else {
+            panic!("Expected run command");
+        };
+        let config = run.overrides.apply(BenchmarkConfig::default());
+        assert_eq!(config.ramp_time, Duration::from_secs(5));
+        assert!(parse_args(&args(&["run", "--ramp-time", "later"])).is_err());
+    }
+
+    #[test]
+    fn test_steady_state_flags() {
+        let apply = |flags: &[&str], base: BenchmarkConfig| {
+            let Command::Run(run) = parse_args(&args(&[&["run"], flags].concat())).unwrap() else {
+                panic!("Expected run command");
+            };
+            run.overrides.apply(base)
+        };
+
+        let config = apply(
+            &[
+                "--steady-window",
+                "20s",
+                "--steady-tolerance",
+                "5%",
+                "--steady-metric",
+                "iops",
+            ],
+            BenchmarkConfig::random_read_write(),
+        );
+        let steady = config.steady_state.unwrap();
+        assert_eq!(steady.window, Duration::from_secs(20));
+        assert_eq!(steady.tolerance, Some(0.05));
+        assert_eq!(steady.metric, SteadyStateMetric::Iops);
+        assert_eq!(
+            steady.max_duration,
+            SteadyStateConfig::default().max_duration
+        );
+
+        // Any steady flag turns detection on; "off" turns it off again
+        let config = apply(
+            &["--steady-max", "5m"],
+            BenchmarkConfig::random_read_write(),
+        );
+        assert_eq!(
+            config.steady_state.unwrap().max_duration,
+            Duration::from_secs(300)
+        );
+        let config = apply(&["--steady-window", "off"], config);
+        assert_eq!(config.steady_state, None);
+
+        assert!(parse_args(&args(&["run", "--steady-metric", "latency"])).is_err());
+        assert!(parse_args(&args(&["run", "--steady-tolerance", "tight"])).is_err());
+    }
+
+    #[test]
+    fn test_repetition_flags() {
+        let config_for = |flags: &[&str]| {
+            let Command::Run(run) = parse_args(&args(&[&["run"], flags].concat())).unwrap() else {
+                panic!("Expected run command");
+            };
+            run.overrides.apply(BenchmarkConfig::default())
+        };
+
+        let config = config_for(&["--repetitions", "5"]);
+        assert_eq!(config.repetitions, 5);
+        assert_eq!(config.target_cv, None);
+
+        // A target alone leaves room for enough runs to judge it
+        let config = config_for(&["--target-cv", "2%"]);
+        assert_eq!(config.target_cv, Some(0.02));
+        assert_eq!(config.repetitions, DEFAULT_MAX_REPETITIONS);
+        assert!(config.validate().is_ok());
+
+        let config = config_for(&["--target-cv", "2", "--repetitions", "20"]);
+        assert_eq!(config.repetitions, 20);
+
+        assert!(parse_args(&args(&["run", "--repetitions", "many"])).is_err());
+        assert!(parse_args(&args(&["run", "--target-cv", "low"])).is_err());
+    }
+
+    #[test]
+    fn test_rate_flags() {
+        let config_for = |flags: &[&str]| {
+            let Command::Run(run) = parse_args(&args(&[&["run"], flags].concat())).unwrap() else {
+                panic!("Expected run command");
+            };
+            run.overrides.apply(BenchmarkConfig::default())
+        };
+
+        let rate = config_for(&["--rate", "5000iops"]).rate_limit.unwrap();
+        assert_eq!(rate.target, RateTarget::Iops(5000.0));
+        assert_eq!(rate.arrivals, ArrivalProcess::Fixed);
+
+        let rate = config_for(&["--rate", "200MiB/s", "--arrivals", "poisson"])
+            .rate_limit
+            .unwrap();
+        assert_eq!(rate.target, RateTarget::Bandwidth(200 * 1024 * 1024));
+        assert_eq!(rate.arrivals, ArrivalProcess::Poisson);
+        assert_eq!(
+            config_for(&["--rate", "250.5"]).rate_limit.unwrap().target,
+            RateTarget::Iops(250.5)
+        );
+
+        // Arrivals only shape a configured rate, and a new rate keeps the saved arrivals
+        let Command::Run(run) = parse_args(&args(&["run", "--arrivals", "poisson"])).unwrap()
+        else {
+            panic!("Expected run command");
+        };
+        let config = run.overrides.apply(BenchmarkConfig::default());
+        assert!(run.overrides.check_arrivals(&config).is_err());
+        let Command::Run(run) =
+            parse_args(&args(&["run", "--rate", "off", "--arrivals", "poisson"])).unwrap()
+        else {
+            panic!("Expected run command");
+        };
+        let config = run
+            .overrides
+            .apply(config.with_rate_limit(Some(RateLimitConfig {
+                target: RateTarget::Iops(100.0),
+                arrivals: ArrivalProcess::Fixed,
+            })));
+        assert!(run.overrides.check_arrivals(&config).is_err());
+        let saved = BenchmarkConfig::default().with_rate_limit(Some(RateLimitConfig {
+            target: RateTarget::Iops(100.0),
+            arrivals: ArrivalProcess::Poisson,
+        }));
+        let Command::Run(run) = parse_args(&args(&["run", "--rate", "off"])).unwrap() else {
+            panic!("Expected run command");
+        };
+        assert_eq!(run.overrides.apply(saved.clone()).rate_limit, None);
+        let Command::Run(run) = parse_args(&args(&["run", "--rate", "10iops"])).unwrap() else {
+            panic!("Expected run command");
+        };
+        let rate = run.overrides.apply(saved).rate_limit.unwrap();
+        assert_eq!(rate.target, RateTarget::Iops(10.0));
+        assert_eq!(rate.arrivals, ArrivalProcess::Poisson);
+
+        assert!(parse_args(&args(&["run", "--rate", "fast"])).is_err());
+        assert!(parse_args(&args(&["run", "--rate", "lots/s"])).is_err());
+        assert!(parse_args(&args(&["run", "--arrivals", "bursty"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_saturate() {
+        let Command::Saturate(saturate) = parse_args(&args(&[
+            "saturate",
+            "--mode",
+            "random",
+            "--queue-depths",
+            "1..32",
+            "--latency-limit",
+            "20ms",
+            "--no-save",
+        ]))
+        .unwrap() else {
+            panic!("Expected saturate command");
+        };
+        assert_eq!(
+            saturate.saturation.ladder,
+            LoadLadder::QueueDepths(vec![1, 2, 4, 8, 16, 32])
+        );
+        assert_eq!(saturate.saturation.latency_limit, Duration::from_millis(20));
+        assert_eq!(saturate.saturation.knee_factor, 1.5);
+        assert!(!saturate.run.save);
+
+        let Command::Saturate(saturate) = parse_args(&args(&[
+            "saturate",
+            "--rates",
+            "1000,2000iops",
+            "--knee-factor",
+            "2",
+        ]))
+        .unwrap() else {
+            panic!("Expected saturate command");
+        };
+        assert_eq!(
+            saturate.saturation.ladder,
+            LoadLadder::TargetIops(vec![1000.0, 2000.0])
+        );
+        assert_eq!(saturate.saturation.knee_factor, 2.0);
+
+        // Without a ladder flag, queue depth doubles up to the maximum
+        let Command::Saturate(saturate) = parse_args(&args(&["saturate"])).unwrap() else {
+            panic!("Expected saturate command");
+        };
+        assert_eq!(saturate.saturation, SaturationConfig::default());
+
+        assert!(parse_args(&args(&[
+            "saturate",
+            "--queue-depths",
+            "1..8",
+            "--thread-counts",
+            "1..8"
+        ]))
+        .is_err());
+        assert!(parse_args(&args(&["saturate", "--latency-limit", "soon"])).is_err());
+        assert!(parse_args(&args(&["saturate", "--block-sizes", "4KiB"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_autotune() {
+        let Command::Autotune(autotune) = parse_args(&args(&[
+            "autotune",
+            "--path",
+            "/mnt/nvme",
+            "--name",
+            "nvme",
+            "--probe-size",
+            "16MiB",
+            "--probe-time",
+            "1s",
+            "--tolerance",
+            "10%",
+            "--no-save",
+        ]))
+        .unwrap() else {
+            panic!("Expected autotune command");
+        };
+        assert_eq!(autotune.name, "nvme");
+        assert_eq!(
+            autotune.overrides.disk_path,
+            Some(PathBuf::from("/mnt/nvme"))
+        );
+        assert_eq!(autotune.autotune.probe_file_size, 16 * 1024 * 1024);
+        assert_eq!(autotune.autotune.probe_duration, Duration::from_secs(1));
+        assert!((autotune.autotune.tolerance - 0.1).abs() < 1e-9);
+        assert!(!autotune.save);
+
+        assert_eq!(
+            parse_args(&args(&["autotune"])).unwrap(),
+            Command::Autotune(Box::default())
+        );
+        assert!(parse_args(&args(&["autotune", "--name", ""])).is_err());
+        assert!(parse_args(&args(&["autotune", "--probe-time", "soon"])).is_err());
+        assert!(parse_args(&args(&["autotune", "--block-sizes", "4KiB"])).is_err());
+    }
+
+    #[test]
+    fn test_profile_flag() {
+        let mut file = ConfigFile::default();
+        file.profiles.insert(
+            "nvme".to_string(),
+            Profile {
+                disk_path: PathBuf::from("/mnt/nvme"),
+                io_engine: IoEngineKind::ThreadPool,
+                sequential: None,
+                random: Some(WorkloadTuning {
+                    block_size: 16384,
+                    thread_count: 4,
+                    queue_depth: Some(8),
+                    throughput_mbps: 900.0,
+                    iops: 57_600.0,
+                }),
+            },
+        );
+        let resolve = |flags: &[&str]| {
+            let Command::Run(run) = parse_args(&args(&[&["run"], flags].concat())).unwrap() else {
+                panic!("Expected run command");
+            };
+            run.overrides.resolve(&file)
+        };
+
+        // The profile applies to the mode chosen on the command line
+        let config = resolve(&["--mode", "random", "--profile", "nvme"]).unwrap();
+        assert_eq!(config.block_size, 16384);
+        assert_eq!(config.thread_count, 4);
+        assert_eq!(config.queue_depth, Some(8));
+        assert_eq!(config.io_engine, IoEngineKind::ThreadPool);
+
+        // Explicit flags win over the profile
+        let config = resolve(&["--profile=nvme", "-m", "random", "-t", "2", "-b", "4KiB"]).unwrap();
+        assert_eq!(config.thread_count, 2);
+        assert_eq!(config.block_size, 4096);
+        assert_eq!(config.queue_depth, Some(8));
+
+        // Modes the profile has no settings for are left alone
+        let config = resolve(&["--mode", "seq-write", "--profile", "nvme"]).unwrap();
+        assert_eq!(config.block_size, 64 * 1024);
+
+        assert!(resolve(&["--profile", "hdd"]).is_err());
+        assert_eq!(
+            resolve(&["--threads", "3"]).unwrap().thread_count,
+            3,
+            "no profile, no lookup"
+        );
+    }
+
+    #[test]
+    fn test_parse_sweep() {
+        let Command::Sweep(sweep) = parse_args(&args(&[
+            "sweep",
+            "--mode",
+            "random",
+            "--block-sizes",
+            "4KiB..64KiB",
+            "--thread-counts",
+            "8,1,4,1",
+            "--metric",
+            "iops",
+            "--no-save",
+        ]))
+        .unwrap() else {
+            panic!("Expected sweep command");
+        };
+        assert_eq!(
+            sweep.sweep.block_sizes,
+            vec![4096, 8192, 16384, 32768, 65536]
+        );
+        assert_eq!(sweep.sweep.thread_counts, vec![1, 4, 8]);
+        assert!(sweep.sweep.queue_depths.is_empty());
+        assert_eq!(sweep.metric, Some(SweepMetric::Iops));
+        assert!(!sweep.run.save);
+        assert_eq!(
+            sweep.run.overrides.mode,
+            Some(BenchmarkMode::RandomReadWrite)
+        );
+
+        // A range stops at the last doubling that fits
+        let Command::Sweep(sweep) =
+            parse_args(&args(&["sweep", "--queue-depths", "1..48"])).unwrap()
+        else {
+            panic!("Expected sweep command");
+        };
+        assert_eq!(sweep.sweep.queue_depths, vec![1, 2, 4, 8, 16, 32]);
+        assert!(sweep.run.save);
+
+        assert!(parse_args(&args(&["sweep", "--thread-counts", "8..1"])).is_err());
+        assert!(parse_args(&args(&["sweep", "--thread-counts", "0..4"])).is_err());
+        assert!(parse_args(&args(&["sweep", "--block-sizes", "4KiB,huge"])).is_err());
+        assert!(parse_args(&args(&["sweep", "--metric", "speed"])).is_err());
+        // Sweep flags are not run flags
+        assert!(parse_args(&args(&["run", "--block-sizes", "4KiB"])).is_err());
+    }
+
+    #[test]
+    fn test_mode_change_applies_mode_defaults() {
+        let overrides = ConfigOverrides {
+            mode: Some(BenchmarkMode::RandomReadWrite),
+            ..ConfigOverrides::default()
+        };
+        let config = overrides.apply(BenchmarkConfig::sequential_write());
+        assert_eq!(
+            config.block_size,
+            BenchmarkMode::RandomReadWrite.default_block_size()
+        );
+    }
+
+    #[test]
+    fn test_read_ratio_implies_mixed() {
+        let Command::Run(run) =
+            parse_args(&args(&["run", "--read-ratio", "0.25", "--no-save"])).unwrap()
+        else {
+            panic!("Expected run command");
+        };
+        assert!(!run.save);
+        assert!(!run.json);
+        let config = run.overrides.apply(BenchmarkConfig::default());
+        match config.mode {
+            BenchmarkMode::Mixed { read_ratio } => assert_eq!(read_ratio, 0.25),
+            other => panic!("Unexpected mode: {:?}", other),
+        }
+
+        // An explicit mixed mode takes the ratio; any other mode rejects it
+        let Command::Run(run) =
+            parse_args(&args(&["run", "--mode", "mixed", "--read-ratio", "0.4"])).unwrap()
+        else {
+            panic!("Expected run command");
+        };
+        assert_eq!(
+            run.overrides.apply(BenchmarkConfig::default()).mode,
+            BenchmarkMode::Mixed { read_ratio: 0.4 }
+        );
+        assert!(parse_args(&args(&["run", "--mode", "seq-read", "--read-ratio", "0.4"])).is_err());
+        assert!(parse_args(&args(&["run", "--read-ratio", "0.4", "--mode", "random"])).is_err());
+    }
+
+    #[test]
+    fn test_engine_flags_need_random_mode() {
+        let overrides = |flags: &[&str]| {
+            let Command::Run(run) = parse_args(&args(flags)).unwrap() else {
+                panic!("Expected run command");
+            };
+            run.overrides
+        };
+        let uring = overrides(&["run", "--engine", "io-uring", "--queue-depth", "32"]);
+        assert!(uring
+            .check_engine(&BenchmarkConfig::sequential_read())
+            .is_err());
+        assert!(uring
+            .check_engine(&BenchmarkConfig::random_read_write())
+            .is_ok());
+        let depth = overrides(&["run", "--queue-depth", "8"]);
+        assert!(depth
+            .check_engine(&BenchmarkConfig::sequential_write())
+            .is_err());
+        // A saved engine is left alone; only explicit flags are rejected
+        let saved = BenchmarkConfig::sequential_read().with_io_engine(IoEngineKind::IoUring);
+        assert!(overrides(&["run"]).check_engine(&saved).is_ok());
+        let sync = overrides(&["run", "--engine", "sync"]);
+        assert!(sync.check_engine(&saved).is_ok());
+    }
+
+    #[test]
+    fn test_keep_temp_files_flags() {
+        let saved = BenchmarkConfig {
+            keep_temp_files: true,
+            ..BenchmarkConfig::default()
+        };
+        let Command::Run(run) = parse_args(&args(&["run", "--no-keep-temp-files"])).unwrap() else {
+            panic!("Expected run command");
+        };
+        assert!(!run.overrides.apply(saved.clone()).keep_temp_files);
+        let Command::Run(run) = parse_args(&args(&["run"])).unwrap() else {
+            panic!("Expected run command");
+        };
+        assert!(run.overrides.apply(saved).keep_temp_files);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_args(&args(&["bogus"])).is_err());
+        assert!(parse_args(&args(&["run", "--bogus"])).is_err());
+        assert!(parse_args(&args(&["run", "--threads"])).is_err());
+        assert!(parse_args(&args(&["run", "--block-size", "4 XB"])).is_err());
+        assert!(parse_args(&args(&["run", "--mode", "sideways"])).is_err());
+        assert!(parse_args(&args(&["run", "--no-save=yes"])).is_err());
+        assert!(parse_args(&args(&["run", "--output"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_history_and_config() {
+        assert_eq!(
+            parse_args(&args(&["history", "--limit", "5"])).unwrap(),
+            Command::History(HistoryArgs { limit: Some(5) })
+        );
+        assert_eq!(
+            parse_args(&args(&["config"])).unwrap(),
+            Command::Config(ConfigCommand::Show)
+        );
+        assert_eq!(
+            parse_args(&args(&["config", "path"])).unwrap(),
+            Command::Config(ConfigCommand::Path)
+        );
+        let Command::Config(ConfigCommand::Set(overrides)) =
+            parse_args(&args(&["config", "set", "-t", "8"])).unwrap()
+        else {
+            panic!("Expected config set command");
+        };
+        assert_eq!(overrides.thread_count, Some(8));
+        assert!(parse_args(&args(&["config", "show", "extra"])).is_err());
+    }
+}
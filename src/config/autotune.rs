@@ -0,0 +1,253 @@
+//! Auto-tuning
+//!
+//! Autotune runs two short sweeps on the target path, one sequential and one
+//! random, and recommends the block size and concurrency that reached the
+//! highest throughput. Settings that come within a tolerance of the best are
+//! preferred when they use less concurrency or smaller blocks, since they
+//! get the same speed for less load.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::config::{BenchmarkConfig, BenchmarkMode, SweepConfig, WorkloadTuning};
+use crate::models::SweepResult;
+use crate::{DIOrbError, Result};
+
+/// Probe settings and the values each workload is probed over
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutotuneConfig {
+    /// Test file size for sequential probes
+    pub probe_file_size: u64,
+    /// Duration of each random probe
+    pub probe_duration: Duration,
+    /// Values to probe sequential reads over
+    pub sequential: SweepConfig,
+    /// Values to probe random reads and writes over
+    ///
+    /// Queue depths are only probed on a queueing engine.
+    pub random: SweepConfig,
+    /// Settings within this fraction of the best throughput count as equally fast
+    pub tolerance: f64,
+}
+
+impl Default for AutotuneConfig {
+    fn default() -> Self {
+        Self {
+            probe_file_size: 64 * 1024 * 1024,
+            probe_duration: Duration::from_secs(2),
+            sequential: SweepConfig::default()
+                .with_block_sizes(vec![64 * 1024, 256 * 1024, 1024 * 1024])
+                .with_thread_counts(vec![1, 2, 4]),
+            random: SweepConfig::default()
+                .with_block_sizes(vec![4 * 1024, 16 * 1024, 64 * 1024])
+                .with_thread_counts(vec![1, 4])
+                .with_queue_depths(vec![1, 8, 32]),
+            tolerance: 0.05,
+        }
+    }
+}
+
+impl AutotuneConfig {
+    /// Set the test file size for sequential probes
+    pub fn with_probe_file_size(mut self, probe_file_size: u64) -> Self {
+        self.probe_file_size = probe_file_size;
+        self
+    }
+
+    /// Set the duration of each random probe
+    pub fn with_probe_duration(mut self, probe_duration: Duration) -> Self {
+        self.probe_duration = probe_duration;
+        self
+    }
+
+    /// Set the fraction of the best throughput that still counts as equally fast
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Base configuration of the sequential probes, on `config`'s path
+    pub fn sequential_base(&self, config: &BenchmarkConfig) -> BenchmarkConfig {
+        let mut base = probe_base(config, BenchmarkMode::SequentialRead);
+        base.file_size = self.probe_file_size;
+        base
+    }
+
+    /// Base configuration of the random probes, on `config`'s path and engine
+    pub fn random_base(&self, config: &BenchmarkConfig) -> BenchmarkConfig {
+        let mut base = probe_base(config, BenchmarkMode::RandomReadWrite);
+        base.duration = self.probe_duration;
+        base.file_size = self.probe_file_size;
+        base
+    }
+
+    /// Values to probe random workloads over on `base`'s engine
+    pub fn random_sweep(&self, base: &BenchmarkConfig) -> SweepConfig {
+        let mut sweep = self.random.clone();
+        if !base.io_engine.supports_queue_depth() {
+            sweep.queue_depths.clear();
+        }
+        sweep
+    }
+
+    /// Validate the probe settings and both sweeps against `config`
+    pub fn validate(&self, config: &BenchmarkConfig) -> Result<()> {
+        if self.probe_duration.is_zero() {
+            return Err(DIOrbError::ConfigError(
+                "Probe time must be greater than 0".to_string(),
+            ));
+        }
+        if !(0.0..1.0).contains(&self.tolerance) {
+            return Err(DIOrbError::ConfigError(format!(
+                "Tolerance must be between 0 and 1 (got {})",
+                self.tolerance
+            )));
+        }
+        self.sequential.validate(&self.sequential_base(config))?;
+        let random = self.random_base(config);
+        self.random_sweep(&random).validate(&random)
+    }
+
+    /// Pick the settings to recommend from a finished probe sweep
+    ///
+    /// Among the entries within `tolerance` of the highest throughput, the
+    /// one with the fewest requests in flight wins, then the smallest block.
+    pub fn recommend(&self, result: &SweepResult) -> Option<WorkloadTuning> {
+        let best = result
+            .entries
+            .iter()
+            .map(|entry| entry.result.metrics.throughput_mbps)
+            .fold(f64::NAN, f64::max);
+        let threshold = best * (1.0 - self.tolerance);
+        let entry = result
+            .entries
+            .iter()
+            .filter(|entry| entry.result.metrics.throughput_mbps >= threshold)
+            .min_by_key(|entry| {
+                let point = entry.point;
+                let in_flight = point.thread_count * point.queue_depth.unwrap_or(1);
+                (in_flight, point.thread_count, point.block_size)
+            })?;
+        Some(WorkloadTuning {
+            block_size: entry.point.block_size,
+            thread_count: entry.point.thread_count,
+            queue_depth: entry.point.queue_depth,
+            throughput_mbps: entry.result.metrics.throughput_mbps,
+            iops: entry.result.metrics.iops,
+        })
+    }
+}
+
+/// Probe configuration in `mode` that keeps `config`'s path and I/O settings
+fn probe_base(config: &BenchmarkConfig, mode: BenchmarkMode) -> BenchmarkConfig {
+    let mut base = config.clone();
+    base.block_size = mode.default_block_size();
+    base.thread_count = mode.default_thread_count();
+    base.mode = mode;
+    // Probes are short single runs at full speed
+    base.ramp_time = Duration::ZERO;
+    base.steady_state = None;
+    base.repetitions = 1;
+    base.target_cv = None;
+    base.rate_limit = None;
+    base
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{IoEngineKind, SweepPoint};
+    use crate::models::{BenchmarkResult, PerformanceMetrics, SweepEntry, SystemInfo};
+
+    fn entry(block_size: u64, thread_count: usize, queue_depth: usize, mbps: f64) -> SweepEntry {
+        let config = BenchmarkConfig::random_read_write()
+            .with_block_size(block_size)
+            .with_thread_count(thread_count)
+            .with_queue_depth(queue_depth);
+        let metrics = PerformanceMetrics {
+            throughput_mbps: mbps,
+            iops: mbps * 1024.0 * 1024.0 / block_size as f64,
+            ..PerformanceMetrics::default()
+        };
+        SweepEntry {
+            point: SweepPoint::of(&config),
+            result: BenchmarkResult::with_system_info(config, metrics, SystemInfo::default()),
+        }
+    }
+
+    fn sweep(entries: Vec<SweepEntry>) -> SweepResult {
+        SweepResult::new(
+            BenchmarkConfig::random_read_write(),
+            AutotuneConfig::default().random,
+            entries,
+        )
+    }
+
+    #[test]
+    fn test_recommend_prefers_less_load_within_tolerance() {
+        let autotune = AutotuneConfig::default();
+        let result = sweep(vec![
+            entry(4096, 1, 1, 40.0),
+            entry(4096, 4, 32, 600.0),
+            entry(65536, 1, 8, 1500.0),
+            // Fastest, but barely faster for four times the load
+            entry(65536, 4, 8, 1540.0),
+            entry(16384, 1, 8, 900.0),
+        ]);
+        let tuning = autotune.recommend(&result).unwrap();
+        assert_eq!(tuning.block_size, 65536);
+        assert_eq!(tuning.thread_count, 1);
+        assert_eq!(tuning.queue_depth, Some(8));
+        assert_eq!(tuning.throughput_mbps, 1500.0);
+
+        // Without tolerance the fastest wins outright
+        let strict = autotune.clone().with_tolerance(0.0);
+        assert_eq!(strict.recommend(&result).unwrap().thread_count, 4);
+
+        // Equal speed and load picks the smaller block
+        let result = sweep(vec![entry(65536, 2, 1, 500.0), entry(16384, 2, 1, 500.0)]);
+        assert_eq!(autotune.recommend(&result).unwrap().block_size, 16384);
+
+        assert!(autotune.recommend(&sweep(Vec::new())).is_none());
+    }
+
+    #[test]
+    fn test_probe_configurations() {
+        let autotune = AutotuneConfig::default().with_probe_duration(Duration::from_secs(1));
+        let saved = BenchmarkConfig::random_read_write()
+            .with_disk_path(std::env::temp_dir())
+            .with_duration(Duration::from_secs(60))
+            .with_repetitions(5);
+
+        let sequential = autotune.sequential_base(&saved);
+        assert_eq!(sequential.mode, BenchmarkMode::SequentialRead);
+        assert_eq!(sequential.file_size, autotune.probe_file_size);
+        assert_eq!(sequential.repetitions, 1);
+
+        let random = autotune.random_base(&saved);
+        assert_eq!(random.duration, Duration::from_secs(1));
+        assert_eq!(random.disk_path, saved.disk_path);
+        assert!(autotune.validate(&saved).is_ok());
+
+        // Queue depth is only probed where the engine queues requests
+        let sync = saved.clone().with_io_engine(IoEngineKind::Sync);
+        assert!(autotune
+            .random_sweep(&autotune.random_base(&sync))
+            .queue_depths
+            .is_empty());
+        assert!(autotune.validate(&sync).is_ok());
+        let threads = saved.with_io_engine(IoEngineKind::ThreadPool);
+        assert_eq!(
+            autotune
+                .random_sweep(&autotune.random_base(&threads))
+                .queue_depths,
+            vec![1, 8, 32]
+        );
+
+        assert!(autotune
+            .clone()
+            .with_tolerance(1.5)
+            .validate(&BenchmarkConfig::default())
+            .is_err());
+    }
+}
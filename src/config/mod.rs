@@ -3,15 +3,27 @@
 //! Handles loading, saving, and validation of benchmark configuration
 //! and user preferences.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
-use std::fs;
 use serde::{Deserialize, Serialize};
 use crate::{DIOrbError, Result, APP_NAME, CONFIG_FILE};
 
+pub mod autotune;
 pub mod persistence;
+pub mod profile;
+pub mod saturation;
+pub mod sweep;
 
-use crate::models::BenchmarkResult;
+pub use autotune::AutotuneConfig;
+pub use profile::{ConfigFile, Profile, WorkloadTuning};
+pub use saturation::{LoadLadder, SaturationConfig, MAX_SATURATION_STEPS};
+pub use sweep::{SweepConfig, SweepParameter, SweepPoint};
+
+use crate::io::DirectIoMode;
+use crate::models::{BenchmarkResult, Percentile, TimelineRecorder};
+
+/// Fewest runs a coefficient of variation is judged on
+pub const MIN_RUNS_FOR_CV: usize = 3;
 
 /// Benchmark configuration structure containing all test parameters
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,14 +38,51 @@ pub struct BenchmarkConfig {
     pub block_size: u64,
     /// Test duration for time-based benchmarks
     pub duration: Duration,
+    /// Warm-up time before measurement starts; I/O issued during it is not counted
+    #[serde(default)]
+    pub ramp_time: Duration,
     /// Number of concurrent threads/workers
     pub thread_count: usize,
     /// Whether to keep temporary files after testing
     pub keep_temp_files: bool,
+    /// I/O engine used for random and mixed workloads
+    #[serde(default)]
+    pub io_engine: IoEngineKind,
+    /// Requests kept in flight per worker (`None` uses the knee measured by the latest
+    /// saturation run on the path, or picks one for the detected storage type)
+    #[serde(default)]
+    pub queue_depth: Option<usize>,
+    /// Fail instead of falling back to buffered I/O when direct I/O is unavailable
+    #[serde(default)]
+    pub strict_direct_io: bool,
+    /// How random and mixed workloads spread block offsets over the test file
+    #[serde(default)]
+    pub access_pattern: AccessPattern,
+    /// Seed for random offsets and read/write choices (`None` picks one per run)
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Latency percentiles reported in results
+    #[serde(default = "default_percentiles")]
+    pub percentiles: Vec<Percentile>,
+    /// Length of the intervals sampled into the result timeline (zero disables sampling)
+    #[serde(default = "default_sample_interval")]
+    pub sample_interval: Duration,
+    /// Run time-based modes until steady state instead of for `duration`
+    #[serde(default)]
+    pub steady_state: Option<SteadyStateConfig>,
+    /// Independent runs grouped into one result (the most runs when `target_cv` is set)
+    #[serde(default = "default_repetitions")]
+    pub repetitions: usize,
+    /// Stop repeating once the throughput coefficient of variation drops to this fraction
+    #[serde(default)]
+    pub target_cv: Option<f64>,
+    /// Issue requests at a target rate per worker instead of as fast as possible
+    #[serde(default)]
+    pub rate_limit: Option<RateLimitConfig>,
 }
 
 /// Benchmark mode variants for different test types
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum BenchmarkMode {
     /// Sequential write operations
     SequentialWrite,
@@ -48,6 +97,135 @@ pub enum BenchmarkMode {
     },
 }
 
+/// Distribution of block offsets for random and mixed workloads
+///
+/// Fractions are relative to the number of blocks in the test file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum AccessPattern {
+    /// Every block is equally likely
+    #[default]
+    Uniform,
+    /// Block popularity follows a Zipf law with skew `theta` (0 < theta < 1);
+    /// the first blocks of the file are the most popular
+    Zipfian {
+        /// Skew, where values close to 1 concentrate accesses on few blocks
+        theta: f64,
+    },
+    /// A fixed share of operations hits a hot region at the start of the file
+    HotSpot {
+        /// Fraction of operations directed at the hot region (0.0 to 1.0)
+        hot_ops: f64,
+        /// Fraction of the file that is hot (0.0 to 1.0)
+        hot_data: f64,
+    },
+    /// Offsets are normally distributed around a point in the file
+    Normal {
+        /// Center of the distribution (0.0 = start, 1.0 = end of file)
+        center: f64,
+        /// Standard deviation as a fraction of the file
+        std_dev: f64,
+    },
+}
+
+/// I/O engine variants for issuing benchmark requests
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IoEngineKind {
+    /// One blocking request at a time
+    #[default]
+    Sync,
+    /// Linux io_uring with several requests in flight
+    IoUring,
+    /// Positional reads/writes issued in parallel from dedicated threads
+    ThreadPool,
+}
+
+/// Where the queue depth of a random or mixed run came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QueueDepthSource {
+    /// Set explicitly in the configuration
+    Configured,
+    /// Knee of the latest saturation run on the same path, engine and thread count
+    Measured,
+    /// Guess for the detected storage type
+    StorageType,
+}
+
+/// Metric that has to settle for a run to reach steady state
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SteadyStateMetric {
+    /// Throughput in MB/s
+    #[default]
+    Throughput,
+    /// Operations per second
+    Iops,
+}
+
+/// Run-until-steady settings for time-based modes
+///
+/// The run ends once the metric, sampled every `sample_interval`, stays
+/// inside the tolerance band around its mean for a whole `window`, or when
+/// `max_duration` is up.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SteadyStateConfig {
+    /// Metric that has to settle
+    #[serde(default)]
+    pub metric: SteadyStateMetric,
+    /// Rolling window the metric has to stay inside the band for
+    pub window: Duration,
+    /// Allowed deviation from the window mean as a fraction (`None` uses the
+    /// accuracy threshold for the measured throughput)
+    #[serde(default)]
+    pub tolerance: Option<f64>,
+    /// Measured time after which the run ends even if steady state was not reached
+    pub max_duration: Duration,
+}
+
+impl Default for SteadyStateConfig {
+    fn default() -> Self {
+        Self {
+            metric: SteadyStateMetric::Throughput,
+            window: Duration::from_secs(30),
+            tolerance: None,
+            max_duration: Duration::from_secs(600),
+        }
+    }
+}
+
+/// How requests are spaced in a rate-limited run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArrivalProcess {
+    /// Evenly spaced at the target rate
+    #[default]
+    Fixed,
+    /// Exponentially distributed gaps averaging the target rate, like many independent clients
+    Poisson,
+}
+
+/// Target rate of a rate-limited run
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RateTarget {
+    /// Operations per second
+    Iops(f64),
+    /// Bytes per second
+    Bandwidth(u64),
+}
+
+/// Open-loop load settings
+///
+/// Each worker issues requests at the times an arrival schedule says they
+/// are due, whether or not earlier ones have finished, and latency counts
+/// from those intended times. A stalled device then shows up in the
+/// latency of every request that had to wait instead of only in the one
+/// that stalled (coordinated omission).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    /// Target rate per worker
+    pub target: RateTarget,
+    /// How requests are spaced
+    #[serde(default)]
+    pub arrivals: ArrivalProcess,
+}
+
 impl Default for BenchmarkConfig {
     fn default() -> Self {
         Self {
@@ -56,12 +234,36 @@ impl Default for BenchmarkConfig {
             file_size: 1024 * 1024 * 1024, // 1 GiB
             block_size: 64 * 1024, // 64 KiB
             duration: Duration::from_secs(30),
+            ramp_time: Duration::ZERO,
             thread_count: 1,
             keep_temp_files: false,
+            io_engine: IoEngineKind::Sync,
+            queue_depth: None,
+            strict_direct_io: false,
+            access_pattern: AccessPattern::Uniform,
+            seed: None,
+            percentiles: default_percentiles(),
+            sample_interval: default_sample_interval(),
+            steady_state: None,
+            repetitions: default_repetitions(),
+            target_cv: None,
+            rate_limit: None,
         }
     }
 }
 
+fn default_percentiles() -> Vec<Percentile> {
+    Percentile::DEFAULTS.to_vec()
+}
+
+fn default_repetitions() -> usize {
+    1
+}
+
+fn default_sample_interval() -> Duration {
+    Duration::from_secs(1)
+}
+
 impl BenchmarkConfig {
     /// Create a new benchmark configuration with default values
     pub fn new() -> Self {
@@ -164,15 +366,10 @@ impl BenchmarkConfig {
         }
 
         // For sequential operations, file size should be larger than block size
-        match self.mode {
-            BenchmarkMode::SequentialWrite | BenchmarkMode::SequentialRead => {
-                if self.file_size < self.block_size {
-                    return Err(DIOrbError::ConfigError(
-                        "File size must be larger than block size for sequential operations".to_string()
-                    ));
-                }
-            }
-            _ => {}
+        if self.mode.uses_file_size() && self.file_size < self.block_size {
+            return Err(DIOrbError::ConfigError(
+                "File size must be larger than block size for sequential operations".to_string()
+            ));
         }
 
         // Validate duration constraints
@@ -190,6 +387,13 @@ impl BenchmarkConfig {
             ));
         }
 
+        if self.ramp_time > MAX_DURATION {
+            return Err(DIOrbError::ConfigError(
+                format!("Ramp time too long: {}s (max: {}s)", 
+                    self.ramp_time.as_secs(), MAX_DURATION.as_secs())
+            ));
+        }
+
         // Validate thread count constraints
         if self.thread_count == 0 {
             return Err(DIOrbError::ConfigError(
@@ -205,16 +409,64 @@ impl BenchmarkConfig {
             ));
         }
 
+        if let Some(depth) = self.queue_depth {
+            const MAX_QUEUE_DEPTH: usize = 256;
+            if depth == 0 || depth > MAX_QUEUE_DEPTH {
+                return Err(DIOrbError::ConfigError(
+                    format!("Queue depth must be between 1 and {}", MAX_QUEUE_DEPTH)
+                ));
+            }
+        }
+
+        self.access_pattern.validate()?;
+
+        const MIN_SAMPLE_INTERVAL: Duration = Duration::from_millis(10);
+        if !self.sample_interval.is_zero() && self.sample_interval < MIN_SAMPLE_INTERVAL {
+            return Err(DIOrbError::ConfigError(
+                format!("Sample interval must be 0 (disabled) or at least {:?}", MIN_SAMPLE_INTERVAL)
+            ));
+        }
+
+        if let Some(steady_state) = &self.steady_state {
+            if !self.mode.uses_duration() {
+                return Err(DIOrbError::ConfigError(
+                    "Steady-state detection needs a time-based mode".to_string()
+                ));
+            }
+            steady_state.validate(self.sample_interval)?;
+        }
+
+        const MAX_REPETITIONS: usize = 100;
+        if self.repetitions == 0 || self.repetitions > MAX_REPETITIONS {
+            return Err(DIOrbError::ConfigError(
+                format!("Repetitions must be between 1 and {}", MAX_REPETITIONS)
+            ));
+        }
+
+        if let Some(target_cv) = self.target_cv {
+            if !(target_cv > 0.0 && target_cv < 1.0) {
+                return Err(DIOrbError::ConfigError(
+                    "Target CV must be between 0.0 and 1.0 (exclusive)".to_string()
+                ));
+            }
+            if self.repetitions < MIN_RUNS_FOR_CV {
+                return Err(DIOrbError::ConfigError(
+                    format!("Repeating until a target CV needs room for at least {} runs", MIN_RUNS_FOR_CV)
+                ));
+            }
+        }
+
+        if let Some(rate_limit) = &self.rate_limit {
+            rate_limit.validate(self.block_size)?;
+        }
+
         // Validate mode-specific constraints
-        match &self.mode {
-            BenchmarkMode::Mixed { read_ratio } => {
-                if *read_ratio < 0.0 || *read_ratio > 1.0 {
-                    return Err(DIOrbError::ConfigError(
-                        "Read ratio must be between 0.0 and 1.0".to_string()
-                    ));
-                }
+        if let BenchmarkMode::Mixed { read_ratio } = &self.mode {
+            if *read_ratio < 0.0 || *read_ratio > 1.0 {
+                return Err(DIOrbError::ConfigError(
+                    "Read ratio must be between 0.0 and 1.0".to_string()
+                ));
             }
-            _ => {}
         }
 
         Ok(())
@@ -250,6 +502,12 @@ impl BenchmarkConfig {
         self
     }
 
+    /// Set the warm-up time excluded from measurements
+    pub fn with_ramp_time(mut self, ramp_time: Duration) -> Self {
+        self.ramp_time = ramp_time;
+        self
+    }
+
     /// Set the number of threads
     pub fn with_thread_count(mut self, count: usize) -> Self {
         self.thread_count = count;
@@ -262,60 +520,119 @@ impl BenchmarkConfig {
         self
     }
 
-    /// Load configuration from the standard config file location
-    /// Returns default configuration if file doesn't exist
-    pub fn load() -> Result<Self> {
-        let config_path = Self::config_file_path()?;
-        
-        if !config_path.exists() {
-            // Return default configuration if file doesn't exist
-            return Ok(Self::default());
-        }
+    /// Set the I/O engine
+    pub fn with_io_engine(mut self, engine: IoEngineKind) -> Self {
+        self.io_engine = engine;
+        self
+    }
 
-        let content = fs::read_to_string(&config_path)
-            .map_err(|e| DIOrbError::ConfigError(
-                format!("Failed to read config file {}: {}", config_path.display(), e)
-            ))?;
+    /// Set the number of requests kept in flight per worker
+    pub fn with_queue_depth(mut self, depth: usize) -> Self {
+        self.queue_depth = Some(depth);
+        self
+    }
 
-        let config: Self = toml::from_str(&content)
-            .map_err(|e| DIOrbError::ConfigError(
-                format!("Failed to parse config file {}: {}", config_path.display(), e)
-            ))?;
+    /// Set whether falling back to buffered I/O is an error
+    pub fn with_strict_direct_io(mut self, strict: bool) -> Self {
+        self.strict_direct_io = strict;
+        self
+    }
 
-        // Validate the loaded configuration
-        config.validate()?;
-        
-        Ok(config)
+    /// Set the offset distribution for random and mixed workloads
+    pub fn with_access_pattern(mut self, pattern: AccessPattern) -> Self {
+        self.access_pattern = pattern;
+        self
     }
 
-    /// Save configuration to the standard config file location
-    pub fn save(&self) -> Result<()> {
-        // Validate before saving
-        self.validate()?;
+    /// Set the seed for random workloads, making their request sequence reproducible
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
 
-        let config_path = Self::config_file_path()?;
-        
-        // Create parent directory if it doesn't exist
-        if let Some(parent) = config_path.parent() {
-            fs::create_dir_all(parent)
-                .map_err(|e| DIOrbError::ConfigError(
-                    format!("Failed to create config directory {}: {}", parent.display(), e)
-                ))?;
-        }
+    /// Set the latency percentiles reported in results
+    pub fn with_percentiles(mut self, percentiles: Vec<Percentile>) -> Self {
+        self.percentiles = percentiles;
+        self
+    }
 
-        let content = toml::to_string_pretty(self)
-            .map_err(|e| DIOrbError::ConfigError(
-                format!("Failed to serialize configuration: {}", e)
-            ))?;
+    /// Set the timeline sampling interval (zero disables sampling)
+    pub fn with_sample_interval(mut self, interval: Duration) -> Self {
+        self.sample_interval = interval;
+        self
+    }
 
-        fs::write(&config_path, content)
-            .map_err(|e| DIOrbError::ConfigError(
-                format!("Failed to write config file {}: {}", config_path.display(), e)
-            ))?;
+    /// Run time-based modes until steady state (`None` runs for `duration`)
+    pub fn with_steady_state(mut self, steady_state: Option<SteadyStateConfig>) -> Self {
+        self.steady_state = steady_state;
+        self
+    }
+
+    /// Set how many independent runs are grouped into one result
+    pub fn with_repetitions(mut self, repetitions: usize) -> Self {
+        self.repetitions = repetitions;
+        self
+    }
+
+    /// Repeat runs until the throughput coefficient of variation drops to `target_cv`
+    pub fn with_target_cv(mut self, target_cv: Option<f64>) -> Self {
+        self.target_cv = target_cv;
+        self
+    }
+
+    /// Issue requests at a target rate per worker (`None` runs as fast as possible)
+    pub fn with_rate_limit(mut self, rate_limit: Option<RateLimitConfig>) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+
+    /// Longest measured time of a time-based run
+    pub fn run_time_limit(&self) -> Duration {
+        self.steady_state.map_or(self.duration, |steady_state| steady_state.max_duration)
+    }
+
+    /// Start a timeline recorder if sampling is enabled
+    pub fn timeline_recorder(&self) -> Option<TimelineRecorder> {
+        (!self.sample_interval.is_zero()).then(|| TimelineRecorder::new(self.sample_interval))
+    }
 
+    /// Check the I/O mode a test file was opened with against `strict_direct_io`
+    pub fn check_direct_io(&self, mode: DirectIoMode) -> Result<()> {
+        if self.strict_direct_io && mode != DirectIoMode::Direct {
+            return Err(DIOrbError::DirectIoUnsupported(format!(
+                "{}: test file fell back to {}",
+                self.disk_path.display(),
+                mode.description()
+            )));
+        }
         Ok(())
     }
 
+    /// Load configuration from the standard config file location
+    /// Returns default configuration if file doesn't exist
+    pub fn load() -> Result<Self> {
+        Ok(ConfigFile::load()?.config)
+    }
+
+    /// Save configuration to the standard config file location
+    /// Profiles already saved in the file are kept
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&Self::config_file_path()?)
+    }
+
+    /// Save configuration to the config file at `path`, keeping its profiles
+    ///
+    /// A file that cannot be read or parsed is left alone rather than
+    /// overwritten, so its profiles are not lost.
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        // Validate before saving
+        self.validate()?;
+
+        let mut file = ConfigFile::load_from(path)?;
+        file.config = self.clone();
+        file.save_to(path)
+    }
+
     /// Get the standard configuration file path
     /// Uses $CONFIG_HOME/diorb.toml or falls back to $HOME/.config/diorb.toml
     pub fn config_file_path() -> Result<PathBuf> {
@@ -367,6 +684,167 @@ impl BenchmarkMode {
     }
 }
 
+impl IoEngineKind {
+    /// Get a human-readable description of the engine
+    pub fn description(&self) -> &'static str {
+        match self {
+            IoEngineKind::Sync => "Sync",
+            IoEngineKind::IoUring => "io_uring",
+            IoEngineKind::ThreadPool => "Thread pool",
+        }
+    }
+
+    /// Check if this engine can keep more than one request in flight
+    pub fn supports_queue_depth(&self) -> bool {
+        !matches!(self, IoEngineKind::Sync)
+    }
+}
+
+impl QueueDepthSource {
+    /// Get a human-readable description of the source
+    pub fn description(&self) -> &'static str {
+        match self {
+            QueueDepthSource::Configured => "configured",
+            QueueDepthSource::Measured => "measured by saturation run",
+            QueueDepthSource::StorageType => "guessed from storage type",
+        }
+    }
+}
+
+impl AccessPattern {
+    /// Get a human-readable description of the pattern
+    pub fn description(&self) -> String {
+        match self {
+            AccessPattern::Uniform => "Uniform".to_string(),
+            AccessPattern::Zipfian { theta } => format!("Zipfian (theta {})", theta),
+            AccessPattern::HotSpot { hot_ops, hot_data } => format!(
+                "Hot spot ({:.0}% of ops on {:.0}% of data)",
+                hot_ops * 100.0,
+                hot_data * 100.0
+            ),
+            AccessPattern::Normal { center, std_dev } => {
+                format!("Normal (center {}, std dev {})", center, std_dev)
+            }
+        }
+    }
+
+    /// Validate the pattern parameters
+    pub fn validate(&self) -> Result<()> {
+        let in_unit = |value: f64| (0.0..=1.0).contains(&value);
+        match *self {
+            AccessPattern::Uniform => Ok(()),
+            AccessPattern::Zipfian { theta } => {
+                if theta > 0.0 && theta < 1.0 {
+                    Ok(())
+                } else {
+                    Err(DIOrbError::ConfigError(
+                        "Zipfian theta must be between 0.0 and 1.0 (exclusive)".to_string()
+                    ))
+                }
+            }
+            AccessPattern::HotSpot { hot_ops, hot_data } => {
+                if in_unit(hot_ops) && hot_data > 0.0 && hot_data < 1.0 {
+                    Ok(())
+                } else {
+                    Err(DIOrbError::ConfigError(
+                        "Hot spot needs an op share between 0.0 and 1.0 and a data share between 0.0 and 1.0 (exclusive)".to_string()
+                    ))
+                }
+            }
+            AccessPattern::Normal { center, std_dev } => {
+                if in_unit(center) && std_dev > 0.0 {
+                    Ok(())
+                } else {
+                    Err(DIOrbError::ConfigError(
+                        "Normal pattern needs a center between 0.0 and 1.0 and a positive std dev".to_string()
+                    ))
+                }
+            }
+        }
+    }
+}
+
+impl SteadyStateMetric {
+    /// Get a human-readable description of the metric
+    pub fn description(&self) -> &'static str {
+        match self {
+            SteadyStateMetric::Throughput => "Throughput",
+            SteadyStateMetric::Iops => "IOPS",
+        }
+    }
+}
+
+impl SteadyStateConfig {
+    /// Validate the settings against the timeline sampling interval they are checked at
+    pub fn validate(&self, sample_interval: Duration) -> Result<()> {
+        if sample_interval.is_zero() {
+            return Err(DIOrbError::ConfigError(
+                "Steady-state detection needs timeline sampling; set a sample interval".to_string()
+            ));
+        }
+        if self.window < sample_interval * 2 {
+            return Err(DIOrbError::ConfigError(
+                format!("Steady-state window must cover at least two sample intervals ({:?})", sample_interval * 2)
+            ));
+        }
+        if self.max_duration < self.window || self.max_duration > Duration::from_secs(3600) {
+            return Err(DIOrbError::ConfigError(
+                "Steady-state max duration must be between the window and 3600s".to_string()
+            ));
+        }
+        if let Some(tolerance) = self.tolerance {
+            if !(tolerance > 0.0 && tolerance < 1.0) {
+                return Err(DIOrbError::ConfigError(
+                    "Steady-state tolerance must be between 0.0 and 1.0 (exclusive)".to_string()
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ArrivalProcess {
+    /// Get a human-readable description of the arrival process
+    pub fn description(&self) -> &'static str {
+        match self {
+            ArrivalProcess::Fixed => "Fixed interval",
+            ArrivalProcess::Poisson => "Poisson",
+        }
+    }
+}
+
+impl RateLimitConfig {
+    /// Target operations per second for requests of `block_size` bytes
+    pub fn target_iops(&self, block_size: u64) -> f64 {
+        match self.target {
+            RateTarget::Iops(iops) => iops,
+            RateTarget::Bandwidth(bytes_per_sec) => bytes_per_sec as f64 / block_size.max(1) as f64,
+        }
+    }
+
+    /// Validate the target rate for requests of `block_size` bytes
+    pub fn validate(&self, block_size: u64) -> Result<()> {
+        const MAX_IOPS: f64 = 10_000_000.0;
+        const MIN_IOPS: f64 = 0.1;
+        let iops = self.target_iops(block_size);
+        if !(MIN_IOPS..=MAX_IOPS).contains(&iops) {
+            return Err(DIOrbError::ConfigError(
+                format!("Target rate must work out to between {} and {} IOPS per worker (got {:.2})", MIN_IOPS, MAX_IOPS, iops)
+            ));
+        }
+        Ok(())
+    }
+
+    /// Get a human-readable description such as `5000 IOPS, Poisson`
+    pub fn description(&self) -> String {
+        let target = match self.target {
+            RateTarget::Iops(iops) => format!("{} IOPS", iops),
+            RateTarget::Bandwidth(bytes_per_sec) => format!("{}/s", crate::util::units::format_bytes(bytes_per_sec)),
+        };
+        format!("{} per worker, {}", target, self.arrivals.description())
+    }
+}
+
 /// Configuration manager for handling config and results persistence
 pub struct ConfigManager {
     config_path: PathBuf,
@@ -395,6 +873,11 @@ impl ConfigManager {
         config.save()
     }
 
+    /// Get the configuration file path managed by this instance
+    pub fn config_path(&self) -> &PathBuf {
+        &self.config_path
+    }
+
     /// Save a benchmark result
     pub fn save_result(&self, result: BenchmarkResult) -> Result<()> {
         self.results_manager.append_result(result)
@@ -408,7 +891,7 @@ impl ConfigManager {
     /// Get the most recent results (up to limit)
     pub fn get_recent_results(&self, limit: usize) -> Result<Vec<BenchmarkResult>> {
         let mut results = self.load_results()?;
-        results.sort_by(|a, b| b.timestamp.cmp(&a.timestamp)); // Most recent first
+        results.sort_by_key(|r| std::cmp::Reverse(r.timestamp)); // Most recent first
         results.truncate(limit);
         Ok(results)
     }
@@ -449,6 +932,178 @@ mod tests {
         assert_eq!(config.keep_temp_files, deserialized.keep_temp_files);
     }
 
+    #[test]
+    fn test_engine_fields_default_when_missing() {
+        let mut table: toml::Table = toml::from_str(&toml::to_string(&BenchmarkConfig::default()).unwrap()).unwrap();
+        table.remove("io_engine");
+        table.remove("queue_depth");
+        table.remove("strict_direct_io");
+        table.remove("access_pattern");
+        table.remove("seed");
+        table.remove("percentiles");
+        table.remove("sample_interval");
+        table.remove("ramp_time");
+        table.remove("repetitions");
+        let config: BenchmarkConfig = toml::from_str(&toml::to_string(&table).unwrap()).unwrap();
+        assert_eq!(config.io_engine, IoEngineKind::Sync);
+        assert_eq!(config.queue_depth, None);
+        assert!(!config.strict_direct_io);
+        assert_eq!(config.access_pattern, AccessPattern::Uniform);
+        assert_eq!(config.seed, None);
+        assert_eq!(config.percentiles, Percentile::DEFAULTS);
+        assert_eq!(config.sample_interval, Duration::from_secs(1));
+        assert_eq!(config.ramp_time, Duration::ZERO);
+        assert_eq!(config.steady_state, None);
+        assert_eq!(config.repetitions, 1);
+        assert_eq!(config.target_cv, None);
+        assert_eq!(config.rate_limit, None);
+    }
+
+    #[test]
+    fn test_sample_interval_validation() {
+        let config = BenchmarkConfig::default().with_disk_path(std::env::temp_dir());
+        assert!(config.clone().with_sample_interval(Duration::from_millis(100)).validate().is_ok());
+        assert!(config.clone().with_sample_interval(Duration::from_millis(1)).validate().is_err());
+
+        let disabled = config.with_sample_interval(Duration::ZERO);
+        assert!(disabled.validate().is_ok());
+        assert!(disabled.timeline_recorder().is_none());
+    }
+
+    #[test]
+    fn test_steady_state_validation() {
+        let config = BenchmarkConfig::random_read_write().with_disk_path(std::env::temp_dir());
+        let steady = SteadyStateConfig::default();
+        assert!(config.clone().with_steady_state(Some(steady)).validate().is_ok());
+        assert_eq!(config.clone().with_steady_state(Some(steady)).run_time_limit(), steady.max_duration);
+        assert_eq!(config.run_time_limit(), config.duration);
+        
+        let invalid = [
+            SteadyStateConfig { window: Duration::from_millis(1500), ..steady },
+            SteadyStateConfig { max_duration: Duration::from_secs(10), ..steady },
+            SteadyStateConfig { tolerance: Some(0.0), ..steady },
+            SteadyStateConfig { tolerance: Some(1.5), ..steady },
+        ];
+        for steady in invalid {
+            assert!(config.clone().with_steady_state(Some(steady)).validate().is_err());
+        }
+        
+        // Detection runs on timeline samples and only makes sense for timed runs
+        let unsampled = config.with_sample_interval(Duration::ZERO).with_steady_state(Some(steady));
+        assert!(unsampled.validate().is_err());
+        let sequential = BenchmarkConfig::sequential_write()
+            .with_disk_path(std::env::temp_dir())
+            .with_steady_state(Some(steady));
+        assert!(sequential.validate().is_err());
+    }
+    
+    #[test]
+    fn test_repetition_validation() {
+        let config = BenchmarkConfig::default().with_disk_path(std::env::temp_dir());
+        assert!(config.clone().with_repetitions(5).validate().is_ok());
+        assert!(config.clone().with_repetitions(0).validate().is_err());
+        assert!(config.clone().with_repetitions(1000).validate().is_err());
+        
+        let until_cv = config.with_repetitions(10).with_target_cv(Some(0.03));
+        assert!(until_cv.validate().is_ok());
+        assert!(until_cv.clone().with_repetitions(2).validate().is_err());
+        assert!(until_cv.with_target_cv(Some(0.0)).validate().is_err());
+    }
+    
+    #[test]
+    fn test_rate_limit_validation() {
+        let config = BenchmarkConfig::random_read_write().with_disk_path(std::env::temp_dir());
+        let rate = |target| Some(RateLimitConfig { target, arrivals: ArrivalProcess::Poisson });
+        assert!(config.clone().with_rate_limit(rate(RateTarget::Iops(5000.0))).validate().is_ok());
+        assert!(config.clone().with_rate_limit(rate(RateTarget::Iops(0.0))).validate().is_err());
+        assert!(config.clone().with_rate_limit(rate(RateTarget::Iops(f64::NAN))).validate().is_err());
+        assert!(config.clone().with_rate_limit(rate(RateTarget::Iops(1e9))).validate().is_err());
+        assert!(config.clone().with_rate_limit(rate(RateTarget::Bandwidth(0))).validate().is_err());
+
+        // Bandwidth targets are paced in whole blocks
+        let bandwidth = RateLimitConfig { target: RateTarget::Bandwidth(40 * 1024 * 1024), arrivals: ArrivalProcess::Fixed };
+        assert_eq!(bandwidth.target_iops(4096), 10240.0);
+
+        let with_rate = config.with_rate_limit(Some(bandwidth));
+        let toml_str = toml::to_string(&with_rate).expect("Failed to serialize to TOML");
+        let deserialized: BenchmarkConfig = toml::from_str(&toml_str).expect("Failed to deserialize from TOML");
+        assert_eq!(deserialized.rate_limit, Some(bandwidth));
+    }
+
+    #[test]
+    fn test_ramp_time_validation() {
+        let config = BenchmarkConfig::default().with_disk_path(std::env::temp_dir());
+        assert!(config.clone().with_ramp_time(Duration::from_secs(5)).validate().is_ok());
+        assert!(config.with_ramp_time(Duration::from_secs(7200)).validate().is_err());
+    }
+
+    #[test]
+    fn test_percentiles_toml_round_trip() {
+        let config = BenchmarkConfig::default()
+            .with_percentiles(vec![Percentile::P99, Percentile::new(99.999).unwrap()]);
+        let toml_str = toml::to_string(&config).expect("Failed to serialize to TOML");
+        assert!(toml_str.contains("percentiles = [99.0, 99.999]"));
+        let deserialized: BenchmarkConfig = toml::from_str(&toml_str).expect("Failed to deserialize from TOML");
+        assert_eq!(deserialized.percentiles, config.percentiles);
+
+        let invalid = toml_str.replace("99.999", "120");
+        assert!(toml::from_str::<BenchmarkConfig>(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_queue_depth_validation() {
+        let config = BenchmarkConfig::random_read_write().with_disk_path(std::env::temp_dir());
+        assert!(config.clone().with_queue_depth(32).validate().is_ok());
+        assert!(config.clone().with_queue_depth(0).validate().is_err());
+        assert!(config.with_queue_depth(1024).validate().is_err());
+    }
+
+    #[test]
+    fn test_access_pattern_validation() {
+        let config = BenchmarkConfig::random_read_write().with_disk_path(std::env::temp_dir());
+        let valid = [
+            AccessPattern::Uniform,
+            AccessPattern::Zipfian { theta: 0.99 },
+            AccessPattern::HotSpot { hot_ops: 0.8, hot_data: 0.2 },
+            AccessPattern::Normal { center: 0.5, std_dev: 0.1 },
+        ];
+        for pattern in valid {
+            assert!(config.clone().with_access_pattern(pattern).validate().is_ok());
+        }
+
+        let invalid = [
+            AccessPattern::Zipfian { theta: 1.0 },
+            AccessPattern::HotSpot { hot_ops: 1.5, hot_data: 0.2 },
+            AccessPattern::HotSpot { hot_ops: 0.8, hot_data: 1.0 },
+            AccessPattern::Normal { center: 0.5, std_dev: 0.0 },
+        ];
+        for pattern in invalid {
+            assert!(config.clone().with_access_pattern(pattern).validate().is_err());
+        }
+    }
+
+    #[test]
+    fn test_access_pattern_toml_round_trip() {
+        let config = BenchmarkConfig::random_read_write()
+            .with_access_pattern(AccessPattern::HotSpot { hot_ops: 0.8, hot_data: 0.2 });
+        let toml_str = toml::to_string(&config).expect("Failed to serialize to TOML");
+        let deserialized: BenchmarkConfig = toml::from_str(&toml_str).expect("Failed to deserialize from TOML");
+        assert_eq!(deserialized.access_pattern, config.access_pattern);
+    }
+
+    #[test]
+    fn test_strict_direct_io_check() {
+        let config = BenchmarkConfig::default();
+        assert!(config.check_direct_io(DirectIoMode::Buffered).is_ok());
+
+        let strict = config.with_strict_direct_io(true);
+        assert!(strict.check_direct_io(DirectIoMode::Direct).is_ok());
+        assert!(matches!(
+            strict.check_direct_io(DirectIoMode::BufferedFsync),
+            Err(DIOrbError::DirectIoUnsupported(_))
+        ));
+    }
+
     #[test]
     fn test_config_file_path() {
         let path = BenchmarkConfig::config_file_path();
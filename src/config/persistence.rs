@@ -3,13 +3,21 @@
 //! Handles saving, loading, and rotation of benchmark results.
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 use crate::{DIOrbError, Result, APP_NAME, RESULTS_FILE, MAX_RESULTS_HISTORY};
+use crate::config::IoEngineKind;
 use crate::models::result::BenchmarkResult;
+use crate::models::{SaturationResult, SweepResult};
+
+/// Maximum number of sweeps kept in the results file
+pub const MAX_SWEEP_HISTORY: usize = 20;
+
+/// Maximum number of saturation runs kept in the results file
+pub const MAX_SATURATION_HISTORY: usize = 20;
 
 /// Results storage manager
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ResultsStorage {
     results_path: PathBuf,
 }
@@ -19,6 +27,12 @@ pub struct ResultsStorage {
 struct ResultsFile {
     version: u32,
     results: Vec<BenchmarkResult>,
+    /// Parameter sweeps, each kept as one grouped entry
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    sweeps: Vec<SweepResult>,
+    /// Saturation runs, each kept as one grouped entry
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    saturations: Vec<SaturationResult>,
 }
 
 impl Default for ResultsFile {
@@ -26,6 +40,8 @@ impl Default for ResultsFile {
         Self {
             version: 1,
             results: Vec::new(),
+            sweeps: Vec::new(),
+            saturations: Vec::new(),
         }
     }
 }
@@ -37,6 +53,11 @@ impl ResultsStorage {
         Ok(Self { results_path })
     }
 
+    /// Create a results storage manager for the results file at `results_path`
+    pub fn with_path(results_path: PathBuf) -> Self {
+        Self { results_path }
+    }
+
     /// Get the standard results file path
     /// Uses $DATA_HOME/diorb/results.json or falls back to $HOME/.local/share/diorb/results.json
     pub fn results_file_path() -> Result<PathBuf> {
@@ -50,8 +71,43 @@ impl ResultsStorage {
 
     /// Load all results from the results file
     pub fn load_results(&self) -> Result<Vec<BenchmarkResult>> {
+        Ok(self.load_file()?.results)
+    }
+
+    /// Load all sweeps from the results file
+    pub fn load_sweeps(&self) -> Result<Vec<SweepResult>> {
+        Ok(self.load_file()?.sweeps)
+    }
+
+    /// Load all saturation runs from the results file
+    pub fn load_saturations(&self) -> Result<Vec<SaturationResult>> {
+        Ok(self.load_file()?.saturations)
+    }
+
+    /// Queue depth at the knee of the latest saturation run on `path` with
+    /// `engine` and `thread_count` workers
+    ///
+    /// Only runs that raised queue depth and found a knee count.
+    pub fn measured_queue_depth(
+        &self,
+        path: &Path,
+        engine: IoEngineKind,
+        thread_count: usize,
+    ) -> Result<Option<usize>> {
+        Ok(self.load_saturations()?
+            .iter()
+            .rev()
+            .filter(|saturation| {
+                let base = &saturation.base;
+                base.disk_path == path && base.io_engine == engine && base.thread_count == thread_count
+            })
+            .find_map(SaturationResult::measured_queue_depth))
+    }
+
+    /// Read the results file, or an empty one if it does not exist yet
+    fn load_file(&self) -> Result<ResultsFile> {
         if !self.results_path.exists() {
-            return Ok(Vec::new());
+            return Ok(ResultsFile::default());
         }
 
         let content = fs::read_to_string(&self.results_path)
@@ -64,7 +120,7 @@ impl ResultsStorage {
                 format!("Failed to parse results file {}: {}", self.results_path.display(), e)
             ))?;
 
-        Ok(results_file.results)
+        Ok(results_file)
     }
 
     /// Append a new result to the results file
@@ -87,8 +143,45 @@ impl ResultsStorage {
         self.save_results(results)
     }
 
-    /// Save all results to the results file
+    /// Append a sweep to the results file as one grouped entry
+    /// Automatically rotates old sweeps if the file exceeds MAX_SWEEP_HISTORY sweeps
+    pub fn append_sweep(&self, sweep: SweepResult) -> Result<()> {
+        let mut results_file = self.load_file()?;
+        results_file.sweeps.push(sweep);
+
+        if results_file.sweeps.len() > MAX_SWEEP_HISTORY {
+            let skip_count = results_file.sweeps.len() - MAX_SWEEP_HISTORY;
+            results_file.sweeps.drain(..skip_count);
+        }
+
+        self.save_file(results_file)
+    }
+
+    /// Append a saturation run to the results file as one grouped entry
+    /// Automatically rotates old runs if the file exceeds MAX_SATURATION_HISTORY runs
+    pub fn append_saturation(&self, saturation: SaturationResult) -> Result<()> {
+        let mut results_file = self.load_file()?;
+        results_file.saturations.push(saturation);
+
+        if results_file.saturations.len() > MAX_SATURATION_HISTORY {
+            let skip_count = results_file.saturations.len() - MAX_SATURATION_HISTORY;
+            results_file.saturations.drain(..skip_count);
+        }
+
+        self.save_file(results_file)
+    }
+
+    /// Save all results to the results file, keeping stored sweeps and saturation runs
     fn save_results(&self, results: Vec<BenchmarkResult>) -> Result<()> {
+        let results_file = ResultsFile {
+            results,
+            ..self.load_file()?
+        };
+        self.save_file(results_file)
+    }
+
+    /// Write the results file
+    fn save_file(&self, results_file: ResultsFile) -> Result<()> {
         // Create parent directory if it doesn't exist
         if let Some(parent) = self.results_path.parent() {
             fs::create_dir_all(parent)
@@ -97,11 +190,6 @@ impl ResultsStorage {
                 ))?;
         }
 
-        let results_file = ResultsFile {
-            version: 1,
-            results,
-        };
-
         let content = serde_json::to_string_pretty(&results_file)
             .map_err(|e| DIOrbError::ConfigError(
                 format!("Failed to serialize results: {}", e)
@@ -121,7 +209,7 @@ impl ResultsStorage {
         Ok(results.len())
     }
 
-    /// Clear all stored results
+    /// Clear all stored results, sweeps and saturation runs
     pub fn clear_results(&self) -> Result<()> {
         if self.results_path.exists() {
             fs::remove_file(&self.results_path)
@@ -155,11 +243,13 @@ impl ResultsStorage {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::BenchmarkConfig;
+    use crate::config::{BenchmarkConfig, SaturationConfig, SweepConfig, SweepPoint};
+    use crate::models::{SaturationStep, SweepEntry};
     use crate::models::result::{BenchmarkResult, PerformanceMetrics, LatencyStats};
+    use crate::models::Percentile;
     use chrono::Utc;
     use std::time::Duration;
-    use std::collections::HashMap;
+    use std::collections::BTreeMap;
     use tempfile::TempDir;
 
     fn create_test_result() -> BenchmarkResult {
@@ -176,15 +266,24 @@ mod tests {
                     avg: Duration::from_millis(5),
                     max: Duration::from_millis(20),
                     percentiles: {
-                        let mut map = HashMap::new();
-                        map.insert(50, Duration::from_millis(4));
-                        map.insert(95, Duration::from_millis(15));
-                        map.insert(99, Duration::from_millis(18));
+                        let mut map = BTreeMap::new();
+                        map.insert(Percentile::P50, Duration::from_millis(4));
+                        map.insert(Percentile::P95, Duration::from_millis(15));
+                        map.insert(Percentile::P99, Duration::from_millis(18));
                         map
                     },
                 },
+                ..Default::default()
             },
             system_info: Default::default(),
+            direct_io_mode: None,
+            engine_fallback: None,
+            queue_depth_source: None,
+            worker_metrics: Vec::new(),
+            timeline: None,
+            steady_state: None,
+            repetitions: None,
+            open_loop: None,
         }
     }
 
@@ -322,5 +421,68 @@ mod tests {
         
         assert_eq!(results_file.version, 1);
         assert_eq!(results_file.results.len(), 1);
+        // Files without sweeps keep the original layout
+        assert!(!content.contains("sweeps"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_append_and_rotate_sweeps() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = ResultsStorage { results_path: temp_dir.path().join("results.json") };
+        storage.append_result(create_test_result()).unwrap();
+
+        let sweep = |threads: usize| {
+            let config = BenchmarkConfig::default().with_thread_count(threads);
+            SweepResult::new(
+                BenchmarkConfig::default(),
+                SweepConfig::default().with_thread_counts(vec![threads]),
+                vec![SweepEntry { point: SweepPoint::of(&config), result: create_test_result() }],
+            )
+        };
+        for threads in 1..=MAX_SWEEP_HISTORY + 2 {
+            storage.append_sweep(sweep(threads)).unwrap();
+        }
+
+        let sweeps = storage.load_sweeps().unwrap();
+        assert_eq!(sweeps.len(), MAX_SWEEP_HISTORY);
+        assert_eq!(sweeps[0].sweep.thread_counts, vec![3]);
+
+        // Results and sweeps are stored side by side without disturbing each other
+        storage.append_result(create_test_result()).unwrap();
+        assert_eq!(storage.count_results().unwrap(), 2);
+        assert_eq!(storage.load_sweeps().unwrap().len(), MAX_SWEEP_HISTORY);
+    }
+
+    #[test]
+    fn test_measured_queue_depth() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = ResultsStorage { results_path: temp_dir.path().join("results.json") };
+        let path = temp_dir.path().to_path_buf();
+        let base = BenchmarkConfig::random_read_write()
+            .with_disk_path(path.clone())
+            .with_io_engine(IoEngineKind::ThreadPool);
+        assert_eq!(storage.measured_queue_depth(&path, IoEngineKind::ThreadPool, 1).unwrap(), None);
+
+        // Latency stays flat, so the knee is the highest depth tried
+        let saturation = |base: &BenchmarkConfig, config: SaturationConfig| {
+            let steps = config.expand(base).into_iter().map(|config| SaturationStep {
+                level: config.queue_depth.unwrap() as f64,
+                result: BenchmarkResult { config, ..create_test_result() },
+            }).collect();
+            SaturationResult::new(base.clone(), config, steps)
+        };
+        storage.append_saturation(saturation(&base, SaturationConfig::queue_depths(vec![1, 4]))).unwrap();
+        storage.append_saturation(saturation(&base, SaturationConfig::queue_depths(vec![1, 16]))).unwrap();
+        let other_path = base.clone().with_disk_path(PathBuf::from("/elsewhere"));
+        storage.append_saturation(saturation(&other_path, SaturationConfig::queue_depths(vec![1, 64]))).unwrap();
+        let more_threads = base.clone().with_thread_count(4);
+        storage.append_saturation(saturation(&more_threads, SaturationConfig::queue_depths(vec![1, 32]))).unwrap();
+
+        // The latest run on the same path, engine and thread count wins
+        assert_eq!(storage.measured_queue_depth(&path, IoEngineKind::ThreadPool, 1).unwrap(), Some(16));
+        assert_eq!(storage.measured_queue_depth(&path, IoEngineKind::ThreadPool, 4).unwrap(), Some(32));
+        assert_eq!(storage.measured_queue_depth(&path, IoEngineKind::ThreadPool, 2).unwrap(), None);
+        assert_eq!(storage.measured_queue_depth(&path, IoEngineKind::IoUring, 1).unwrap(), None);
+        assert_eq!(storage.load_saturations().unwrap().len(), 4);
+    }
+}
@@ -0,0 +1,259 @@
+//! Named tuning profiles
+//!
+//! `diorb autotune` probes a path and stores the block size and concurrency
+//! that worked best as a named profile next to the saved configuration.
+//! Runs started with `--profile NAME` take those settings for their mode.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::{BenchmarkConfig, BenchmarkMode, IoEngineKind};
+use crate::{DIOrbError, Result};
+
+/// Settings that worked best for one kind of workload
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WorkloadTuning {
+    /// Block size in bytes
+    pub block_size: u64,
+    /// Number of concurrent workers
+    pub thread_count: usize,
+    /// Requests in flight per worker, for random and mixed workloads
+    #[serde(default)]
+    pub queue_depth: Option<usize>,
+    /// Throughput the probe reached with these settings, in MB/s
+    pub throughput_mbps: f64,
+    /// Operations per second the probe reached with these settings
+    pub iops: f64,
+}
+
+/// Tuned settings for sequential and random workloads on one path
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    /// Path the probes ran on
+    pub disk_path: PathBuf,
+    /// Engine the random probes ran with
+    pub io_engine: IoEngineKind,
+    /// Settings for sequential reads and writes
+    #[serde(default)]
+    pub sequential: Option<WorkloadTuning>,
+    /// Settings for random and mixed workloads
+    #[serde(default)]
+    pub random: Option<WorkloadTuning>,
+}
+
+/// Contents of the config file: the saved configuration and named profiles
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConfigFile {
+    /// Configuration runs start from
+    #[serde(flatten)]
+    pub config: BenchmarkConfig,
+    /// Profiles by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+impl Profile {
+    /// Tuned settings for `mode`, if that kind of workload was probed
+    pub fn tuning_for(&self, mode: &BenchmarkMode) -> Option<&WorkloadTuning> {
+        if mode.uses_duration() {
+            self.random.as_ref()
+        } else {
+            self.sequential.as_ref()
+        }
+    }
+
+    /// Apply the tuned settings for the configuration's mode
+    pub fn apply(&self, config: &mut BenchmarkConfig) {
+        let Some(tuning) = self.tuning_for(&config.mode) else {
+            return;
+        };
+        config.block_size = tuning.block_size;
+        config.thread_count = tuning.thread_count;
+        if config.mode.uses_duration() {
+            config.io_engine = self.io_engine;
+            config.queue_depth = tuning.queue_depth;
+        }
+    }
+}
+
+impl ConfigFile {
+    /// Load the config file from the standard location, or defaults if it doesn't exist
+    pub fn load() -> Result<Self> {
+        Self::load_from(&BenchmarkConfig::config_file_path()?)
+    }
+
+    /// Load the config file at `path`, or defaults if it doesn't exist
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path).map_err(|e| {
+            DIOrbError::ConfigError(format!(
+                "Failed to read config file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let file: Self = toml::from_str(&content).map_err(|e| {
+            DIOrbError::ConfigError(format!(
+                "Failed to parse config file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        file.config.validate()?;
+        Ok(file)
+    }
+
+    /// Save the config file to the standard location
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&BenchmarkConfig::config_file_path()?)
+    }
+
+    /// Save the config file to `path`
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        self.config.validate()?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                DIOrbError::ConfigError(format!(
+                    "Failed to create config directory {}: {}",
+                    parent.display(),
+                    e
+                ))
+            })?;
+        }
+
+        let content = toml::to_string_pretty(self).map_err(|e| {
+            DIOrbError::ConfigError(format!("Failed to serialize configuration: {}", e))
+        })?;
+        fs::write(path, content).map_err(|e| {
+            DIOrbError::ConfigError(format!(
+                "Failed to write config file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Ok(())
+    }
+
+    /// Look up a profile by name
+    pub fn profile(&self, name: &str) -> Result<&Profile> {
+        self.profiles.get(name).ok_or_else(|| {
+            let known: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            DIOrbError::ConfigError(if known.is_empty() {
+                format!(
+                    "Unknown profile: {} (run `diorb autotune` to create one)",
+                    name
+                )
+            } else {
+                format!("Unknown profile: {} (saved: {})", name, known.join(", "))
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn profile() -> Profile {
+        Profile {
+            disk_path: PathBuf::from("/data"),
+            io_engine: IoEngineKind::ThreadPool,
+            sequential: Some(WorkloadTuning {
+                block_size: 1024 * 1024,
+                thread_count: 2,
+                queue_depth: None,
+                throughput_mbps: 2000.0,
+                iops: 2000.0,
+            }),
+            random: Some(WorkloadTuning {
+                block_size: 4096,
+                thread_count: 4,
+                queue_depth: Some(32),
+                throughput_mbps: 800.0,
+                iops: 200_000.0,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_apply_profile_by_mode() {
+        let mut random = BenchmarkConfig::random_read_write();
+        profile().apply(&mut random);
+        assert_eq!(random.block_size, 4096);
+        assert_eq!(random.thread_count, 4);
+        assert_eq!(random.queue_depth, Some(32));
+        assert_eq!(random.io_engine, IoEngineKind::ThreadPool);
+
+        let mut sequential = BenchmarkConfig::sequential_read();
+        let engine = sequential.io_engine;
+        profile().apply(&mut sequential);
+        assert_eq!(sequential.block_size, 1024 * 1024);
+        assert_eq!(sequential.thread_count, 2);
+        assert_eq!(sequential.io_engine, engine);
+
+        // A workload that was not probed keeps its settings
+        let partial = Profile {
+            random: None,
+            ..profile()
+        };
+        let mut mixed = BenchmarkConfig::mixed(0.7);
+        let before = mixed.block_size;
+        partial.apply(&mut mixed);
+        assert_eq!(mixed.block_size, before);
+    }
+
+    #[test]
+    fn test_config_file_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("diorb").join("diorb.toml");
+        assert!(ConfigFile::load_from(&path).unwrap().profiles.is_empty());
+
+        let mut file = ConfigFile {
+            config: BenchmarkConfig::default().with_thread_count(3),
+            ..ConfigFile::default()
+        };
+        file.profiles.insert("nvme".to_string(), profile());
+        file.save_to(&path).unwrap();
+
+        let loaded = ConfigFile::load_from(&path).unwrap();
+        assert_eq!(loaded.config.thread_count, 3);
+        assert_eq!(loaded.profile("nvme").unwrap(), &profile());
+        assert!(loaded.profile("hdd").is_err());
+
+        // A config file without profiles still loads as a plain configuration
+        let content = fs::read_to_string(&path).unwrap();
+        let config: BenchmarkConfig = toml::from_str(&content).unwrap();
+        assert_eq!(config.thread_count, 3);
+        let plain = toml::to_string(&BenchmarkConfig::default()).unwrap();
+        fs::write(&path, plain).unwrap();
+        assert!(ConfigFile::load_from(&path).unwrap().profiles.is_empty());
+    }
+
+    #[test]
+    fn test_saving_config_keeps_profiles() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("diorb.toml");
+        let mut file = ConfigFile::default();
+        file.profiles.insert("nvme".to_string(), profile());
+        file.save_to(&path).unwrap();
+
+        let config = BenchmarkConfig::default().with_thread_count(5);
+        config.save_to(&path).unwrap();
+        let loaded = ConfigFile::load_from(&path).unwrap();
+        assert_eq!(loaded.config.thread_count, 5);
+        assert_eq!(loaded.profile("nvme").unwrap(), &profile());
+
+        // A file that does not parse is reported, not replaced
+        fs::write(&path, "thread_count = [").unwrap();
+        assert!(config.save_to(&path).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "thread_count = [");
+    }
+}
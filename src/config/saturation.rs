@@ -0,0 +1,305 @@
+//! Saturation runs
+//!
+//! A saturation run raises the offered load on a random or mixed workload
+//! step by step, through more requests in flight or a higher target rate,
+//! and watches latency climb. A `SaturationConfig` lists the load levels and
+//! when to stop, and expands into one `BenchmarkConfig` per step.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::config::{BenchmarkConfig, RateLimitConfig, RateTarget};
+use crate::{DIOrbError, Result};
+
+/// Most steps a saturation run may take
+pub const MAX_SATURATION_STEPS: usize = 32;
+
+/// How the offered load rises from one step to the next
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LoadLadder {
+    /// Requests in flight per worker, at the base thread count
+    QueueDepths(Vec<usize>),
+    /// Concurrent workers, at the base queue depth
+    Threads(Vec<usize>),
+    /// Target IOPS per worker, issued open-loop
+    TargetIops(Vec<f64>),
+}
+
+/// Load levels of a saturation run and when to stop raising them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SaturationConfig {
+    /// Load levels, lowest first
+    pub ladder: LoadLadder,
+    /// Stop once p99 latency passes this
+    #[serde(default = "default_latency_limit")]
+    pub latency_limit: Duration,
+    /// Latency degrades sharply at a step where p99 latency grows more than
+    /// this many times as fast as IOPS
+    #[serde(default = "default_knee_factor")]
+    pub knee_factor: f64,
+}
+
+fn default_latency_limit() -> Duration {
+    Duration::from_millis(100)
+}
+
+fn default_knee_factor() -> f64 {
+    1.5
+}
+
+impl Default for SaturationConfig {
+    fn default() -> Self {
+        Self {
+            // Doubling from 1 to 256
+            ladder: LoadLadder::QueueDepths((0..=8).map(|shift| 1 << shift).collect()),
+            latency_limit: default_latency_limit(),
+            knee_factor: default_knee_factor(),
+        }
+    }
+}
+
+impl LoadLadder {
+    /// Human-readable name of the load measure
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::QueueDepths(_) => "Queue depth",
+            Self::Threads(_) => "Threads",
+            Self::TargetIops(_) => "Target IOPS",
+        }
+    }
+
+    /// Number of load levels
+    pub fn len(&self) -> usize {
+        match self {
+            Self::QueueDepths(levels) => levels.len(),
+            Self::Threads(levels) => levels.len(),
+            Self::TargetIops(levels) => levels.len(),
+        }
+    }
+
+    /// Whether the ladder has no load levels
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Load level of the step at `index`
+    pub fn level(&self, index: usize) -> Option<f64> {
+        match self {
+            Self::QueueDepths(levels) => levels.get(index).map(|&level| level as f64),
+            Self::Threads(levels) => levels.get(index).map(|&level| level as f64),
+            Self::TargetIops(levels) => levels.get(index).copied(),
+        }
+    }
+
+    /// Format a load level, e.g. `16` or `5000 IOPS`
+    pub fn format_level(&self, level: f64) -> String {
+        match self {
+            Self::QueueDepths(_) | Self::Threads(_) => format!("{:.0}", level),
+            Self::TargetIops(_) => format!("{:.0} IOPS", level),
+        }
+    }
+}
+
+impl SaturationConfig {
+    /// Raise load through these queue depths
+    pub fn queue_depths(levels: Vec<usize>) -> Self {
+        Self {
+            ladder: LoadLadder::QueueDepths(levels),
+            ..Self::default()
+        }
+    }
+
+    /// Raise load through these thread counts
+    pub fn threads(levels: Vec<usize>) -> Self {
+        Self {
+            ladder: LoadLadder::Threads(levels),
+            ..Self::default()
+        }
+    }
+
+    /// Raise load through these target rates per worker
+    pub fn target_iops(levels: Vec<f64>) -> Self {
+        Self {
+            ladder: LoadLadder::TargetIops(levels),
+            ..Self::default()
+        }
+    }
+
+    /// Set the p99 latency to stop at
+    pub fn with_latency_limit(mut self, latency_limit: Duration) -> Self {
+        self.latency_limit = latency_limit;
+        self
+    }
+
+    /// Set how much faster than IOPS latency has to grow to count as degrading
+    pub fn with_knee_factor(mut self, knee_factor: f64) -> Self {
+        self.knee_factor = knee_factor;
+        self
+    }
+
+    /// Expand into one configuration per load level, lowest first
+    pub fn expand(&self, base: &BenchmarkConfig) -> Vec<BenchmarkConfig> {
+        let step = |apply: &dyn Fn(&mut BenchmarkConfig)| {
+            let mut config = base.clone();
+            apply(&mut config);
+            config
+        };
+        match &self.ladder {
+            LoadLadder::QueueDepths(levels) => levels
+                .iter()
+                .map(|&depth| step(&|config| config.queue_depth = Some(depth)))
+                .collect(),
+            LoadLadder::Threads(levels) => levels
+                .iter()
+                .map(|&threads| step(&|config| config.thread_count = threads))
+                .collect(),
+            LoadLadder::TargetIops(levels) => {
+                let arrivals = base
+                    .rate_limit
+                    .map(|rate| rate.arrivals)
+                    .unwrap_or_default();
+                levels
+                    .iter()
+                    .map(|&iops| {
+                        step(&|config| {
+                            config.rate_limit = Some(RateLimitConfig {
+                                target: RateTarget::Iops(iops),
+                                arrivals,
+                            })
+                        })
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Check whether a step with this p99 latency ends the run
+    pub fn passes_limit(&self, p99: Duration) -> bool {
+        p99 > self.latency_limit
+    }
+
+    /// Validate the load levels and every configuration they expand into
+    pub fn validate(&self, base: &BenchmarkConfig) -> Result<()> {
+        if self.ladder.len() < 2 {
+            return Err(DIOrbError::ConfigError(
+                "A saturation run needs at least two load levels".to_string(),
+            ));
+        }
+        if self.ladder.len() > MAX_SATURATION_STEPS {
+            return Err(DIOrbError::ConfigError(format!(
+                "Saturation run has {} load levels (max: {})",
+                self.ladder.len(),
+                MAX_SATURATION_STEPS
+            )));
+        }
+        let levels: Vec<f64> = (0..self.ladder.len())
+            .filter_map(|index| self.ladder.level(index))
+            .collect();
+        if levels.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(DIOrbError::ConfigError(format!(
+                "{} levels must rise from one step to the next",
+                self.ladder.description()
+            )));
+        }
+
+        if !base.mode.uses_duration() {
+            return Err(DIOrbError::ConfigError(format!(
+                "A saturation run needs a random or mixed mode (not {})",
+                base.mode.description()
+            )));
+        }
+        if matches!(self.ladder, LoadLadder::QueueDepths(_))
+            && !base.io_engine.supports_queue_depth()
+        {
+            return Err(DIOrbError::ConfigError(format!(
+                "Raising queue depth needs a queueing engine (not {})",
+                base.io_engine.description()
+            )));
+        }
+        if self.latency_limit.is_zero() {
+            return Err(DIOrbError::ConfigError(
+                "Latency limit must be greater than 0".to_string(),
+            ));
+        }
+        if !(self.knee_factor > 1.0 && self.knee_factor.is_finite()) {
+            return Err(DIOrbError::ConfigError(format!(
+                "Knee factor must be greater than 1 (got {})",
+                self.knee_factor
+            )));
+        }
+
+        for config in self.expand(base) {
+            config.validate()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ArrivalProcess, IoEngineKind};
+
+    fn base() -> BenchmarkConfig {
+        BenchmarkConfig::random_read_write()
+            .with_disk_path(std::env::temp_dir())
+            .with_io_engine(IoEngineKind::ThreadPool)
+    }
+
+    #[test]
+    fn test_expand_ladders() {
+        let configs = SaturationConfig::queue_depths(vec![1, 4, 16]).expand(&base());
+        let depths: Vec<Option<usize>> = configs.iter().map(|c| c.queue_depth).collect();
+        assert_eq!(depths, vec![Some(1), Some(4), Some(16)]);
+        assert!(configs
+            .iter()
+            .all(|c| c.thread_count == base().thread_count));
+
+        let configs = SaturationConfig::threads(vec![1, 2]).expand(&base());
+        assert_eq!(configs[1].thread_count, 2);
+        assert_eq!(configs[1].queue_depth, base().queue_depth);
+
+        // Rate ladders keep the base arrival process
+        let poisson = base().with_rate_limit(Some(RateLimitConfig {
+            target: RateTarget::Iops(10.0),
+            arrivals: ArrivalProcess::Poisson,
+        }));
+        let configs = SaturationConfig::target_iops(vec![1000.0, 2000.0]).expand(&poisson);
+        let rate = configs[1].rate_limit.unwrap();
+        assert_eq!(rate.target, RateTarget::Iops(2000.0));
+        assert_eq!(rate.arrivals, ArrivalProcess::Poisson);
+    }
+
+    #[test]
+    fn test_saturation_validation() {
+        assert!(SaturationConfig::default().validate(&base()).is_ok());
+        assert!(SaturationConfig::queue_depths(vec![4])
+            .validate(&base())
+            .is_err());
+        assert!(SaturationConfig::queue_depths(vec![4, 2])
+            .validate(&base())
+            .is_err());
+        assert!(SaturationConfig::queue_depths((1..=40).collect())
+            .validate(&base())
+            .is_err());
+        assert!(SaturationConfig::default()
+            .with_knee_factor(1.0)
+            .validate(&base())
+            .is_err());
+        assert!(SaturationConfig::default()
+            .with_latency_limit(Duration::ZERO)
+            .validate(&base())
+            .is_err());
+
+        // Queue depth only matters on a queueing engine, and load only rises on random modes
+        let sync = base().with_io_engine(IoEngineKind::Sync);
+        assert!(SaturationConfig::default().validate(&sync).is_err());
+        assert!(SaturationConfig::threads(vec![1, 2])
+            .validate(&sync)
+            .is_ok());
+        let sequential = BenchmarkConfig::sequential_write().with_disk_path(std::env::temp_dir());
+        assert!(SaturationConfig::threads(vec![1, 2])
+            .validate(&sequential)
+            .is_err());
+    }
+}
@@ -0,0 +1,328 @@
+//! Parameter sweeps
+//!
+//! Characterising a drive means running one workload over a grid of block
+//! sizes, thread counts and queue depths. A `SweepConfig` lists the values
+//! of each parameter and expands into one `BenchmarkConfig` per combination.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::BenchmarkConfig;
+use crate::util::units::format_bytes;
+use crate::{DIOrbError, Result};
+
+/// Most configurations a sweep may expand into
+pub const MAX_SWEEP_POINTS: usize = 256;
+
+/// Parameter a sweep can vary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SweepParameter {
+    /// I/O block size in bytes
+    BlockSize,
+    /// Number of concurrent workers
+    Threads,
+    /// Requests in flight per worker
+    QueueDepth,
+}
+
+/// Values to sweep for each parameter
+///
+/// A parameter without values keeps the base configuration's value. The
+/// configurations are expanded with block size varying slowest and queue
+/// depth fastest.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SweepConfig {
+    /// Block sizes in bytes
+    #[serde(default)]
+    pub block_sizes: Vec<u64>,
+    /// Thread counts
+    #[serde(default)]
+    pub thread_counts: Vec<usize>,
+    /// Queue depths per worker
+    #[serde(default)]
+    pub queue_depths: Vec<usize>,
+}
+
+/// Parameter values of one configuration in a sweep
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SweepPoint {
+    /// Block size in bytes
+    pub block_size: u64,
+    /// Number of concurrent workers
+    pub thread_count: usize,
+    /// Requests in flight per worker (`None` picks the default for the storage type)
+    pub queue_depth: Option<usize>,
+}
+
+impl SweepParameter {
+    /// Every parameter, in expansion order
+    pub const ALL: [SweepParameter; 3] = [Self::BlockSize, Self::Threads, Self::QueueDepth];
+
+    /// Human-readable name
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::BlockSize => "Block size",
+            Self::Threads => "Threads",
+            Self::QueueDepth => "Queue depth",
+        }
+    }
+
+    /// Value of this parameter at `point`
+    pub fn value(&self, point: &SweepPoint) -> Option<u64> {
+        match self {
+            Self::BlockSize => Some(point.block_size),
+            Self::Threads => Some(point.thread_count as u64),
+            Self::QueueDepth => point.queue_depth.map(|depth| depth as u64),
+        }
+    }
+
+    /// Format a value of this parameter, e.g. `4.0 KiB` for a block size
+    pub fn format_value(&self, value: u64) -> String {
+        match self {
+            Self::BlockSize => format_bytes(value),
+            Self::Threads | Self::QueueDepth => value.to_string(),
+        }
+    }
+}
+
+impl SweepConfig {
+    /// Set the block sizes to sweep
+    pub fn with_block_sizes(mut self, block_sizes: Vec<u64>) -> Self {
+        self.block_sizes = block_sizes;
+        self
+    }
+
+    /// Set the thread counts to sweep
+    pub fn with_thread_counts(mut self, thread_counts: Vec<usize>) -> Self {
+        self.thread_counts = thread_counts;
+        self
+    }
+
+    /// Set the queue depths to sweep
+    pub fn with_queue_depths(mut self, queue_depths: Vec<usize>) -> Self {
+        self.queue_depths = queue_depths;
+        self
+    }
+
+    /// Parameters with values to sweep, in expansion order
+    pub fn parameters(&self) -> Vec<SweepParameter> {
+        SweepParameter::ALL
+            .into_iter()
+            .filter(|parameter| self.values(*parameter) > 0)
+            .collect()
+    }
+
+    /// Number of configurations the sweep expands into
+    pub fn point_count(&self) -> usize {
+        SweepParameter::ALL
+            .into_iter()
+            .map(|parameter| self.values(parameter).max(1))
+            .product()
+    }
+
+    /// Expand into one configuration per combination of values
+    pub fn expand(&self, base: &BenchmarkConfig) -> Vec<BenchmarkConfig> {
+        let block_sizes = fallback(&self.block_sizes, base.block_size);
+        let thread_counts = fallback(&self.thread_counts, base.thread_count);
+        let queue_depths: Vec<Option<usize>> = if self.queue_depths.is_empty() {
+            vec![base.queue_depth]
+        } else {
+            self.queue_depths.iter().copied().map(Some).collect()
+        };
+
+        let mut configs = Vec::with_capacity(self.point_count());
+        for &block_size in &block_sizes {
+            for &thread_count in &thread_counts {
+                for &queue_depth in &queue_depths {
+                    let mut config = base.clone();
+                    config.block_size = block_size;
+                    config.thread_count = thread_count;
+                    config.queue_depth = queue_depth;
+                    configs.push(config);
+                }
+            }
+        }
+        configs
+    }
+
+    /// Validate the sweep and every configuration it expands into
+    pub fn validate(&self, base: &BenchmarkConfig) -> Result<()> {
+        if self.parameters().is_empty() {
+            return Err(DIOrbError::ConfigError(
+                "A sweep needs values for at least one parameter".to_string(),
+            ));
+        }
+
+        let points = self.point_count();
+        if points > MAX_SWEEP_POINTS {
+            return Err(DIOrbError::ConfigError(format!(
+                "Sweep expands into {} configurations (max: {})",
+                points, MAX_SWEEP_POINTS
+            )));
+        }
+
+        for parameter in self.parameters() {
+            let mut values: Vec<u64> = match parameter {
+                SweepParameter::BlockSize => self.block_sizes.clone(),
+                SweepParameter::Threads => self.thread_counts.iter().map(|&v| v as u64).collect(),
+                SweepParameter::QueueDepth => self.queue_depths.iter().map(|&v| v as u64).collect(),
+            };
+            values.sort_unstable();
+            if let Some(pair) = values.windows(2).find(|pair| pair[0] == pair[1]) {
+                return Err(DIOrbError::ConfigError(format!(
+                    "{} {} appears twice in the sweep",
+                    parameter.description(),
+                    parameter.format_value(pair[0])
+                )));
+            }
+        }
+
+        // Queue depth only shapes random and mixed runs on a queueing engine
+        let queues = base.mode.uses_duration() && base.io_engine.supports_queue_depth();
+        if !self.queue_depths.is_empty() && !queues {
+            return Err(DIOrbError::ConfigError(format!(
+                "Sweeping queue depth needs a random or mixed mode with a queueing engine (not {} with {})",
+                base.mode.description(),
+                base.io_engine.description()
+            )));
+        }
+
+        for config in self.expand(base) {
+            config.validate()?;
+        }
+        Ok(())
+    }
+
+    fn values(&self, parameter: SweepParameter) -> usize {
+        match parameter {
+            SweepParameter::BlockSize => self.block_sizes.len(),
+            SweepParameter::Threads => self.thread_counts.len(),
+            SweepParameter::QueueDepth => self.queue_depths.len(),
+        }
+    }
+}
+
+impl SweepPoint {
+    /// Parameter values of `config`
+    pub fn of(config: &BenchmarkConfig) -> Self {
+        Self {
+            block_size: config.block_size,
+            thread_count: config.thread_count,
+            queue_depth: config.queue_depth,
+        }
+    }
+
+    /// Describe the swept parameters, e.g. `Block size 4.0 KiB, Threads 8`
+    pub fn description(&self, parameters: &[SweepParameter]) -> String {
+        parameters
+            .iter()
+            .filter_map(|parameter| {
+                let value = parameter.value(self)?;
+                Some(format!(
+                    "{} {}",
+                    parameter.description(),
+                    parameter.format_value(value)
+                ))
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+fn fallback<T: Copy>(values: &[T], base: T) -> Vec<T> {
+    if values.is_empty() {
+        vec![base]
+    } else {
+        values.to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::IoEngineKind;
+
+    fn base() -> BenchmarkConfig {
+        BenchmarkConfig::random_read_write()
+            .with_disk_path(std::env::temp_dir())
+            .with_io_engine(IoEngineKind::ThreadPool)
+    }
+
+    #[test]
+    fn test_expand_cartesian_product() {
+        let sweep = SweepConfig::default()
+            .with_block_sizes(vec![4096, 65536])
+            .with_thread_counts(vec![1, 2, 4]);
+        assert_eq!(
+            sweep.parameters(),
+            vec![SweepParameter::BlockSize, SweepParameter::Threads]
+        );
+        assert_eq!(sweep.point_count(), 6);
+
+        let configs = sweep.expand(&base());
+        let points: Vec<(u64, usize)> = configs
+            .iter()
+            .map(|c| (c.block_size, c.thread_count))
+            .collect();
+        assert_eq!(
+            points,
+            vec![
+                (4096, 1),
+                (4096, 2),
+                (4096, 4),
+                (65536, 1),
+                (65536, 2),
+                (65536, 4)
+            ]
+        );
+        // Parameters that are not swept keep the base value
+        assert!(configs.iter().all(|c| c.queue_depth == base().queue_depth));
+        assert!(sweep.validate(&base()).is_ok());
+    }
+
+    #[test]
+    fn test_sweep_validation() {
+        assert!(SweepConfig::default().validate(&base()).is_err());
+
+        let duplicate = SweepConfig::default().with_thread_counts(vec![1, 2, 1]);
+        assert!(duplicate.validate(&base()).is_err());
+
+        // Every expanded configuration has to be valid
+        let odd_block = SweepConfig::default().with_block_sizes(vec![4096, 5000]);
+        assert!(odd_block.validate(&base()).is_err());
+
+        let too_many = SweepConfig::default()
+            .with_block_sizes((9..=16).map(|shift| 1 << shift).collect())
+            .with_thread_counts((1..=8).collect())
+            .with_queue_depths((1..=8).collect());
+        assert!(too_many.point_count() > MAX_SWEEP_POINTS);
+        assert!(too_many.validate(&base()).is_err());
+    }
+
+    #[test]
+    fn test_queue_depth_sweep_needs_queueing_engine() {
+        let sweep = SweepConfig::default().with_queue_depths(vec![1, 8, 32]);
+        assert!(sweep.validate(&base()).is_ok());
+        assert_eq!(sweep.expand(&base())[2].queue_depth, Some(32));
+
+        let sync = base().with_io_engine(IoEngineKind::Sync);
+        assert!(sweep.validate(&sync).is_err());
+        let sequential = BenchmarkConfig::sequential_write()
+            .with_disk_path(std::env::temp_dir())
+            .with_io_engine(IoEngineKind::ThreadPool);
+        assert!(sweep.validate(&sequential).is_err());
+    }
+
+    #[test]
+    fn test_point_description() {
+        let point = SweepPoint {
+            block_size: 4096,
+            thread_count: 8,
+            queue_depth: None,
+        };
+        assert_eq!(
+            point.description(&SweepParameter::ALL),
+            "Block size 4.0 KiB, Threads 8"
+        );
+        assert_eq!(point.description(&[SweepParameter::Threads]), "Threads 8");
+    }
+}
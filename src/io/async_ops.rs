@@ -4,8 +4,9 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::task;
 
-use super::buffer::{BufferPool, PooledBuffer};
+use super::buffer::{AlignedBuffer, BufferPool, PooledBuffer};
 use super::disk::{DiskIO, DirectFile, TempFile};
+use super::storage::SystemRoots;
 
 /// Async wrapper for disk I/O operations
 pub struct AsyncDiskIO {
@@ -29,9 +30,13 @@ impl AsyncDiskIO {
         data: Vec<u8>,
     ) -> io::Result<(Box<dyn DirectFile>, usize, Duration)> {
         let start = Instant::now();
+        let alignment = self.buffer_pool.alignment();
         
         let result = task::spawn_blocking(move || {
-            let bytes_written = file.write_direct(&data)?;
+            // Stage through an aligned buffer so files opened for direct I/O accept it
+            let mut buffer = AlignedBuffer::new(data.len(), alignment);
+            buffer.copy_from_slice(&data);
+            let bytes_written = file.write_direct(&buffer)?;
             file.sync_all()?;
             Ok((file, bytes_written))
         }).await;
@@ -41,7 +46,7 @@ impl AsyncDiskIO {
         match result {
             Ok(Ok((file, bytes_written))) => Ok((file, bytes_written, elapsed)),
             Ok(Err(e)) => Err(e),
-            Err(e) => Err(io::Error::new(io::ErrorKind::Other, e)),
+            Err(e) => Err(io::Error::other(e)),
         }
     }
     
@@ -52,12 +57,12 @@ impl AsyncDiskIO {
         buffer_size: usize,
     ) -> io::Result<(Box<dyn DirectFile>, Vec<u8>, Duration)> {
         let start = Instant::now();
+        let alignment = self.buffer_pool.alignment();
         
         let result = task::spawn_blocking(move || {
-            let mut buffer = vec![0u8; buffer_size];
+            let mut buffer = AlignedBuffer::new(buffer_size, alignment);
             let bytes_read = file.read_direct(&mut buffer)?;
-            buffer.truncate(bytes_read);
-            Ok((file, buffer))
+            Ok((file, buffer[..bytes_read].to_vec()))
         }).await;
         
         let elapsed = start.elapsed();
@@ -65,7 +70,7 @@ impl AsyncDiskIO {
         match result {
             Ok(Ok((file, buffer))) => Ok((file, buffer, elapsed)),
             Ok(Err(e)) => Err(e),
-            Err(e) => Err(io::Error::new(io::ErrorKind::Other, e)),
+            Err(e) => Err(io::Error::other(e)),
         }
     }
     
@@ -81,7 +86,7 @@ impl AsyncDiskIO {
         task::spawn_blocking(move || {
             inner.create_temp_file(&target_dir, size_hint)
         }).await
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
+        .map_err(io::Error::other)?
     }
     
     /// Get optimal block size for path asynchronously
@@ -92,13 +97,13 @@ impl AsyncDiskIO {
         task::spawn_blocking(move || {
             inner.get_optimal_block_size(&path)
         }).await
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
+        .map_err(io::Error::other)?
     }
     
     /// Get a pooled buffer for I/O operations
     pub async fn get_pooled_buffer(&self) -> io::Result<PooledBuffer> {
         PooledBuffer::new(self.buffer_pool.clone()).await
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+            .map_err(io::Error::other)
     }
     
     /// Get the buffer pool for advanced usage
@@ -174,7 +179,9 @@ impl StorageType {
         }
     }
     
-    /// Get optimal queue depth for storage type
+    /// Get a guess at a good queue depth for storage type
+    ///
+    /// Only used until a saturation run has measured the knee for the path.
     pub fn optimal_queue_depth(&self) -> usize {
         match self {
             StorageType::HDD => 1,      // HDDs work best with sequential access
@@ -185,18 +192,16 @@ impl StorageType {
     }
 }
 
-/// Detect storage type based on path (simplified heuristic)
-pub async fn detect_storage_type(_path: &Path) -> io::Result<StorageType> {
-    // This is a simplified implementation
-    // In a real implementation, you would query system APIs to determine
-    // the actual storage device type
-    
+/// Detect storage type of the block device behind a path
+///
+/// Paths not backed by a block device, such as on tmpfs, report `Unknown`.
+pub async fn detect_storage_type(path: &Path) -> io::Result<StorageType> {
+    let path = path.to_path_buf();
     task::spawn_blocking(move || {
-        // For now, return a reasonable default
-        // TODO: Implement actual storage detection using platform APIs
-        Ok(StorageType::SSD)
+        let device = SystemRoots::default().device_for_path(&path)?;
+        Ok(device.map_or(StorageType::Unknown, |device| device.storage_type()))
     }).await
-    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
+    .map_err(io::Error::other)?
 }
 
 #[cfg(test)]
@@ -1,17 +1,86 @@
 use std::sync::{Arc, Mutex};
 use std::collections::VecDeque;
+use std::alloc::{self, Layout};
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
 use crate::{DIOrbError, Result};
 
+/// Smallest alignment handed out, matching the largest common logical block size
+pub const MIN_ALIGNMENT: usize = 4096;
+
+/// Zero-initialised heap buffer whose start address is aligned for direct I/O
+///
+/// O_DIRECT (and FILE_FLAG_NO_BUFFERING on Windows) rejects buffers that are
+/// not aligned to the device's logical block size, which a plain `Vec<u8>`
+/// does not guarantee.
+pub struct AlignedBuffer {
+    ptr: NonNull<u8>,
+    len: usize,
+    layout: Layout,
+}
+
+// SAFETY: the buffer exclusively owns its allocation, like a `Vec<u8>`
+unsafe impl Send for AlignedBuffer {}
+unsafe impl Sync for AlignedBuffer {}
+
+impl AlignedBuffer {
+    /// Allocate `len` zeroed bytes aligned to `alignment`, rounded up to a
+    /// power of two of at least `MIN_ALIGNMENT`
+    pub fn new(len: usize, alignment: usize) -> Self {
+        let alignment = alignment.max(MIN_ALIGNMENT).next_power_of_two();
+        let layout = Layout::from_size_align(len.max(1), alignment)
+            .expect("buffer size overflows when aligned");
+        // SAFETY: the layout has a non-zero size
+        let ptr = unsafe { alloc::alloc_zeroed(layout) };
+        let ptr = NonNull::new(ptr).unwrap_or_else(|| alloc::handle_alloc_error(layout));
+        Self { ptr, len, layout }
+    }
+
+    /// Alignment of the start address in bytes
+    pub fn alignment(&self) -> usize {
+        self.layout.align()
+    }
+}
+
+impl Deref for AlignedBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: `ptr` is valid for `len` initialised bytes for the buffer's lifetime
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl DerefMut for AlignedBuffer {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        // SAFETY: as for `deref`, and `&mut self` guarantees exclusive access
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl Drop for AlignedBuffer {
+    fn drop(&mut self) {
+        // SAFETY: allocated in `new` with this exact layout
+        unsafe { alloc::dealloc(self.ptr.as_ptr(), self.layout) }
+    }
+}
+
 /// Buffer pool for reusing allocated buffers to reduce memory allocation overhead
 pub struct BufferPool {
-    buffers: Arc<Mutex<VecDeque<Vec<u8>>>>,
+    buffers: Arc<Mutex<VecDeque<AlignedBuffer>>>,
     buffer_size: usize,
     max_buffers: usize,
+    alignment: usize,
 }
 
 impl BufferPool {
     /// Create a new buffer pool with specified buffer size and maximum count
     pub fn new(buffer_size: usize, max_buffers: usize) -> Result<Self> {
+        Self::with_alignment(buffer_size, max_buffers, MIN_ALIGNMENT)
+    }
+    
+    /// Create a buffer pool whose buffers are aligned to the target's block size
+    pub fn with_alignment(buffer_size: usize, max_buffers: usize, alignment: usize) -> Result<Self> {
         if buffer_size == 0 {
             return Err(DIOrbError::ConfigError("Buffer size must be greater than 0".to_string()));
         }
@@ -23,26 +92,25 @@ impl BufferPool {
             buffers: Arc::new(Mutex::new(VecDeque::new())),
             buffer_size,
             max_buffers,
+            alignment: alignment.max(MIN_ALIGNMENT).next_power_of_two(),
         })
     }
     
     /// Get a buffer from the pool, creating a new one if none available
-    pub async fn get_buffer(&self) -> Result<Vec<u8>> {
+    pub async fn get_buffer(&self) -> Result<AlignedBuffer> {
         // For now, this is synchronous but we keep the async signature for future improvements
         let mut buffers = self.buffers.lock()
             .map_err(|_| DIOrbError::BenchmarkError("Buffer pool lock poisoned".to_string()))?;
         
-        let buffer = buffers.pop_front().unwrap_or_else(|| {
-            let mut buffer = Vec::with_capacity(self.buffer_size);
-            buffer.resize(self.buffer_size, 0);
-            buffer
-        });
+        let buffer = buffers
+            .pop_front()
+            .unwrap_or_else(|| AlignedBuffer::new(self.buffer_size, self.alignment));
         
         Ok(buffer)
     }
     
     /// Return a buffer to the pool for reuse
-    pub fn return_buffer(&self, mut buffer: Vec<u8>) -> Result<()> {
+    pub fn return_buffer(&self, mut buffer: AlignedBuffer) -> Result<()> {
         if buffer.len() == self.buffer_size {
             let mut buffers = self.buffers.lock()
                 .map_err(|_| DIOrbError::BenchmarkError("Buffer pool lock poisoned".to_string()))?;
@@ -61,6 +129,11 @@ impl BufferPool {
         self.buffer_size
     }
     
+    /// Get the alignment of handed-out buffers
+    pub fn alignment(&self) -> usize {
+        self.alignment
+    }
+    
     /// Get current number of pooled buffers
     pub fn pool_size(&self) -> Result<usize> {
         let buffers = self.buffers.lock()
@@ -75,13 +148,14 @@ impl Clone for BufferPool {
             buffers: Arc::clone(&self.buffers),
             buffer_size: self.buffer_size,
             max_buffers: self.max_buffers,
+            alignment: self.alignment,
         }
     }
 }
 
 /// RAII wrapper for buffer pool management
 pub struct PooledBuffer {
-    buffer: Option<Vec<u8>>,
+    buffer: Option<AlignedBuffer>,
     pool: BufferPool,
 }
 
@@ -94,16 +168,6 @@ impl PooledBuffer {
         })
     }
     
-    /// Get mutable access to the buffer
-    pub fn as_mut(&mut self) -> &mut [u8] {
-        self.buffer.as_mut().unwrap()
-    }
-    
-    /// Get immutable access to the buffer
-    pub fn as_ref(&self) -> &[u8] {
-        self.buffer.as_ref().unwrap()
-    }
-    
     /// Get the buffer size
     pub fn len(&self) -> usize {
         self.buffer.as_ref().unwrap().len()
@@ -115,6 +179,20 @@ impl PooledBuffer {
     }
 }
 
+impl AsMut<[u8]> for PooledBuffer {
+    /// Get mutable access to the buffer
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.buffer.as_mut().unwrap()
+    }
+}
+
+impl AsRef<[u8]> for PooledBuffer {
+    /// Get immutable access to the buffer
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_ref().unwrap()
+    }
+}
+
 impl Drop for PooledBuffer {
     fn drop(&mut self) {
         if let Some(buffer) = self.buffer.take() {
@@ -177,4 +255,21 @@ mod tests {
         let pooled2 = PooledBuffer::new(pool.clone()).await.unwrap();
         assert_eq!(pooled2.as_ref()[0], 0);
     }
+    
+    #[tokio::test]
+    async fn test_buffers_are_aligned() {
+        let pool = BufferPool::with_alignment(512, 2, 8192).unwrap();
+        assert_eq!(pool.alignment(), 8192);
+        
+        let buffer = pool.get_buffer().await.unwrap();
+        assert_eq!(buffer.len(), 512);
+        assert_eq!(buffer.as_ptr() as usize % 8192, 0);
+        assert!(buffer.iter().all(|&b| b == 0));
+        
+        // Requests below the minimum are raised to it
+        let small = BufferPool::with_alignment(4096, 1, 512).unwrap();
+        let buffer = small.get_buffer().await.unwrap();
+        assert_eq!(buffer.alignment(), MIN_ALIGNMENT);
+        assert_eq!(buffer.as_ptr() as usize % MIN_ALIGNMENT, 0);
+    }
 }
\ No newline at end of file
@@ -1,6 +1,34 @@
 use std::fs::{File, OpenOptions};
 use std::io::{self, SeekFrom};
 use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use super::buffer::MIN_ALIGNMENT;
+
+/// How a file's I/O actually reaches the device
+///
+/// Opening with O_DIRECT (or FILE_FLAG_NO_BUFFERING) can fail on filesystems
+/// such as tmpfs, in which case files fall back to the page cache.
+/// Variants are ordered from most to least faithful to the device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum DirectIoMode {
+    /// The page cache is bypassed
+    Direct,
+    /// Writes go through the page cache and are flushed with fsync after each call
+    BufferedFsync,
+    /// Reads and writes are served by the page cache
+    Buffered,
+}
+
+impl DirectIoMode {
+    /// Get human-readable description
+    pub fn description(&self) -> &'static str {
+        match self {
+            DirectIoMode::Direct => "Direct",
+            DirectIoMode::BufferedFsync => "Buffered + fsync",
+            DirectIoMode::Buffered => "Buffered (page cache)",
+        }
+    }
+}
 
 /// Cross-platform disk I/O operations trait
 pub trait DiskIO {
@@ -10,11 +38,17 @@ pub trait DiskIO {
     /// Open a file for direct read operations (bypassing OS cache)
     fn open_direct_read(&self, path: &Path) -> io::Result<Box<dyn DirectFile>>;
     
+    /// Open an existing file for direct reads and writes without truncating it
+    fn open_direct_read_write(&self, path: &Path) -> io::Result<Box<dyn DirectFile>>;
+    
     /// Create a temporary file for benchmarking
     fn create_temp_file(&self, target_dir: &Path, size_hint: u64) -> io::Result<TempFile>;
     
     /// Get optimal block size for the given path
     fn get_optimal_block_size(&self, path: &Path) -> io::Result<u64>;
+    
+    /// Get the buffer alignment direct I/O needs for files under the given path
+    fn get_alignment(&self, path: &Path) -> io::Result<usize>;
 }
 
 /// Direct file operations trait for unbuffered I/O
@@ -28,11 +62,24 @@ pub trait DirectFile: Send + Sync {
     /// Seek to position
     fn seek_direct(&mut self, pos: SeekFrom) -> io::Result<u64>;
     
+    /// Read data at an absolute offset without using the file cursor
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize>;
+    
+    /// Write data at an absolute offset without using the file cursor
+    fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize>;
+    
     /// Force synchronization to disk
-    fn sync_all(&mut self) -> io::Result<()>;
+    fn sync_all(&self) -> io::Result<()>;
     
     /// Get file size
     fn file_size(&self) -> io::Result<u64>;
+    
+    /// How I/O on this file reaches the device
+    fn direct_io_mode(&self) -> DirectIoMode;
+    
+    /// Get the underlying file descriptor
+    #[cfg(unix)]
+    fn raw_fd(&self) -> std::os::unix::io::RawFd;
 }
 
 /// Temporary file wrapper with automatic cleanup
@@ -96,11 +143,12 @@ mod windows_impl {
     
     pub struct WindowsDirectFile {
         file: File,
+        mode: DirectIoMode,
     }
     
     impl WindowsDirectFile {
-        pub fn new(file: File) -> Self {
-            Self { file }
+        pub fn new(file: File, mode: DirectIoMode) -> Self {
+            Self { file, mode }
         }
     }
     
@@ -122,13 +170,27 @@ mod windows_impl {
             self.file.seek(pos)
         }
         
-        fn sync_all(&mut self) -> io::Result<()> {
+        fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+            use std::os::windows::fs::FileExt;
+            self.file.seek_read(buf, offset)
+        }
+        
+        fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
+            use std::os::windows::fs::FileExt;
+            self.file.seek_write(buf, offset)
+        }
+        
+        fn sync_all(&self) -> io::Result<()> {
             self.file.sync_all()
         }
         
         fn file_size(&self) -> io::Result<u64> {
             Ok(self.file.metadata()?.len())
         }
+        
+        fn direct_io_mode(&self) -> DirectIoMode {
+            self.mode
+        }
     }
     
     impl DiskIO for PlatformDiskIO {
@@ -141,7 +203,7 @@ mod windows_impl {
                 .custom_flags(FILE_FLAG_WRITE_THROUGH | FILE_FLAG_NO_BUFFERING)
                 .open(path)
             {
-                Ok(file) => Ok(Box::new(WindowsDirectFile::new(file))),
+                Ok(file) => Ok(Box::new(WindowsDirectFile::new(file, DirectIoMode::Direct))),
                 Err(_) => {
                     // Fallback to regular file operations
                     let file = OpenOptions::new()
@@ -149,7 +211,7 @@ mod windows_impl {
                         .create(true)
                         .truncate(true)
                         .open(path)?;
-                    Ok(Box::new(WindowsDirectFile::new(file)))
+                    Ok(Box::new(WindowsDirectFile::new(file, DirectIoMode::Buffered)))
                 }
             }
         }
@@ -161,13 +223,33 @@ mod windows_impl {
                 .custom_flags(FILE_FLAG_NO_BUFFERING)
                 .open(path)
             {
-                Ok(file) => Ok(Box::new(WindowsDirectFile::new(file))),
+                Ok(file) => Ok(Box::new(WindowsDirectFile::new(file, DirectIoMode::Direct))),
                 Err(_) => {
                     // Fallback to regular file operations
                     let file = OpenOptions::new()
                         .read(true)
                         .open(path)?;
-                    Ok(Box::new(WindowsDirectFile::new(file)))
+                    Ok(Box::new(WindowsDirectFile::new(file, DirectIoMode::Buffered)))
+                }
+            }
+        }
+        
+        fn open_direct_read_write(&self, path: &Path) -> io::Result<Box<dyn DirectFile>> {
+            // Try direct I/O first, fall back to regular file operations
+            match OpenOptions::new()
+                .read(true)
+                .write(true)
+                .custom_flags(FILE_FLAG_WRITE_THROUGH | FILE_FLAG_NO_BUFFERING)
+                .open(path)
+            {
+                Ok(file) => Ok(Box::new(WindowsDirectFile::new(file, DirectIoMode::Direct))),
+                Err(_) => {
+                    // Fallback to regular file operations
+                    let file = OpenOptions::new()
+                        .read(true)
+                        .write(true)
+                        .open(path)?;
+                    Ok(Box::new(WindowsDirectFile::new(file, DirectIoMode::Buffered)))
                 }
             }
         }
@@ -186,6 +268,11 @@ mod windows_impl {
             // Windows typically works well with 64KB blocks for sequential I/O
             Ok(65536)
         }
+        
+        fn get_alignment(&self, _path: &Path) -> io::Result<usize> {
+            // Sector sizes above 4KiB are not used in practice
+            Ok(MIN_ALIGNMENT)
+        }
     }
 }
 
@@ -196,12 +283,18 @@ mod unix_impl {
     
     pub struct UnixDirectFile {
         file: File,
+        mode: DirectIoMode,
         use_fsync: bool,
     }
     
     impl UnixDirectFile {
-        pub fn new(file: File, use_fsync: bool) -> Self {
-            Self { file, use_fsync }
+        /// Wrap an open file; `BufferedFsync` makes every write call fsync
+        pub fn new(file: File, mode: DirectIoMode) -> Self {
+            Self {
+                file,
+                mode,
+                use_fsync: mode == DirectIoMode::BufferedFsync,
+            }
         }
     }
     
@@ -226,13 +319,36 @@ mod unix_impl {
             self.file.seek(pos)
         }
         
-        fn sync_all(&mut self) -> io::Result<()> {
+        fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+            use std::os::unix::fs::FileExt;
+            self.file.read_at(buf, offset)
+        }
+        
+        fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
+            use std::os::unix::fs::FileExt;
+            let result = self.file.write_at(buf, offset)?;
+            if self.use_fsync {
+                self.file.sync_all()?;
+            }
+            Ok(result)
+        }
+        
+        fn sync_all(&self) -> io::Result<()> {
             self.file.sync_all()
         }
         
         fn file_size(&self) -> io::Result<u64> {
             Ok(self.file.metadata()?.len())
         }
+        
+        fn direct_io_mode(&self) -> DirectIoMode {
+            self.mode
+        }
+        
+        fn raw_fd(&self) -> std::os::unix::io::RawFd {
+            use std::os::unix::io::AsRawFd;
+            self.file.as_raw_fd()
+        }
     }
     
     impl DiskIO for PlatformDiskIO {
@@ -245,7 +361,7 @@ mod unix_impl {
                 .custom_flags(libc::O_DIRECT)
                 .open(path)
             {
-                Ok(file) => Ok(Box::new(UnixDirectFile::new(file, false))),
+                Ok(file) => Ok(Box::new(UnixDirectFile::new(file, DirectIoMode::Direct))),
                 Err(_) => {
                     // Fallback to regular file with fsync
                     let file = OpenOptions::new()
@@ -253,7 +369,7 @@ mod unix_impl {
                         .create(true)
                         .truncate(true)
                         .open(path)?;
-                    Ok(Box::new(UnixDirectFile::new(file, true)))
+                    Ok(Box::new(UnixDirectFile::new(file, DirectIoMode::BufferedFsync)))
                 }
             }
         }
@@ -265,13 +381,33 @@ mod unix_impl {
                 .custom_flags(libc::O_DIRECT)
                 .open(path)
             {
-                Ok(file) => Ok(Box::new(UnixDirectFile::new(file, false))),
+                Ok(file) => Ok(Box::new(UnixDirectFile::new(file, DirectIoMode::Direct))),
                 Err(_) => {
                     // Fallback to regular file
                     let file = OpenOptions::new()
                         .read(true)
                         .open(path)?;
-                    Ok(Box::new(UnixDirectFile::new(file, false)))
+                    Ok(Box::new(UnixDirectFile::new(file, DirectIoMode::Buffered)))
+                }
+            }
+        }
+        
+        fn open_direct_read_write(&self, path: &Path) -> io::Result<Box<dyn DirectFile>> {
+            // Try O_DIRECT first, fall back to regular file with fsync
+            match OpenOptions::new()
+                .read(true)
+                .write(true)
+                .custom_flags(libc::O_DIRECT)
+                .open(path)
+            {
+                Ok(file) => Ok(Box::new(UnixDirectFile::new(file, DirectIoMode::Direct))),
+                Err(_) => {
+                    // Fallback to regular file with fsync
+                    let file = OpenOptions::new()
+                        .read(true)
+                        .write(true)
+                        .open(path)?;
+                    Ok(Box::new(UnixDirectFile::new(file, DirectIoMode::BufferedFsync)))
                 }
             }
         }
@@ -290,6 +426,13 @@ mod unix_impl {
             // Unix systems typically work well with 64KB blocks for sequential I/O
             Ok(65536)
         }
+        
+        fn get_alignment(&self, path: &Path) -> io::Result<usize> {
+            use std::os::unix::fs::MetadataExt;
+            // The filesystem block size is a multiple of the device's logical block size
+            let block_size = std::fs::metadata(path)?.blksize() as usize;
+            Ok(block_size.max(MIN_ALIGNMENT).next_power_of_two())
+        }
     }
 }
 
@@ -340,6 +483,28 @@ mod tests {
         std::fs::remove_file(path).unwrap();
     }
     
+    #[cfg(unix)]
+    #[test]
+    fn test_positional_io_leaves_cursor_alone() {
+        let temp_dir = tempdir().unwrap();
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(temp_dir.path().join("positional.dat"))
+            .unwrap();
+        let mut file = UnixDirectFile::new(file, DirectIoMode::Buffered);
+        
+        assert_eq!(file.write_at(b"world", 6).unwrap(), 5);
+        assert_eq!(file.write_direct(b"hello ").unwrap(), 6);
+        
+        let mut buf = [0u8; 5];
+        assert_eq!(file.read_at(&mut buf, 6).unwrap(), 5);
+        assert_eq!(&buf, b"world");
+        assert_eq!(file.seek_direct(SeekFrom::Current(0)).unwrap(), 6);
+    }
+    
     #[test]
     fn test_optimal_block_size() {
         let temp_dir = tempdir().unwrap();
@@ -348,4 +513,14 @@ mod tests {
         let block_size = disk_io.get_optimal_block_size(temp_dir.path()).unwrap();
         assert_eq!(block_size, 65536); // 64KB
     }
+    
+    #[test]
+    fn test_alignment() {
+        let temp_dir = tempdir().unwrap();
+        let disk_io = PlatformDiskIO::new();
+        
+        let alignment = disk_io.get_alignment(temp_dir.path()).unwrap();
+        assert!(alignment >= MIN_ALIGNMENT);
+        assert!(alignment.is_power_of_two());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,379 @@
+//! I/O engines
+//!
+//! An engine issues block-sized reads and writes at explicit offsets against
+//! one open file and may keep several requests in flight at once, so that
+//! devices with deep hardware queues are measured under realistic load.
+
+use std::io;
+use std::time::{Duration, Instant};
+
+use super::buffer::AlignedBuffer;
+use super::disk::DirectFile;
+use super::threaded::ThreadPoolEngine;
+use crate::config::IoEngineKind;
+
+/// Kind of I/O operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IoOp {
+    Read,
+    Write,
+}
+
+/// A single block-sized request at an absolute file offset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IoRequest {
+    pub op: IoOp,
+    /// Must be a multiple of the engine's block size
+    pub offset: u64,
+}
+
+impl IoRequest {
+    /// Reject offsets that direct I/O would fail on
+    pub(crate) fn check_aligned(&self, block_size: usize) -> io::Result<()> {
+        if !self.offset.is_multiple_of(block_size as u64) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "offset {} is not aligned to the {} byte block size",
+                    self.offset, block_size
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Result of a completed request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IoCompletion {
+    pub op: IoOp,
+    pub offset: u64,
+    /// Bytes transferred
+    pub bytes: usize,
+    /// Time from submission to completion
+    pub latency: Duration,
+}
+
+/// Engine that keeps up to `queue_depth` requests in flight
+pub trait IoEngine: Send {
+    /// The engine actually in use (may differ from the requested one after a fallback)
+    fn kind(&self) -> IoEngineKind;
+
+    /// Maximum number of requests in flight
+    fn queue_depth(&self) -> usize;
+
+    /// Number of submitted requests that have not been reaped yet
+    fn in_flight(&self) -> usize;
+
+    /// Queue a request; callers must keep `in_flight() < queue_depth()`
+    fn submit(&mut self, request: IoRequest) -> io::Result<()>;
+
+    /// Wait until at least `min_complete` requests finish and append their completions
+    fn reap(&mut self, min_complete: usize, completions: &mut Vec<IoCompletion>) -> io::Result<()>;
+
+    /// Force written data to stable storage
+    fn sync(&mut self) -> io::Result<()>;
+
+    /// Time spent in flight, summed over every completed request
+    fn busy_time(&self) -> Duration;
+
+    /// Number of requests completed so far
+    fn completed_ops(&self) -> u64;
+
+    /// Why the requested engine could not be used, when this one stands in for it
+    fn fallback_reason(&self) -> Option<&str> {
+        None
+    }
+
+    /// Average number of requests actually in flight over `elapsed`
+    ///
+    /// By Little's law this is the summed request latency divided by the
+    /// wall-clock time, so it shows whether the device really saw the
+    /// configured queue depth.
+    fn achieved_queue_depth(&self, elapsed: Duration) -> f64 {
+        if elapsed.is_zero() {
+            0.0
+        } else {
+            self.busy_time().as_secs_f64() / elapsed.as_secs_f64()
+        }
+    }
+}
+
+/// Create an engine of the requested kind over an open file
+///
+/// Engine buffers are aligned to `alignment` (see `DiskIO::get_alignment`)
+/// so they can be used with files opened for direct I/O.
+///
+/// Engines that cannot be set up on this system (io_uring on non-Linux
+/// platforms or inside a seccomp sandbox, for example) fall back to the
+/// sync engine; check `kind()` on the returned engine for what is in use.
+pub fn create_engine(
+    kind: IoEngineKind,
+    file: Box<dyn DirectFile>,
+    block_size: usize,
+    queue_depth: usize,
+    alignment: usize,
+) -> io::Result<Box<dyn IoEngine>> {
+    match kind {
+        IoEngineKind::Sync => Ok(Box::new(SyncEngine::new(file, block_size, alignment))),
+        IoEngineKind::ThreadPool => Ok(Box::new(ThreadPoolEngine::new(
+            file,
+            block_size,
+            queue_depth,
+            alignment,
+        )?)),
+        IoEngineKind::IoUring => {
+            #[cfg(target_os = "linux")]
+            {
+                match super::uring::IoUringEngine::new(file, block_size, queue_depth, alignment) {
+                    Ok(engine) => Ok(Box::new(engine)),
+                    Err((file, e)) => Ok(Box::new(
+                        SyncEngine::new(file, block_size, alignment)
+                            .with_fallback_reason(format!("io_uring unavailable: {}", e)),
+                    )),
+                }
+            }
+            #[cfg(not(target_os = "linux"))]
+            {
+                let _ = queue_depth;
+                Ok(Box::new(
+                    SyncEngine::new(file, block_size, alignment)
+                        .with_fallback_reason("io_uring is only available on Linux".to_string()),
+                ))
+            }
+        }
+    }
+}
+
+/// Fill a buffer with the repeating byte pattern used for writes
+pub fn fill_test_pattern(buf: &mut [u8]) {
+    for (i, byte) in buf.iter_mut().enumerate() {
+        *byte = (i % 256) as u8;
+    }
+}
+
+/// Engine performing one blocking positional request at a time
+pub struct SyncEngine {
+    file: Box<dyn DirectFile>,
+    buffer: AlignedBuffer,
+    completed: Vec<IoCompletion>,
+    busy: Duration,
+    completed_ops: u64,
+    fallback_reason: Option<String>,
+}
+
+impl SyncEngine {
+    /// Create a sync engine over an open file
+    pub fn new(file: Box<dyn DirectFile>, block_size: usize, alignment: usize) -> Self {
+        let mut buffer = AlignedBuffer::new(block_size, alignment);
+        fill_test_pattern(&mut buffer);
+        Self {
+            file,
+            buffer,
+            completed: Vec::with_capacity(1),
+            busy: Duration::ZERO,
+            completed_ops: 0,
+            fallback_reason: None,
+        }
+    }
+
+    /// Note that this engine stands in for one that could not be used
+    pub fn with_fallback_reason(mut self, reason: String) -> Self {
+        self.fallback_reason = Some(reason);
+        self
+    }
+}
+
+impl IoEngine for SyncEngine {
+    fn kind(&self) -> IoEngineKind {
+        IoEngineKind::Sync
+    }
+
+    fn queue_depth(&self) -> usize {
+        1
+    }
+
+    fn in_flight(&self) -> usize {
+        self.completed.len()
+    }
+
+    fn submit(&mut self, request: IoRequest) -> io::Result<()> {
+        request.check_aligned(self.buffer.len())?;
+        let start = Instant::now();
+        let bytes = match request.op {
+            IoOp::Read => self.file.read_at(&mut self.buffer, request.offset)?,
+            IoOp::Write => self.file.write_at(&self.buffer, request.offset)?,
+        };
+        let latency = start.elapsed();
+        self.busy += latency;
+        self.completed_ops += 1;
+        self.completed.push(IoCompletion {
+            op: request.op,
+            offset: request.offset,
+            bytes,
+            latency,
+        });
+        Ok(())
+    }
+
+    fn reap(
+        &mut self,
+        _min_complete: usize,
+        completions: &mut Vec<IoCompletion>,
+    ) -> io::Result<()> {
+        completions.append(&mut self.completed);
+        Ok(())
+    }
+
+    fn sync(&mut self) -> io::Result<()> {
+        self.file.sync_all()
+    }
+
+    fn busy_time(&self) -> Duration {
+        self.busy
+    }
+
+    fn completed_ops(&self) -> u64 {
+        self.completed_ops
+    }
+
+    fn fallback_reason(&self) -> Option<&str> {
+        self.fallback_reason.as_deref()
+    }
+}
+
+#[cfg(all(test, unix))]
+pub(crate) mod test_support {
+    use super::*;
+    use crate::io::disk::{DirectIoMode, UnixDirectFile};
+    use std::fs::OpenOptions;
+    use std::path::Path;
+
+    /// Open a buffered read/write file, avoiding O_DIRECT alignment rules
+    pub(crate) fn open_buffered(path: &Path) -> Box<dyn DirectFile> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)
+            .unwrap();
+        Box::new(UnixDirectFile::new(file, DirectIoMode::Buffered))
+    }
+
+    /// Open a read/write file with O_DIRECT, or `None` where the filesystem
+    /// does not support it (tmpfs on older kernels, for example)
+    pub(crate) fn open_direct(path: &Path) -> Option<Box<dyn DirectFile>> {
+        use std::os::unix::fs::OpenOptionsExt;
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .custom_flags(libc::O_DIRECT)
+            .open(path)
+            .ok()?;
+        Some(Box::new(UnixDirectFile::new(file, DirectIoMode::Direct)))
+    }
+
+    /// Write every block through the engine, then read them all back
+    pub(crate) fn exercise_engine(engine: &mut dyn IoEngine, blocks: u64, block_size: usize) {
+        let mut completions = Vec::new();
+        let ops_before = engine.completed_ops();
+        for op in [IoOp::Write, IoOp::Read] {
+            let mut next = 0;
+            while next < blocks || engine.in_flight() > 0 {
+                while next < blocks && engine.in_flight() < engine.queue_depth() {
+                    let offset = next * block_size as u64;
+                    engine.submit(IoRequest { op, offset }).unwrap();
+                    next += 1;
+                }
+                engine.reap(1, &mut completions).unwrap();
+            }
+            if op == IoOp::Write {
+                engine.sync().unwrap();
+            }
+        }
+
+        assert_eq!(completions.len() as u64, blocks * 2);
+        assert_eq!(engine.completed_ops() - ops_before, blocks * 2);
+        assert!(completions.iter().all(|c| c.bytes == block_size));
+        let mut read_offsets: Vec<u64> = completions
+            .iter()
+            .filter(|c| c.op == IoOp::Read)
+            .map(|c| c.offset)
+            .collect();
+        read_offsets.sort();
+        let expected: Vec<u64> = (0..blocks).map(|b| b * block_size as u64).collect();
+        assert_eq!(read_offsets, expected);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::buffer::MIN_ALIGNMENT;
+    use tempfile::tempdir;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sync_engine_round_trip() {
+        let temp_dir = tempdir().unwrap();
+        let file = test_support::open_buffered(&temp_dir.path().join("engine.dat"));
+
+        let mut engine = create_engine(IoEngineKind::Sync, file, 4096, 8, MIN_ALIGNMENT).unwrap();
+        assert_eq!(engine.kind(), IoEngineKind::Sync);
+        assert_eq!(engine.queue_depth(), 1);
+        test_support::exercise_engine(engine.as_mut(), 16, 4096);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_engines_with_o_direct() {
+        let temp_dir = tempdir().unwrap();
+        for kind in [
+            IoEngineKind::Sync,
+            IoEngineKind::ThreadPool,
+            IoEngineKind::IoUring,
+        ] {
+            let path = temp_dir.path().join("direct.dat");
+            let Some(file) = test_support::open_direct(&path) else {
+                eprintln!("O_DIRECT not supported here, skipping");
+                return;
+            };
+            let mut engine = create_engine(kind, file, 4096, 4, MIN_ALIGNMENT).unwrap();
+            test_support::exercise_engine(engine.as_mut(), 32, 4096);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unaligned_offset_rejected() {
+        let temp_dir = tempdir().unwrap();
+        for kind in [
+            IoEngineKind::Sync,
+            IoEngineKind::ThreadPool,
+            IoEngineKind::IoUring,
+        ] {
+            let file = test_support::open_buffered(&temp_dir.path().join("engine.dat"));
+            let mut engine = create_engine(kind, file, 4096, 4, MIN_ALIGNMENT).unwrap();
+            let err = engine
+                .submit(IoRequest {
+                    op: IoOp::Write,
+                    offset: 100,
+                })
+                .unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+            assert_eq!(engine.in_flight(), 0);
+        }
+    }
+
+    #[test]
+    fn test_fill_test_pattern() {
+        let mut buf = vec![0u8; 300];
+        fill_test_pattern(&mut buf);
+        assert_eq!(buf[0], 0);
+        assert_eq!(buf[255], 255);
+        assert_eq!(buf[256], 0);
+    }
+}
@@ -1,242 +1,245 @@
-#[cfg(test)]
-mod integration_tests {
-    use crate::io::disk::{PlatformDiskIO, DiskIO};
-    use crate::io::async_ops::{AsyncDiskIO, IOMetrics, StorageType};
-    use std::io::SeekFrom;
-    use tempfile::tempdir;
-    use tokio::time::{timeout, Duration};
-    
-    /// Test basic file creation and cleanup
-    #[tokio::test]
-    async fn test_cross_platform_file_operations() {
-        let temp_dir = tempdir().unwrap();
-        let disk_io = PlatformDiskIO::new();
-        
-        // Test temp file creation
-        let temp_file = disk_io.create_temp_file(temp_dir.path(), 1024).unwrap();
-        assert!(temp_file.path().exists());
-        
-        // Test file cleanup
-        let path = temp_file.path().to_owned();
-        drop(temp_file);
-        assert!(!path.exists());
+use crate::io::disk::{PlatformDiskIO, DiskIO};
+use crate::io::async_ops::{AsyncDiskIO, IOMetrics, StorageType};
+use std::io::SeekFrom;
+use tempfile::tempdir;
+use tokio::time::{timeout, Duration};
+
+/// Test basic file creation and cleanup
+#[tokio::test]
+async fn test_cross_platform_file_operations() {
+    let temp_dir = tempdir().unwrap();
+    let disk_io = PlatformDiskIO::new();
+    
+    // Test temp file creation
+    let temp_file = disk_io.create_temp_file(temp_dir.path(), 1024).unwrap();
+    assert!(temp_file.path().exists());
+    
+    // Test file cleanup
+    let path = temp_file.path().to_owned();
+    drop(temp_file);
+    assert!(!path.exists());
+}
+
+/// Test direct I/O write and read operations
+#[tokio::test]
+async fn test_direct_io_operations() {
+    let temp_dir = tempdir().unwrap();
+    let disk_io = PlatformDiskIO::new();
+    
+    // Create a temporary file for testing
+    let temp_file_result = disk_io.create_temp_file(temp_dir.path(), 4096);
+    
+    // Direct I/O might fail due to permissions on some systems
+    if temp_file_result.is_err() {
+        println!("Direct I/O not available, skipping test");
+        return;
     }
     
-    /// Test direct I/O write and read operations
-    #[tokio::test]
-    async fn test_direct_io_operations() {
-        let temp_dir = tempdir().unwrap();
-        let disk_io = PlatformDiskIO::new();
-        
-        // Create a temporary file for testing
-        let temp_file_result = disk_io.create_temp_file(temp_dir.path(), 4096);
-        
-        // Direct I/O might fail due to permissions on some systems
-        if temp_file_result.is_err() {
-            println!("Direct I/O not available, skipping test");
-            return;
-        }
-        
-        let mut temp_file = temp_file_result.unwrap();
-        
-        // Test data (must be aligned for direct I/O)
-        let test_data = vec![0x42u8; 4096];
-        
-        // Write data
-        let write_result = temp_file.file.write_direct(&test_data);
-        if write_result.is_err() {
-            println!("Direct I/O write failed, likely due to alignment or permissions");
-            return;
-        }
-        
-        let bytes_written = write_result.unwrap();
-        assert_eq!(bytes_written, 4096);
-        
-        // Sync to ensure data is written
-        if temp_file.file.sync_all().is_err() {
-            println!("Sync failed, likely due to direct I/O limitations");
-            return;
-        }
-        
-        // Seek back to beginning
-        if temp_file.file.seek_direct(SeekFrom::Start(0)).is_err() {
-            println!("Seek failed, likely due to direct I/O limitations");
-            return;
-        }
-        
-        // Read data back
-        let mut read_buffer = vec![0u8; 4096];
-        let read_result = temp_file.file.read_direct(&mut read_buffer);
-        if read_result.is_err() {
-            println!("Read failed, likely due to direct I/O limitations");
-            return;
-        }
-        
-        let bytes_read = read_result.unwrap();
-        assert_eq!(bytes_read, 4096);
-        assert_eq!(read_buffer, test_data);
+    let mut temp_file = temp_file_result.unwrap();
+    
+    // Test data (must be aligned for direct I/O)
+    let test_data = vec![0x42u8; 4096];
+    
+    // Write data
+    let write_result = temp_file.file.write_direct(&test_data);
+    if write_result.is_err() {
+        println!("Direct I/O write failed, likely due to alignment or permissions");
+        return;
     }
     
-    /// Test async I/O operations with timeout
-    #[tokio::test]
-    async fn test_async_io_operations() {
-        let temp_dir = tempdir().unwrap();
-        let disk_io = PlatformDiskIO::new();
-        let async_io = AsyncDiskIO::new(disk_io, 4096).unwrap();
-        
-        // Create temp file asynchronously with timeout
-        let temp_file = timeout(
-            Duration::from_secs(5),
-            async_io.create_temp_file_async(temp_dir.path(), 4096)
-        ).await.unwrap().unwrap();
-        
-        assert!(temp_file.path().exists());
+    let bytes_written = write_result.unwrap();
+    assert_eq!(bytes_written, 4096);
+    
+    // Sync to ensure data is written
+    if temp_file.file.sync_all().is_err() {
+        println!("Sync failed, likely due to direct I/O limitations");
+        return;
     }
     
-    /// Test buffer pool performance under load
-    #[tokio::test]
-    async fn test_buffer_pool_performance() {
-        let disk_io = PlatformDiskIO::new();
-        let async_io = AsyncDiskIO::new(disk_io, 4096).unwrap();
-        
-        // Get multiple buffers
-        let mut buffers = Vec::new();
-        for _ in 0..10 {
-            buffers.push(async_io.get_pooled_buffer().await.unwrap());
-        }
-        
-        // Verify all buffers are correct size
-        for buffer in &buffers {
-            assert_eq!(buffer.len(), 4096);
-        }
-        
-        // Drop buffers and verify pool reuse
-        drop(buffers);
-        
-        // Pool should have some buffers available now
-        let pool_size = async_io.buffer_pool().pool_size().unwrap();
-        assert!(pool_size > 0);
+    // Seek back to beginning
+    if temp_file.file.seek_direct(SeekFrom::Start(0)).is_err() {
+        println!("Seek failed, likely due to direct I/O limitations");
+        return;
     }
     
-    /// Test I/O performance measurement accuracy
-    #[tokio::test]
-    #[ignore]
-    async fn test_performance_measurement() {
-        let temp_dir = tempdir().unwrap();
-        let disk_io = PlatformDiskIO::new();
-        let async_io = AsyncDiskIO::new(disk_io.clone(), 4096).unwrap();
-        
-        // Create a test file
-        let temp_file = async_io.create_temp_file_async(temp_dir.path(), 4096).await.unwrap();
-        let file = disk_io.open_direct_write(temp_file.path()).unwrap();
-        
-        // Test data
-        let test_data = vec![0x55u8; 4096];
-        
-        // Measure write performance
-        let (_, bytes_written, elapsed) = async_io.write_async(file, test_data).await.unwrap();
-        
-        assert_eq!(bytes_written, 4096);
-        assert!(elapsed.as_nanos() > 0);
-        
-        // Create metrics
-        let metrics = IOMetrics::new(bytes_written as u64, elapsed, 1);
-        assert_eq!(metrics.bytes_processed, 4096);
-        assert_eq!(metrics.operations_count, 1);
-        assert!(metrics.throughput_mbps >= 0.0);
+    // Read data back
+    let mut read_buffer = vec![0u8; 4096];
+    let read_result = temp_file.file.read_direct(&mut read_buffer);
+    if read_result.is_err() {
+        println!("Read failed, likely due to direct I/O limitations");
+        return;
     }
     
-    /// Test optimal block size detection
-    #[tokio::test]
-    async fn test_block_size_optimization() {
-        let temp_dir = tempdir().unwrap();
-        let disk_io = PlatformDiskIO::new();
-        let async_io = AsyncDiskIO::new(disk_io, 4096).unwrap();
-        
-        let block_size = async_io.get_optimal_block_size_async(temp_dir.path()).await.unwrap();
-        
-        // Should return a reasonable block size (64KB default)
-        assert_eq!(block_size, 65536);
+    let bytes_read = read_result.unwrap();
+    assert_eq!(bytes_read, 4096);
+    assert_eq!(read_buffer, test_data);
+}
+
+/// Test async I/O operations with timeout
+#[tokio::test]
+async fn test_async_io_operations() {
+    let temp_dir = tempdir().unwrap();
+    let disk_io = PlatformDiskIO::new();
+    let async_io = AsyncDiskIO::new(disk_io, 4096).unwrap();
+    
+    // Create temp file asynchronously with timeout
+    let temp_file = timeout(
+        Duration::from_secs(5),
+        async_io.create_temp_file_async(temp_dir.path(), 4096)
+    ).await.unwrap().unwrap();
+    
+    assert!(temp_file.path().exists());
+}
+
+/// Test buffer pool performance under load
+#[tokio::test]
+async fn test_buffer_pool_performance() {
+    let disk_io = PlatformDiskIO::new();
+    let async_io = AsyncDiskIO::new(disk_io, 4096).unwrap();
+    
+    // Get multiple buffers
+    let mut buffers = Vec::new();
+    for _ in 0..10 {
+        buffers.push(async_io.get_pooled_buffer().await.unwrap());
     }
     
-    /// Test storage type detection and optimization
-    #[tokio::test]
-    async fn test_storage_type_detection() {
-        let temp_dir = tempdir().unwrap();
-        
-        let storage_type = crate::io::async_ops::detect_storage_type(temp_dir.path()).await.unwrap();
-        
-        // Should detect some storage type
+    // Verify all buffers are correct size
+    for buffer in &buffers {
+        assert_eq!(buffer.len(), 4096);
+    }
+    
+    // Drop buffers and verify pool reuse
+    drop(buffers);
+    
+    // Pool should have some buffers available now
+    let pool_size = async_io.buffer_pool().pool_size().unwrap();
+    assert!(pool_size > 0);
+}
+
+/// Test I/O performance measurement accuracy
+#[tokio::test]
+#[ignore]
+async fn test_performance_measurement() {
+    let temp_dir = tempdir().unwrap();
+    let disk_io = PlatformDiskIO::new();
+    let async_io = AsyncDiskIO::new(disk_io.clone(), 4096).unwrap();
+    
+    // Create a test file
+    let temp_file = async_io.create_temp_file_async(temp_dir.path(), 4096).await.unwrap();
+    let file = disk_io.open_direct_write(temp_file.path()).unwrap();
+    
+    // Test data
+    let test_data = vec![0x55u8; 4096];
+    
+    // Measure write performance
+    let (_, bytes_written, elapsed) = async_io.write_async(file, test_data).await.unwrap();
+    
+    assert_eq!(bytes_written, 4096);
+    assert!(elapsed.as_nanos() > 0);
+    
+    // Create metrics
+    let metrics = IOMetrics::new(bytes_written as u64, elapsed, 1);
+    assert_eq!(metrics.bytes_processed, 4096);
+    assert_eq!(metrics.operations_count, 1);
+    assert!(metrics.throughput_mbps >= 0.0);
+}
+
+/// Test optimal block size detection
+#[tokio::test]
+async fn test_block_size_optimization() {
+    let temp_dir = tempdir().unwrap();
+    let disk_io = PlatformDiskIO::new();
+    let async_io = AsyncDiskIO::new(disk_io, 4096).unwrap();
+    
+    let block_size = async_io.get_optimal_block_size_async(temp_dir.path()).await.unwrap();
+    
+    // Should return a reasonable block size (64KB default)
+    assert_eq!(block_size, 65536);
+}
+
+/// Test storage type detection and optimization
+#[tokio::test]
+async fn test_storage_type_detection() {
+    let temp_dir = tempdir().unwrap();
+    
+    let storage_type = crate::io::async_ops::detect_storage_type(temp_dir.path()).await.unwrap();
+    
+    // Only a block device with a known rotational flag has a storage type;
+    // tmpfs, overlayfs or NFS have none
+    let device = crate::io::SystemRoots::default().device_for_path(temp_dir.path()).unwrap();
+    if device.is_some_and(|device| device.rotational.is_some()) {
         assert_ne!(storage_type, StorageType::Unknown);
-        
-        // Test optimal parameters
-        let block_size = storage_type.optimal_block_size();
-        let queue_depth = storage_type.optimal_queue_depth();
-        
-        assert!(block_size >= 4096);
-        assert!(queue_depth >= 1);
+    } else {
+        assert_eq!(storage_type, StorageType::Unknown);
     }
     
-    /// Test error handling and recovery
-    #[tokio::test]
-    async fn test_error_handling() {
-        let disk_io = PlatformDiskIO::new();
-        
-        // Try to create temp file in non-existent directory
-        let result = disk_io.create_temp_file(std::path::Path::new("/nonexistent/path"), 1024);
-        assert!(result.is_err());
-        
-        // Try to get block size for non-existent path
-        let result = disk_io.get_optimal_block_size(std::path::Path::new("/nonexistent/path"));
-        // This might succeed with default value depending on implementation
-        assert!(result.is_ok() || result.is_err());
+    // Test optimal parameters
+    let block_size = storage_type.optimal_block_size();
+    let queue_depth = storage_type.optimal_queue_depth();
+    
+    assert!(block_size >= 4096);
+    assert!(queue_depth >= 1);
+}
+
+/// Test error handling and recovery
+#[tokio::test]
+async fn test_error_handling() {
+    let disk_io = PlatformDiskIO::new();
+    
+    // Try to create temp file in non-existent directory
+    let result = disk_io.create_temp_file(std::path::Path::new("/nonexistent/path"), 1024);
+    assert!(result.is_err());
+    
+    // Try to get block size for non-existent path
+    let result = disk_io.get_optimal_block_size(std::path::Path::new("/nonexistent/path"));
+    // This might succeed with default value depending on implementation
+    assert!(result.is_ok() || result.is_err());
+}
+
+/// Test concurrent I/O operations
+#[tokio::test]
+async fn test_concurrent_operations() {
+    let temp_dir = tempdir().unwrap();
+    let temp_path = temp_dir.path().to_owned();
+    
+    // Test concurrent buffer pool operations instead of file creation
+    // to avoid direct I/O permission issues
+    let mut handles = Vec::new();
+    
+    for _i in 0..5 {
+        let async_io = AsyncDiskIO::new(PlatformDiskIO::new(), 4096).unwrap();
+        let path = temp_path.clone();
+        
+        let handle = tokio::spawn(async move {
+            // Test buffer operations which don't require special permissions
+            let buffer = async_io.get_pooled_buffer().await?;
+            let block_size = async_io.get_optimal_block_size_async(&path).await?;
+            Ok::<_, std::io::Error>(buffer.len() == 4096 && block_size > 0)
+        });
+        
+        handles.push(handle);
     }
     
-    /// Test concurrent I/O operations
-    #[tokio::test]
-    async fn test_concurrent_operations() {
-        let temp_dir = tempdir().unwrap();
-        let temp_path = temp_dir.path().to_owned();
-        
-        // Test concurrent buffer pool operations instead of file creation
-        // to avoid direct I/O permission issues
-        let mut handles = Vec::new();
-        
-        for _i in 0..5 {
-            let async_io = AsyncDiskIO::new(PlatformDiskIO::new(), 4096).unwrap();
-            let path = temp_path.clone();
-            
-            let handle = tokio::spawn(async move {
-                // Test buffer operations which don't require special permissions
-                let buffer = async_io.get_pooled_buffer().await?;
-                let block_size = async_io.get_optimal_block_size_async(&path).await?;
-                Ok::<_, std::io::Error>(buffer.len() == 4096 && block_size > 0)
-            });
-            
-            handles.push(handle);
-        }
-        
-        // Wait for all operations to complete
-        for handle in handles {
-            let result = handle.await.unwrap().unwrap();
-            assert!(result);
-        }
+    // Wait for all operations to complete
+    for handle in handles {
+        let result = handle.await.unwrap().unwrap();
+        assert!(result);
     }
+}
+
+/// Test memory usage and cleanup
+#[tokio::test]
+async fn test_memory_cleanup() {
+    let disk_io = PlatformDiskIO::new();
+    let _async_io = AsyncDiskIO::new(disk_io, 1024 * 1024).unwrap(); // 1MB buffers
     
-    /// Test memory usage and cleanup
-    #[tokio::test]
-    async fn test_memory_cleanup() {
-        let disk_io = PlatformDiskIO::new();
-        let _async_io = AsyncDiskIO::new(disk_io, 1024 * 1024).unwrap(); // 1MB buffers
-        
-        // Create and drop many buffers to test memory management
-        for _ in 0..100 {
-            let _buffer = _async_io.get_pooled_buffer().await.unwrap();
-            // Buffer should be automatically returned to pool on drop
-        }
-        
-        // Pool should have reasonable number of buffers
-        let pool_size = _async_io.buffer_pool().pool_size().unwrap();
-        assert!(pool_size <= 16); // Should not exceed max_buffers
+    // Create and drop many buffers to test memory management
+    for _ in 0..100 {
+        let _buffer = _async_io.get_pooled_buffer().await.unwrap();
+        // Buffer should be automatically returned to pool on drop
     }
-}
\ No newline at end of file
+    
+    // Pool should have reasonable number of buffers
+    let pool_size = _async_io.buffer_pool().pool_size().unwrap();
+    assert!(pool_size <= 16); // Should not exceed max_buffers
+}
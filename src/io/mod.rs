@@ -6,10 +6,19 @@
 pub mod disk;
 pub mod buffer;
 pub mod async_ops;
+pub mod engine;
+pub mod threaded;
+pub mod storage;
+pub mod system;
+#[cfg(target_os = "linux")]
+pub mod uring;
 
 #[cfg(test)]
 mod integration_tests;
 
-pub use disk::{DiskIO, DirectFile, TempFile, create_disk_io};
+pub use disk::{DiskIO, DirectFile, DirectIoMode, TempFile, create_disk_io};
 pub use buffer::{BufferPool, PooledBuffer};
-pub use async_ops::{AsyncDiskIO, IOMetrics, StorageType, detect_storage_type};
\ No newline at end of file
+pub use async_ops::{AsyncDiskIO, IOMetrics, StorageType, detect_storage_type};
+pub use storage::{BlockDevice, MountEntry, SystemRoots};
+pub use system::{CpuInfo, FilesystemSpace, KernelInfo, MemoryInfo};
+pub use engine::{create_engine, IoCompletion, IoEngine, IoOp, IoRequest};
\ No newline at end of file
@@ -0,0 +1,450 @@
+//! Block device detection
+//!
+//! Resolves a path to the block device behind it: the mount holding the
+//! path is found in `/proc/self/mountinfo` by the path's `st_dev`, and the
+//! device's queue settings and model are read from sysfs. The procfs and
+//! sysfs roots are configurable so detection can run against fixtures.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::async_ops::StorageType;
+
+/// Where procfs and sysfs are mounted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemRoots {
+    /// Root of procfs, normally `/proc`
+    pub proc: PathBuf,
+    /// Root of sysfs, normally `/sys`
+    pub sys: PathBuf,
+}
+
+/// One line of `/proc/self/mountinfo`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MountEntry {
+    /// Major number of the mounted device
+    pub major: u32,
+    /// Minor number of the mounted device
+    pub minor: u32,
+    /// Directory within the filesystem that forms the root of the mount
+    pub root: PathBuf,
+    /// Where the filesystem is mounted
+    pub mount_point: PathBuf,
+    /// Per-mount options, e.g. `rw,relatime`
+    pub mount_options: String,
+    /// Filesystem type, e.g. `ext4`
+    pub fs_type: String,
+    /// Mount source, e.g. `/dev/nvme0n1p2`
+    pub source: String,
+    /// Per-filesystem options
+    pub super_options: String,
+}
+
+/// Block device a path is stored on, as described by sysfs
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockDevice {
+    /// Kernel name of the whole disk, e.g. `nvme0n1` or `sda`
+    pub name: String,
+    /// Model string reported by the device
+    pub model: Option<String>,
+    /// Whether the device has spinning media
+    pub rotational: Option<bool>,
+    /// Smallest unit the device can address, in bytes
+    pub logical_block_size: Option<u64>,
+    /// Smallest unit the device writes without a read-modify-write, in bytes
+    pub physical_block_size: Option<u64>,
+    /// Active I/O scheduler, e.g. `mq-deadline` or `none`
+    pub scheduler: Option<String>,
+}
+
+impl Default for SystemRoots {
+    fn default() -> Self {
+        Self::new("/proc", "/sys")
+    }
+}
+
+impl SystemRoots {
+    /// Use procfs and sysfs mounted at the given roots
+    pub fn new(proc: impl Into<PathBuf>, sys: impl Into<PathBuf>) -> Self {
+        Self {
+            proc: proc.into(),
+            sys: sys.into(),
+        }
+    }
+
+    /// Read the mount table of the current process
+    pub fn mounts(&self) -> io::Result<Vec<MountEntry>> {
+        let content = fs::read_to_string(self.proc.join("self/mountinfo"))?;
+        Ok(parse_mountinfo(&content))
+    }
+
+    /// Find the mount holding `path`
+    #[cfg(unix)]
+    pub fn mount_for_path(&self, path: &Path) -> io::Result<Option<MountEntry>> {
+        use std::os::unix::fs::MetadataExt;
+
+        let path = fs::canonicalize(path)?;
+        let device = split_device_number(fs::metadata(&path)?.dev());
+        Ok(find_mount(self.mounts()?, &path, device))
+    }
+
+    /// Find the mount holding `path`
+    #[cfg(not(unix))]
+    pub fn mount_for_path(&self, _path: &Path) -> io::Result<Option<MountEntry>> {
+        Ok(None)
+    }
+
+    /// Find the block device `path` is stored on
+    ///
+    /// Returns `None` when the path is not backed by a block device, such
+    /// as on tmpfs or a network filesystem.
+    pub fn device_for_path(&self, path: &Path) -> io::Result<Option<BlockDevice>> {
+        Ok(self
+            .mount_for_path(path)?
+            .and_then(|mount| self.block_device(&mount)))
+    }
+
+    /// Describe the whole disk behind a mount
+    ///
+    /// The device is looked up by number first and by the mount source's
+    /// name second, for filesystems such as btrfs that report an anonymous
+    /// device number. Partitions resolve to the disk holding them, since
+    /// the queue settings live there.
+    pub fn block_device(&self, mount: &MountEntry) -> Option<BlockDevice> {
+        let by_number = self
+            .sys
+            .join("dev/block")
+            .join(format!("{}:{}", mount.major, mount.minor));
+        let dir = fs::canonicalize(by_number).ok().or_else(|| {
+            let name = Path::new(&mount.source).file_name()?;
+            fs::canonicalize(self.sys.join("class/block").join(name)).ok()
+        })?;
+        let disk = if dir.join("partition").exists() {
+            dir.parent()?
+        } else {
+            dir.as_path()
+        };
+        let name = disk.file_name()?.to_str()?.to_string();
+
+        let block = self.sys.join("block").join(&name);
+        let queue = block.join("queue");
+        Some(BlockDevice {
+            model: read_value(&block.join("device/model")),
+            rotational: read_value(&queue.join("rotational")).map(|value| value == "1"),
+            logical_block_size: read_number(&queue.join("logical_block_size")),
+            physical_block_size: read_number(&queue.join("physical_block_size")),
+            scheduler: read_value(&queue.join("scheduler")).map(|value| active_scheduler(&value)),
+            name,
+        })
+    }
+}
+
+impl BlockDevice {
+    /// Kind of storage, from the device name and whether it rotates
+    pub fn storage_type(&self) -> StorageType {
+        if self.name.starts_with("nvme") {
+            return StorageType::NVMe;
+        }
+        match self.rotational {
+            Some(true) => StorageType::HDD,
+            Some(false) => StorageType::SSD,
+            None => StorageType::Unknown,
+        }
+    }
+
+    /// Device node path, e.g. `/dev/sda`
+    pub fn dev_path(&self) -> PathBuf {
+        Path::new("/dev").join(&self.name)
+    }
+}
+
+/// Parse the contents of a `mountinfo` file, skipping malformed lines
+pub fn parse_mountinfo(content: &str) -> Vec<MountEntry> {
+    content.lines().filter_map(parse_mountinfo_line).collect()
+}
+
+/// Parse a line such as
+/// `36 35 98:0 /mnt1 /mnt/parent rw,noatime master:1 - ext3 /dev/root rw`
+fn parse_mountinfo_line(line: &str) -> Option<MountEntry> {
+    // Optional fields run up to a lone "-"
+    let (mount, filesystem) = line.split_once(" - ")?;
+    let mut fields = mount.split_whitespace();
+    let _mount_id = fields.next()?;
+    let _parent_id = fields.next()?;
+    let (major, minor) = fields.next()?.split_once(':')?;
+    let root = fields.next()?;
+    let mount_point = fields.next()?;
+    let mount_options = fields.next()?;
+
+    let mut fields = filesystem.split_whitespace();
+    let fs_type = fields.next()?;
+    let source = fields.next()?;
+    let super_options = fields.next().unwrap_or_default();
+
+    Some(MountEntry {
+        major: major.parse().ok()?,
+        minor: minor.parse().ok()?,
+        root: PathBuf::from(unescape(root)),
+        mount_point: PathBuf::from(unescape(mount_point)),
+        mount_options: mount_options.to_string(),
+        fs_type: fs_type.to_string(),
+        source: unescape(source),
+        super_options: super_options.to_string(),
+    })
+}
+
+/// Pick the mount holding `path` on the device numbered `device`
+///
+/// Of the mounts of that device containing the path, the deepest wins. If
+/// none matches the device, as for mounts stacked on other filesystems, the
+/// deepest mount containing the path is used.
+pub fn find_mount(mounts: Vec<MountEntry>, path: &Path, device: (u32, u32)) -> Option<MountEntry> {
+    let (containing, others): (Vec<MountEntry>, Vec<MountEntry>) = mounts
+        .into_iter()
+        .filter(|mount| path.starts_with(&mount.mount_point))
+        .partition(|mount| (mount.major, mount.minor) == device);
+    let deepest = |mounts: Vec<MountEntry>| {
+        mounts
+            .into_iter()
+            .max_by_key(|mount| mount.mount_point.components().count())
+    };
+    deepest(containing).or_else(|| deepest(others))
+}
+
+/// Split a Linux `dev_t` into its major and minor numbers
+pub fn split_device_number(dev: u64) -> (u32, u32) {
+    let major = ((dev >> 8) & 0xfff) | ((dev >> 32) & !0xfff);
+    let minor = (dev & 0xff) | ((dev >> 12) & !0xff);
+    (major as u32, minor as u32)
+}
+
+/// Active scheduler from a list such as `none [mq-deadline] kyber`
+fn active_scheduler(list: &str) -> String {
+    list.split_whitespace()
+        .find_map(|name| name.strip_prefix('[')?.strip_suffix(']'))
+        .unwrap_or(list.trim())
+        .to_string()
+}
+
+/// Undo the octal escapes mountinfo uses for spaces, tabs, newlines and backslashes
+fn unescape(field: &str) -> String {
+    let mut out = String::with_capacity(field.len());
+    let mut rest = field;
+    while let Some(index) = rest.find('\\') {
+        out.push_str(&rest[..index]);
+        let escaped = rest
+            .get(index + 1..index + 4)
+            .and_then(|digits| u8::from_str_radix(digits, 8).ok());
+        match escaped {
+            Some(byte) => {
+                out.push(byte as char);
+                rest = &rest[index + 4..];
+            }
+            None => {
+                out.push('\\');
+                rest = &rest[index + 1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Read a sysfs attribute, trimmed, or `None` if it is missing or empty
+fn read_value(path: &Path) -> Option<String> {
+    let value = fs::read_to_string(path).ok()?;
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+fn read_number(path: &Path) -> Option<u64> {
+    read_value(path)?.parse().ok()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::{symlink, MetadataExt};
+    use tempfile::TempDir;
+
+    const MOUNTINFO: &str = "\
+22 1 8:2 / / rw,relatime - ext4 /dev/sda2 rw,errors=remount-ro
+23 22 0:22 / /proc rw,nosuid - proc proc rw
+24 22 0:45 /@home /home rw,noatime shared:5 - btrfs /dev/nvme0n1p3 rw,ssd,space_cache=v2
+25 22 259:1 / /mnt/fast\\040disk rw,noatime master:1 - xfs /dev/nvme0n1p1 rw,attr2
+26 22 0:30 / /tmp rw - tmpfs tmpfs rw,size=1024k
+garbage line
+";
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    /// Lay out sysfs entries for a disk and one of its partitions, the way
+    /// the kernel links them
+    fn add_disk(
+        sys: &Path,
+        disk: &str,
+        partition: (&str, u32, u32),
+        rotational: &str,
+        model: Option<&str>,
+    ) {
+        let device = sys.join("devices/pci0000:00/host0/block").join(disk);
+        write(&device.join("queue/rotational"), rotational);
+        write(&device.join("queue/logical_block_size"), "512\n");
+        write(&device.join("queue/physical_block_size"), "4096\n");
+        write(
+            &device.join("queue/scheduler"),
+            "none [mq-deadline] kyber bfq\n",
+        );
+        if let Some(model) = model {
+            write(&device.join("device/model"), model);
+        }
+        let (name, major, minor) = partition;
+        write(&device.join(name).join("partition"), "1\n");
+
+        for dir in ["block", "dev/block", "class/block"] {
+            fs::create_dir_all(sys.join(dir)).unwrap();
+        }
+        symlink(&device, sys.join("block").join(disk)).unwrap();
+        symlink(device.join(name), sys.join("class/block").join(name)).unwrap();
+        symlink(
+            device.join(name),
+            sys.join("dev/block").join(format!("{}:{}", major, minor)),
+        )
+        .unwrap();
+    }
+
+    fn fixture() -> (TempDir, SystemRoots) {
+        let root = TempDir::new().unwrap();
+        let roots = SystemRoots::new(root.path().join("proc"), root.path().join("sys"));
+        write(&roots.proc.join("self/mountinfo"), MOUNTINFO);
+        add_disk(
+            &roots.sys,
+            "sda",
+            ("sda2", 8, 2),
+            "1\n",
+            Some("ST2000DM008  \n"),
+        );
+        add_disk(
+            &roots.sys,
+            "nvme0n1",
+            ("nvme0n1p1", 259, 1),
+            "0\n",
+            Some("Samsung SSD 980 PRO 1TB\n"),
+        );
+        (root, roots)
+    }
+
+    #[test]
+    fn test_parse_mountinfo() {
+        let mounts = parse_mountinfo(MOUNTINFO);
+        assert_eq!(mounts.len(), 5, "malformed lines are skipped");
+        assert_eq!(
+            mounts[0],
+            MountEntry {
+                major: 8,
+                minor: 2,
+                root: PathBuf::from("/"),
+                mount_point: PathBuf::from("/"),
+                mount_options: "rw,relatime".to_string(),
+                fs_type: "ext4".to_string(),
+                source: "/dev/sda2".to_string(),
+                super_options: "rw,errors=remount-ro".to_string(),
+            }
+        );
+        // Optional fields are skipped and escaped spaces restored
+        assert_eq!(mounts[2].fs_type, "btrfs");
+        assert_eq!(mounts[2].root, PathBuf::from("/@home"));
+        assert_eq!(mounts[3].mount_point, PathBuf::from("/mnt/fast disk"));
+        assert_eq!(mounts[3].source, "/dev/nvme0n1p1");
+    }
+
+    #[test]
+    fn test_find_mount() {
+        let mounts = parse_mountinfo(MOUNTINFO);
+        let find = |path: &str, device| {
+            find_mount(mounts.clone(), Path::new(path), device).map(|mount| mount.mount_point)
+        };
+        assert_eq!(
+            find("/mnt/fast disk/data", (259, 1)),
+            Some(PathBuf::from("/mnt/fast disk"))
+        );
+        assert_eq!(find("/var/lib", (8, 2)), Some(PathBuf::from("/")));
+        // A device number no mount reports falls back to the deepest containing mount
+        assert_eq!(find("/home/me", (0, 99)), Some(PathBuf::from("/home")));
+        assert_eq!(find("/tmp/x", (0, 30)), Some(PathBuf::from("/tmp")));
+    }
+
+    #[test]
+    fn test_block_device_from_sysfs() {
+        let (_root, roots) = fixture();
+        let mounts = parse_mountinfo(MOUNTINFO);
+
+        // A partition resolves to its disk, where the queue settings live
+        let hdd = roots.block_device(&mounts[0]).unwrap();
+        assert_eq!(
+            hdd,
+            BlockDevice {
+                name: "sda".to_string(),
+                model: Some("ST2000DM008".to_string()),
+                rotational: Some(true),
+                logical_block_size: Some(512),
+                physical_block_size: Some(4096),
+                scheduler: Some("mq-deadline".to_string()),
+            }
+        );
+        assert_eq!(hdd.storage_type(), StorageType::HDD);
+        assert_eq!(hdd.dev_path(), PathBuf::from("/dev/sda"));
+
+        let nvme = roots.block_device(&mounts[3]).unwrap();
+        assert_eq!(nvme.name, "nvme0n1");
+        assert_eq!(nvme.storage_type(), StorageType::NVMe);
+
+        // btrfs reports an anonymous device number; the source names the device
+        let mut btrfs = mounts[2].clone();
+        btrfs.source = "/dev/sda2".to_string();
+        assert_eq!(roots.block_device(&btrfs).unwrap().name, "sda");
+
+        // tmpfs has no block device
+        assert_eq!(roots.block_device(&mounts[4]), None);
+    }
+
+    #[test]
+    fn test_device_for_path() {
+        let root = TempDir::new().unwrap();
+        let roots = SystemRoots::new(root.path().join("proc"), root.path().join("sys"));
+        let data = root.path().join("data");
+        fs::create_dir(&data).unwrap();
+        let data = fs::canonicalize(data).unwrap();
+        let (major, minor) = split_device_number(fs::metadata(&data).unwrap().dev());
+
+        // Mount a SATA SSD at the data directory
+        add_disk(&roots.sys, "sdb", ("sdb1", major, minor), "0\n", None);
+        let mountinfo = format!(
+            "22 1 8:2 / / rw - ext4 /dev/sda2 rw\n30 22 {}:{} / {} rw - ext4 /dev/sdb1 rw\n",
+            major,
+            minor,
+            data.display()
+        );
+        write(&roots.proc.join("self/mountinfo"), &mountinfo);
+
+        let device = roots.device_for_path(&data).unwrap().unwrap();
+        assert_eq!(device.name, "sdb");
+        assert_eq!(device.model, None);
+        assert_eq!(device.storage_type(), StorageType::SSD);
+        assert!(roots.device_for_path(&root.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_helpers() {
+        assert_eq!(split_device_number(0x0803), (8, 3));
+        // Minor numbers above 255 spill into the high bits
+        assert_eq!(split_device_number(0x1001_0301), (259, 0x1_0001));
+        assert_eq!(active_scheduler("[none] mq-deadline"), "none");
+        assert_eq!(active_scheduler("none"), "none");
+        assert_eq!(unescape("a\\040b\\134c\\"), "a b\\c\\");
+    }
+}
@@ -0,0 +1,310 @@
+//! Host detection
+//!
+//! Reads the CPU model and memory from procfs, the kernel from `uname` and
+//! filesystem space from `statvfs`, so saved results record which machine
+//! and filesystem they came from. Procfs is read under `SystemRoots` so the
+//! parsers can run against fixtures.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::storage::SystemRoots;
+
+/// CPU model and count from `/proc/cpuinfo`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CpuInfo {
+    /// Model name, e.g. `AMD Ryzen 9 7950X 16-Core Processor`
+    pub model: String,
+    /// Number of logical CPUs
+    pub count: usize,
+}
+
+/// Memory totals from `/proc/meminfo`, in bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryInfo {
+    /// Usable memory
+    pub total: u64,
+    /// Memory available for new allocations without swapping
+    pub available: u64,
+}
+
+/// Kernel identification from `uname`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KernelInfo {
+    /// Kernel name, e.g. `Linux`
+    pub name: String,
+    /// Kernel release, e.g. `6.8.0-45-generic`
+    pub release: String,
+    /// Hardware architecture, e.g. `x86_64`
+    pub machine: String,
+}
+
+/// Size of a filesystem from `statvfs`, in bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FilesystemSpace {
+    /// Total size
+    pub total: u64,
+    /// Space available to unprivileged users
+    pub available: u64,
+}
+
+impl SystemRoots {
+    /// Read the CPU model and count
+    pub fn cpu_info(&self) -> io::Result<Option<CpuInfo>> {
+        let content = fs::read_to_string(self.proc.join("cpuinfo"))?;
+        Ok(parse_cpuinfo(&content))
+    }
+
+    /// Read total and available memory
+    pub fn memory_info(&self) -> io::Result<Option<MemoryInfo>> {
+        let content = fs::read_to_string(self.proc.join("meminfo"))?;
+        Ok(parse_meminfo(&content))
+    }
+}
+
+impl KernelInfo {
+    /// Identify the running kernel
+    #[cfg(unix)]
+    pub fn detect() -> Option<Self> {
+        // SAFETY: utsname is plain data, and uname only writes into it
+        let mut name: libc::utsname = unsafe { std::mem::zeroed() };
+        if unsafe { libc::uname(&mut name) } != 0 {
+            return None;
+        }
+        Some(Self {
+            name: c_chars_to_string(&name.sysname),
+            release: c_chars_to_string(&name.release),
+            machine: c_chars_to_string(&name.machine),
+        })
+    }
+
+    /// Identify the running kernel
+    #[cfg(not(unix))]
+    pub fn detect() -> Option<Self> {
+        None
+    }
+
+    /// Human-readable description, e.g. `Linux 6.8.0-45-generic x86_64`
+    pub fn description(&self) -> String {
+        format!("{} {} {}", self.name, self.release, self.machine)
+    }
+}
+
+impl FilesystemSpace {
+    /// Measure the filesystem holding `path`
+    #[cfg(unix)]
+    // The statvfs field types are narrower than u64 on some platforms
+    #[allow(clippy::useless_conversion)]
+    pub fn of(path: &Path) -> io::Result<Self> {
+        use std::ffi::CString;
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = CString::new(path.as_os_str().as_bytes())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        // SAFETY: statvfs is plain data, the path is NUL-terminated and
+        // statvfs only writes into the struct it is given
+        let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+        if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let fragment = u64::from(stat.f_frsize);
+        Ok(Self {
+            total: u64::from(stat.f_blocks) * fragment,
+            available: u64::from(stat.f_bavail) * fragment,
+        })
+    }
+
+    /// Measure the filesystem holding `path`
+    #[cfg(not(unix))]
+    pub fn of(_path: &Path) -> io::Result<Self> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "filesystem space is only measured on Unix",
+        ))
+    }
+}
+
+/// Parse `/proc/cpuinfo`
+///
+/// x86 reports `model name` for every CPU; ARM and other architectures
+/// name the CPU or board under other keys, which are tried in turn.
+pub fn parse_cpuinfo(content: &str) -> Option<CpuInfo> {
+    const MODEL_KEYS: [&str; 5] = ["model name", "Model", "Hardware", "cpu model", "cpu"];
+
+    let fields: Vec<(&str, &str)> = content
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            Some((key.trim(), value.trim()))
+        })
+        .collect();
+    let count = fields.iter().filter(|(key, _)| *key == "processor").count();
+    let model = MODEL_KEYS.iter().find_map(|wanted| {
+        fields
+            .iter()
+            .find(|(key, value)| key == wanted && !value.is_empty())
+            .map(|(_, value)| value.split_whitespace().collect::<Vec<_>>().join(" "))
+    })?;
+    Some(CpuInfo {
+        model,
+        count: count.max(1),
+    })
+}
+
+/// Parse `/proc/meminfo`
+///
+/// Kernels before 3.14 lack `MemAvailable`, so it is estimated from free
+/// memory plus buffers and page cache there.
+pub fn parse_meminfo(content: &str) -> Option<MemoryInfo> {
+    let field = |wanted: &str| {
+        content.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            if key.trim() != wanted {
+                return None;
+            }
+            let kib: u64 = value.trim().trim_end_matches("kB").trim().parse().ok()?;
+            Some(kib * 1024)
+        })
+    };
+    let total = field("MemTotal")?;
+    let available = field("MemAvailable").unwrap_or_else(|| {
+        ["MemFree", "Buffers", "Cached"]
+            .iter()
+            .filter_map(|key| field(key))
+            .sum()
+    });
+    Some(MemoryInfo { total, available })
+}
+
+/// Merge per-mount and per-filesystem options, dropping repeats
+pub fn merge_mount_options(mount_options: &str, super_options: &str) -> Vec<String> {
+    let mut options: Vec<String> = Vec::new();
+    for option in mount_options.split(',').chain(super_options.split(',')) {
+        if !option.is_empty() && !options.iter().any(|seen| seen == option) {
+            options.push(option.to_string());
+        }
+    }
+    options
+}
+
+#[cfg(unix)]
+fn c_chars_to_string(chars: &[libc::c_char]) -> String {
+    let bytes: Vec<u8> = chars
+        .iter()
+        .take_while(|&&c| c != 0)
+        .map(|&c| c as u8)
+        .collect();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const CPUINFO_X86: &str = "\
+processor\t: 0
+vendor_id\t: AuthenticAMD
+model name\t: AMD Ryzen 9 7950X  16-Core Processor
+cpu MHz\t\t: 4500.000
+
+processor\t: 1
+vendor_id\t: AuthenticAMD
+model name\t: AMD Ryzen 9 7950X  16-Core Processor
+cpu MHz\t\t: 4500.000
+";
+
+    const CPUINFO_ARM: &str = "\
+processor\t: 0
+BogoMIPS\t: 108.00
+CPU implementer\t: 0x41
+CPU part\t: 0xd08
+
+processor\t: 1
+BogoMIPS\t: 108.00
+
+processor\t: 2
+
+processor\t: 3
+
+Hardware\t: BCM2835
+Model\t\t: Raspberry Pi 4 Model B Rev 1.4
+";
+
+    const MEMINFO: &str = "\
+MemTotal:       16318412 kB
+MemFree:         2512788 kB
+MemAvailable:   10540524 kB
+Buffers:           73516 kB
+Cached:          3122984 kB
+";
+
+    #[test]
+    fn test_parse_cpuinfo() {
+        assert_eq!(
+            parse_cpuinfo(CPUINFO_X86),
+            Some(CpuInfo {
+                model: "AMD Ryzen 9 7950X 16-Core Processor".to_string(),
+                count: 2,
+            })
+        );
+        let arm = parse_cpuinfo(CPUINFO_ARM).unwrap();
+        assert_eq!(arm.model, "Raspberry Pi 4 Model B Rev 1.4");
+        assert_eq!(arm.count, 4);
+        assert_eq!(parse_cpuinfo("processor\t: 0\n"), None);
+    }
+
+    #[test]
+    fn test_parse_meminfo() {
+        assert_eq!(
+            parse_meminfo(MEMINFO),
+            Some(MemoryInfo {
+                total: 16318412 * 1024,
+                available: 10540524 * 1024,
+            })
+        );
+        // Older kernels: estimate from free memory and caches
+        let old = MEMINFO.replace("MemAvailable:   10540524 kB\n", "");
+        assert_eq!(
+            parse_meminfo(&old).unwrap().available,
+            (2512788 + 73516 + 3122984) * 1024
+        );
+        assert_eq!(parse_meminfo("MemFree: 1 kB\n"), None);
+    }
+
+    #[test]
+    fn test_read_from_roots() {
+        let root = TempDir::new().unwrap();
+        let roots = SystemRoots::new(root.path().join("proc"), root.path().join("sys"));
+        assert!(roots.cpu_info().is_err());
+
+        fs::create_dir_all(&roots.proc).unwrap();
+        fs::write(roots.proc.join("cpuinfo"), CPUINFO_ARM).unwrap();
+        fs::write(roots.proc.join("meminfo"), MEMINFO).unwrap();
+        assert_eq!(roots.cpu_info().unwrap().unwrap().count, 4);
+        assert_eq!(roots.memory_info().unwrap().unwrap().total, 16318412 * 1024);
+    }
+
+    #[test]
+    fn test_merge_mount_options() {
+        assert_eq!(
+            merge_mount_options("rw,noatime", "rw,ssd,discard=async,compress=zstd:3"),
+            vec!["rw", "noatime", "ssd", "discard=async", "compress=zstd:3"]
+        );
+        assert!(merge_mount_options("", "").is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_live_detection() {
+        let kernel = KernelInfo::detect().unwrap();
+        assert!(!kernel.name.is_empty());
+        assert!(!kernel.release.is_empty());
+
+        let space = FilesystemSpace::of(&std::env::temp_dir()).unwrap();
+        assert!(space.total > 0);
+        assert!(space.available <= space.total);
+        assert!(FilesystemSpace::of(Path::new("/nonexistent/path")).is_err());
+    }
+}
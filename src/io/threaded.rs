@@ -0,0 +1,218 @@
+//! Thread-pool I/O engine
+//!
+//! Portable engine that reaches queue depths above one by running blocking
+//! positional reads and writes in parallel on dedicated OS threads, all
+//! sharing a single open file.
+
+use std::io;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use super::buffer::AlignedBuffer;
+use super::disk::DirectFile;
+use super::engine::{fill_test_pattern, IoCompletion, IoEngine, IoOp, IoRequest};
+use crate::config::IoEngineKind;
+
+/// Engine with one I/O thread per queue slot
+pub struct ThreadPoolEngine {
+    file: Arc<dyn DirectFile>,
+    jobs: Option<mpsc::Sender<IoRequest>>,
+    done: mpsc::Receiver<io::Result<IoCompletion>>,
+    threads: Vec<JoinHandle<()>>,
+    block_size: usize,
+    in_flight: usize,
+    busy: Duration,
+    completed_ops: u64,
+}
+
+impl ThreadPoolEngine {
+    /// Start `queue_depth` I/O threads over an open file
+    pub fn new(
+        file: Box<dyn DirectFile>,
+        block_size: usize,
+        queue_depth: usize,
+        alignment: usize,
+    ) -> io::Result<Self> {
+        let file: Arc<dyn DirectFile> = Arc::from(file);
+        let (jobs_tx, jobs_rx) = mpsc::channel::<IoRequest>();
+        let (done_tx, done_rx) = mpsc::channel();
+        let jobs_rx = Arc::new(Mutex::new(jobs_rx));
+
+        let mut threads = Vec::with_capacity(queue_depth.max(1));
+        for id in 0..queue_depth.max(1) {
+            let file = Arc::clone(&file);
+            let jobs = Arc::clone(&jobs_rx);
+            let done = done_tx.clone();
+            let handle = thread::Builder::new()
+                .name(format!("diorb-io-{}", id))
+                .spawn(move || io_thread(file, block_size, alignment, jobs, done))?;
+            threads.push(handle);
+        }
+
+        Ok(Self {
+            file,
+            jobs: Some(jobs_tx),
+            done: done_rx,
+            threads,
+            block_size,
+            in_flight: 0,
+            busy: Duration::ZERO,
+            completed_ops: 0,
+        })
+    }
+
+    fn record(&mut self, result: io::Result<IoCompletion>) -> io::Result<IoCompletion> {
+        self.in_flight -= 1;
+        let completion = result?;
+        self.busy += completion.latency;
+        self.completed_ops += 1;
+        Ok(completion)
+    }
+}
+
+/// Body of an I/O thread: take requests until the engine is dropped
+fn io_thread(
+    file: Arc<dyn DirectFile>,
+    block_size: usize,
+    alignment: usize,
+    jobs: Arc<Mutex<mpsc::Receiver<IoRequest>>>,
+    done: mpsc::Sender<io::Result<IoCompletion>>,
+) {
+    let mut buffer = AlignedBuffer::new(block_size, alignment);
+    fill_test_pattern(&mut buffer);
+
+    loop {
+        let request = match jobs.lock() {
+            Ok(jobs) => match jobs.recv() {
+                Ok(request) => request,
+                Err(_) => return,
+            },
+            Err(_) => return,
+        };
+
+        let start = Instant::now();
+        let result = match request.op {
+            IoOp::Read => file.read_at(&mut buffer, request.offset),
+            IoOp::Write => file.write_at(&buffer, request.offset),
+        };
+        let completion = result.map(|bytes| IoCompletion {
+            op: request.op,
+            offset: request.offset,
+            bytes,
+            latency: start.elapsed(),
+        });
+
+        if done.send(completion).is_err() {
+            return;
+        }
+    }
+}
+
+impl IoEngine for ThreadPoolEngine {
+    fn kind(&self) -> IoEngineKind {
+        IoEngineKind::ThreadPool
+    }
+
+    fn queue_depth(&self) -> usize {
+        self.threads.len()
+    }
+
+    fn in_flight(&self) -> usize {
+        self.in_flight
+    }
+
+    fn submit(&mut self, request: IoRequest) -> io::Result<()> {
+        request.check_aligned(self.block_size)?;
+        if self.in_flight >= self.threads.len() {
+            return Err(io::Error::other("thread pool queue depth exceeded"));
+        }
+        self.jobs
+            .as_ref()
+            .and_then(|jobs| jobs.send(request).ok())
+            .ok_or_else(|| io::Error::other("I/O threads have stopped"))?;
+        self.in_flight += 1;
+        Ok(())
+    }
+
+    fn reap(&mut self, min_complete: usize, completions: &mut Vec<IoCompletion>) -> io::Result<()> {
+        let min_complete = min_complete.min(self.in_flight);
+        for _ in 0..min_complete {
+            let result = self
+                .done
+                .recv()
+                .map_err(|_| io::Error::other("I/O threads have stopped"))?;
+            completions.push(self.record(result)?);
+        }
+        while let Ok(result) = self.done.try_recv() {
+            completions.push(self.record(result)?);
+        }
+        Ok(())
+    }
+
+    fn sync(&mut self) -> io::Result<()> {
+        let mut completions = Vec::new();
+        self.reap(self.in_flight, &mut completions)?;
+        self.file.sync_all()
+    }
+
+    fn busy_time(&self) -> Duration {
+        self.busy
+    }
+
+    fn completed_ops(&self) -> u64 {
+        self.completed_ops
+    }
+}
+
+impl Drop for ThreadPoolEngine {
+    fn drop(&mut self) {
+        // Closing the job channel lets each thread finish its current request and exit
+        self.jobs.take();
+        for handle in self.threads.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::io::buffer::MIN_ALIGNMENT;
+    use crate::io::engine::{create_engine, test_support};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_thread_pool_round_trip() {
+        let temp_dir = tempdir().unwrap();
+        let file = test_support::open_buffered(&temp_dir.path().join("pool.dat"));
+
+        let mut engine =
+            create_engine(IoEngineKind::ThreadPool, file, 4096, 4, MIN_ALIGNMENT).unwrap();
+        assert_eq!(engine.kind(), IoEngineKind::ThreadPool);
+        assert_eq!(engine.queue_depth(), 4);
+        test_support::exercise_engine(engine.as_mut(), 64, 4096);
+    }
+
+    #[test]
+    fn test_thread_pool_rejects_overflow() {
+        let temp_dir = tempdir().unwrap();
+        let file = test_support::open_buffered(&temp_dir.path().join("pool.dat"));
+
+        let mut engine = ThreadPoolEngine::new(file, 512, 2, MIN_ALIGNMENT).unwrap();
+        let request = IoRequest {
+            op: IoOp::Write,
+            offset: 0,
+        };
+        engine.submit(request).unwrap();
+        engine.submit(request).unwrap();
+        assert!(engine.submit(request).is_err());
+
+        let mut completions = Vec::new();
+        engine.reap(2, &mut completions).unwrap();
+        assert_eq!(completions.len(), 2);
+        assert_eq!(engine.in_flight(), 0);
+        assert_eq!(engine.completed_ops(), 2);
+        assert!(engine.achieved_queue_depth(Duration::from_secs(1)) > 0.0);
+    }
+}
@@ -0,0 +1,629 @@
+//! io_uring I/O engine (Linux only)
+//!
+//! Talks to the kernel through the raw `io_uring_setup`/`io_uring_enter`
+//! syscalls and memory-mapped submission/completion rings, keeping up to
+//! `queue_depth` block-sized requests in flight against a single file.
+
+use std::io;
+use std::os::unix::io::RawFd;
+use std::ptr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
+
+use super::buffer::AlignedBuffer;
+use super::disk::DirectFile;
+use super::engine::{fill_test_pattern, IoCompletion, IoEngine, IoOp, IoRequest};
+use crate::config::IoEngineKind;
+
+const IORING_OFF_SQ_RING: libc::off_t = 0;
+const IORING_OFF_CQ_RING: libc::off_t = 0x8000000;
+const IORING_OFF_SQES: libc::off_t = 0x10000000;
+
+const IORING_ENTER_GETEVENTS: u32 = 1;
+
+const IORING_OP_READ: u8 = 22;
+const IORING_OP_WRITE: u8 = 23;
+
+const IORING_REGISTER_PROBE: u32 = 8;
+const IO_URING_OP_SUPPORTED: u16 = 1;
+/// Opcodes to ask the kernel about, enough to cover read and write
+const PROBE_OPS: usize = 64;
+
+#[repr(C)]
+#[derive(Debug, Default)]
+struct SqringOffsets {
+    head: u32,
+    tail: u32,
+    ring_mask: u32,
+    ring_entries: u32,
+    flags: u32,
+    dropped: u32,
+    array: u32,
+    resv1: u32,
+    user_addr: u64,
+}
+
+#[repr(C)]
+#[derive(Debug, Default)]
+struct CqringOffsets {
+    head: u32,
+    tail: u32,
+    ring_mask: u32,
+    ring_entries: u32,
+    overflow: u32,
+    cqes: u32,
+    flags: u32,
+    resv1: u32,
+    user_addr: u64,
+}
+
+#[repr(C)]
+#[derive(Debug, Default)]
+struct Params {
+    sq_entries: u32,
+    cq_entries: u32,
+    flags: u32,
+    sq_thread_cpu: u32,
+    sq_thread_idle: u32,
+    features: u32,
+    wq_fd: u32,
+    resv: [u32; 3],
+    sq_off: SqringOffsets,
+    cq_off: CqringOffsets,
+}
+
+/// Submission queue entry (`struct io_uring_sqe`)
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy)]
+struct Sqe {
+    opcode: u8,
+    flags: u8,
+    ioprio: u16,
+    fd: i32,
+    off: u64,
+    addr: u64,
+    len: u32,
+    rw_flags: u32,
+    user_data: u64,
+    buf_index: u16,
+    personality: u16,
+    splice_fd_in: i32,
+    addr3: u64,
+    pad2: u64,
+}
+
+/// Completion queue entry (`struct io_uring_cqe`)
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct Cqe {
+    user_data: u64,
+    res: i32,
+    flags: u32,
+}
+
+/// One opcode in a probe reply (`struct io_uring_probe_op`)
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy)]
+struct ProbeOp {
+    op: u8,
+    resv: u8,
+    flags: u16,
+    resv2: u32,
+}
+
+/// Opcode support reported by `IORING_REGISTER_PROBE` (`struct io_uring_probe`)
+#[repr(C)]
+#[derive(Debug)]
+struct Probe {
+    last_op: u8,
+    ops_len: u8,
+    resv: u16,
+    resv2: [u32; 3],
+    ops: [ProbeOp; PROBE_OPS],
+}
+
+/// Shared memory mapping of one of the ring regions
+struct Mmap {
+    ptr: *mut libc::c_void,
+    len: usize,
+}
+
+impl Mmap {
+    fn new(fd: RawFd, offset: libc::off_t, len: usize) -> io::Result<Self> {
+        // SAFETY: mapping a fresh region of the ring fd; the kernel validates offset and length
+        let ptr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED | libc::MAP_POPULATE,
+                fd,
+                offset,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self { ptr, len })
+    }
+
+    /// Pointer to a field at a byte offset within the mapping
+    fn at<T>(&self, offset: u32) -> *mut T {
+        // SAFETY: offsets come from the kernel and lie within the mapped length
+        unsafe { self.ptr.cast::<u8>().add(offset as usize).cast() }
+    }
+}
+
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        // SAFETY: ptr/len describe a mapping created in `Mmap::new`
+        unsafe {
+            libc::munmap(self.ptr, self.len);
+        }
+    }
+}
+
+/// Minimal io_uring instance with submission and completion rings
+struct Ring {
+    fd: RawFd,
+    sq_head: *const AtomicU32,
+    sq_tail: *const AtomicU32,
+    sq_mask: u32,
+    sq_entries: u32,
+    sq_array: *mut u32,
+    sqes: *mut Sqe,
+    cq_head: *const AtomicU32,
+    cq_tail: *const AtomicU32,
+    cq_mask: u32,
+    cqes: *const Cqe,
+    _maps: [Mmap; 3],
+}
+
+// SAFETY: the ring is only accessed through `&mut self`, so it is never shared between threads
+unsafe impl Send for Ring {}
+
+impl Ring {
+    /// Set up a ring with at least `entries` submission slots
+    fn new(entries: u32) -> io::Result<Self> {
+        let mut params = Params::default();
+        // SAFETY: params is a valid, zeroed io_uring_params structure
+        let fd = unsafe {
+            libc::syscall(
+                libc::SYS_io_uring_setup,
+                entries,
+                &mut params as *mut Params,
+            )
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let fd = fd as RawFd;
+
+        let maps = (|| {
+            let sq_len = params.sq_off.array as usize + params.sq_entries as usize * 4;
+            let cq_len = params.cq_off.cqes as usize
+                + params.cq_entries as usize * std::mem::size_of::<Cqe>();
+            let sqe_len = params.sq_entries as usize * std::mem::size_of::<Sqe>();
+            Ok::<_, io::Error>([
+                Mmap::new(fd, IORING_OFF_SQ_RING, sq_len)?,
+                Mmap::new(fd, IORING_OFF_CQ_RING, cq_len)?,
+                Mmap::new(fd, IORING_OFF_SQES, sqe_len)?,
+            ])
+        })();
+        let maps = match maps {
+            Ok(maps) => maps,
+            Err(e) => {
+                // SAFETY: fd was returned by io_uring_setup and is not used afterwards
+                unsafe { libc::close(fd) };
+                return Err(e);
+            }
+        };
+
+        let [sq, cq, sqes] = &maps;
+        // SAFETY: ring_mask and ring_entries are read-only fields initialised by the kernel
+        let (sq_mask, sq_entries, cq_mask) = unsafe {
+            (
+                *sq.at::<u32>(params.sq_off.ring_mask),
+                *sq.at::<u32>(params.sq_off.ring_entries),
+                *cq.at::<u32>(params.cq_off.ring_mask),
+            )
+        };
+
+        Ok(Self {
+            fd,
+            sq_head: sq.at(params.sq_off.head),
+            sq_tail: sq.at(params.sq_off.tail),
+            sq_mask,
+            sq_entries,
+            sq_array: sq.at(params.sq_off.array),
+            sqes: sqes.at(0),
+            cq_head: cq.at(params.cq_off.head),
+            cq_tail: cq.at(params.cq_off.tail),
+            cq_mask,
+            cqes: cq.at(params.cq_off.cqes),
+            _maps: maps,
+        })
+    }
+
+    /// Whether the kernel supports every opcode in `opcodes`
+    ///
+    /// Kernels before 5.6 have neither the probe nor the plain read and write
+    /// opcodes, so a failed probe means they are unsupported.
+    fn supports(&self, opcodes: &[u8]) -> bool {
+        let mut probe = Probe {
+            last_op: 0,
+            ops_len: 0,
+            resv: 0,
+            resv2: [0; 3],
+            ops: [ProbeOp::default(); PROBE_OPS],
+        };
+        // SAFETY: probe is zeroed and has room for PROBE_OPS entries
+        let ret = unsafe {
+            libc::syscall(
+                libc::SYS_io_uring_register,
+                self.fd,
+                IORING_REGISTER_PROBE,
+                &mut probe as *mut Probe,
+                PROBE_OPS as u32,
+            )
+        };
+        if ret < 0 {
+            return false;
+        }
+        let reported = &probe.ops[..(probe.ops_len as usize).min(PROBE_OPS)];
+        opcodes.iter().all(|&opcode| {
+            reported
+                .iter()
+                .any(|op| op.op == opcode && op.flags & IO_URING_OP_SUPPORTED != 0)
+        })
+    }
+
+    /// Queue an entry on the submission ring, returning false if it is full
+    fn push(&mut self, sqe: Sqe) -> bool {
+        // SAFETY: head/tail point into the mapped SQ ring; the slot at `tail` is owned
+        // by userspace until the tail is published with release ordering
+        unsafe {
+            let head = (*self.sq_head).load(Ordering::Acquire);
+            let tail = (*self.sq_tail).load(Ordering::Relaxed);
+            if tail.wrapping_sub(head) >= self.sq_entries {
+                return false;
+            }
+            let index = tail & self.sq_mask;
+            ptr::write(self.sqes.add(index as usize), sqe);
+            ptr::write(self.sq_array.add(index as usize), index);
+            (*self.sq_tail).store(tail.wrapping_add(1), Ordering::Release);
+        }
+        true
+    }
+
+    /// Take the next completion off the completion ring
+    fn pop(&mut self) -> Option<Cqe> {
+        // SAFETY: entries between head and tail were published by the kernel
+        unsafe {
+            let head = (*self.cq_head).load(Ordering::Relaxed);
+            let tail = (*self.cq_tail).load(Ordering::Acquire);
+            if head == tail {
+                return None;
+            }
+            let cqe = ptr::read(self.cqes.add((head & self.cq_mask) as usize));
+            (*self.cq_head).store(head.wrapping_add(1), Ordering::Release);
+            Some(cqe)
+        }
+    }
+
+    /// Submit queued entries and optionally wait for completions
+    fn enter(&mut self, to_submit: u32, min_complete: u32) -> io::Result<u32> {
+        let flags = if min_complete > 0 {
+            IORING_ENTER_GETEVENTS
+        } else {
+            0
+        };
+        // SAFETY: plain io_uring_enter call without a signal mask
+        let ret = unsafe {
+            libc::syscall(
+                libc::SYS_io_uring_enter,
+                self.fd,
+                to_submit,
+                min_complete,
+                flags,
+                ptr::null::<libc::sigset_t>(),
+                0usize,
+            )
+        };
+        if ret < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(ret as u32)
+        }
+    }
+}
+
+impl Drop for Ring {
+    fn drop(&mut self) {
+        // SAFETY: fd was returned by io_uring_setup; the mappings are released afterwards
+        unsafe {
+            libc::close(self.fd);
+        }
+    }
+}
+
+/// Per-request buffer and bookkeeping
+struct Slot {
+    buffer: AlignedBuffer,
+    request: Option<(IoRequest, Instant)>,
+}
+
+/// Engine keeping several requests in flight through io_uring
+pub struct IoUringEngine {
+    ring: Ring,
+    file: Box<dyn DirectFile>,
+    block_size: usize,
+    slots: Vec<Slot>,
+    free: Vec<usize>,
+    unsubmitted: u32,
+    busy: Duration,
+    completed_ops: u64,
+}
+
+impl IoUringEngine {
+    /// Set up an io_uring engine over an open file
+    ///
+    /// On failure the file is handed back so callers can fall back to another
+    /// engine. That includes kernels whose io_uring lacks plain reads and writes.
+    pub fn new(
+        file: Box<dyn DirectFile>,
+        block_size: usize,
+        queue_depth: usize,
+        alignment: usize,
+    ) -> std::result::Result<Self, (Box<dyn DirectFile>, io::Error)> {
+        let queue_depth = queue_depth.max(1);
+        let ring = match Ring::new(queue_depth as u32) {
+            Ok(ring) => ring,
+            Err(e) => return Err((file, e)),
+        };
+        if !ring.supports(&[IORING_OP_READ, IORING_OP_WRITE]) {
+            let e = io::Error::new(
+                io::ErrorKind::Unsupported,
+                "io_uring read/write opcodes are not supported (Linux 5.6+)",
+            );
+            return Err((file, e));
+        }
+
+        let slots = (0..queue_depth)
+            .map(|_| {
+                let mut buffer = AlignedBuffer::new(block_size, alignment);
+                fill_test_pattern(&mut buffer);
+                Slot {
+                    buffer,
+                    request: None,
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            ring,
+            file,
+            block_size,
+            slots,
+            free: (0..queue_depth).rev().collect(),
+            unsubmitted: 0,
+            busy: Duration::ZERO,
+            completed_ops: 0,
+        })
+    }
+
+    fn complete(&mut self, cqe: Cqe) -> io::Result<IoCompletion> {
+        let index = cqe.user_data as usize;
+        let (request, submitted) = self.slots[index].request.take().ok_or_else(|| {
+            io::Error::other(format!("io_uring completion for idle slot {}", index))
+        })?;
+        self.free.push(index);
+
+        if cqe.res < 0 {
+            return Err(io::Error::from_raw_os_error(-cqe.res));
+        }
+        let latency = submitted.elapsed();
+        self.busy += latency;
+        self.completed_ops += 1;
+        Ok(IoCompletion {
+            op: request.op,
+            offset: request.offset,
+            bytes: cqe.res as usize,
+            latency,
+        })
+    }
+}
+
+impl IoEngine for IoUringEngine {
+    fn kind(&self) -> IoEngineKind {
+        IoEngineKind::IoUring
+    }
+
+    fn queue_depth(&self) -> usize {
+        self.slots.len()
+    }
+
+    fn in_flight(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    fn submit(&mut self, request: IoRequest) -> io::Result<()> {
+        request.check_aligned(self.block_size)?;
+        let index = self
+            .free
+            .pop()
+            .ok_or_else(|| io::Error::other("io_uring queue depth exceeded"))?;
+        let slot = &mut self.slots[index];
+
+        let sqe = Sqe {
+            opcode: match request.op {
+                IoOp::Read => IORING_OP_READ,
+                IoOp::Write => IORING_OP_WRITE,
+            },
+            fd: self.file.raw_fd(),
+            off: request.offset,
+            addr: slot.buffer.as_mut_ptr() as u64,
+            len: self.block_size as u32,
+            user_data: index as u64,
+            ..Sqe::default()
+        };
+        if !self.ring.push(sqe) {
+            self.free.push(index);
+            return Err(io::Error::other("io_uring submission queue full"));
+        }
+
+        slot.request = Some((request, Instant::now()));
+        self.unsubmitted += 1;
+        Ok(())
+    }
+
+    fn reap(&mut self, min_complete: usize, completions: &mut Vec<IoCompletion>) -> io::Result<()> {
+        let min_complete = min_complete.min(self.in_flight());
+        let mut reaped = 0;
+        loop {
+            while let Some(cqe) = self.ring.pop() {
+                completions.push(self.complete(cqe)?);
+                reaped += 1;
+            }
+            if reaped >= min_complete && self.unsubmitted == 0 {
+                return Ok(());
+            }
+
+            let wait = min_complete.saturating_sub(reaped) as u32;
+            match self.ring.enter(self.unsubmitted, wait) {
+                Ok(submitted) => self.unsubmitted -= submitted.min(self.unsubmitted),
+                Err(e)
+                    if matches!(
+                        e.raw_os_error(),
+                        Some(libc::EINTR | libc::EAGAIN | libc::EBUSY)
+                    ) => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    fn sync(&mut self) -> io::Result<()> {
+        let mut completions = Vec::new();
+        self.reap(self.in_flight(), &mut completions)?;
+        self.file.sync_all()
+    }
+
+    fn busy_time(&self) -> Duration {
+        self.busy
+    }
+
+    fn completed_ops(&self) -> u64 {
+        self.completed_ops
+    }
+}
+
+impl Drop for IoUringEngine {
+    fn drop(&mut self) {
+        // The kernel may still write into slot buffers, so wait for outstanding requests
+        let mut completions = Vec::new();
+        while self.in_flight() > 0 {
+            let before = self.in_flight();
+            if self.reap(1, &mut completions).is_err() && self.in_flight() == before {
+                // Requests the kernel still owns may write into their buffers
+                // after the ring is gone, so leak the buffers instead of freeing them
+                std::mem::forget(std::mem::take(&mut self.slots));
+                self.free.clear();
+                break;
+            }
+            completions.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::buffer::MIN_ALIGNMENT;
+    use crate::io::engine::{create_engine, test_support};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_struct_layouts() {
+        assert_eq!(std::mem::size_of::<Params>(), 120);
+        assert_eq!(std::mem::size_of::<Sqe>(), 64);
+        assert_eq!(std::mem::size_of::<Cqe>(), 16);
+        assert_eq!(std::mem::size_of::<ProbeOp>(), 8);
+        assert_eq!(std::mem::size_of::<Probe>(), 16 + 8 * PROBE_OPS);
+    }
+
+    #[test]
+    fn test_probe_opcodes() {
+        let ring = match Ring::new(4) {
+            Ok(ring) => ring,
+            Err(e) => {
+                eprintln!("Skipping io_uring probe test, setup failed: {}", e);
+                return;
+            }
+        };
+        // Setup succeeding but the probe failing means a pre-5.6 kernel
+        if !ring.supports(&[IORING_OP_READ, IORING_OP_WRITE]) {
+            eprintln!("Skipping io_uring probe test, read/write unsupported");
+            return;
+        }
+        assert!(ring.supports(&[]));
+        assert!(!ring.supports(&[IORING_OP_READ, u8::MAX]));
+    }
+
+    #[test]
+    fn test_io_uring_round_trip() {
+        let temp_dir = tempdir().unwrap();
+        let file = test_support::open_buffered(&temp_dir.path().join("uring.dat"));
+
+        let mut engine = match IoUringEngine::new(file, 4096, 8, MIN_ALIGNMENT) {
+            Ok(engine) => engine,
+            Err((_, e)) => {
+                eprintln!("Skipping io_uring test, setup failed: {}", e);
+                return;
+            }
+        };
+
+        // Requests stay in flight until reaped
+        for block in 0..8 {
+            engine
+                .submit(IoRequest {
+                    op: IoOp::Write,
+                    offset: block * 4096,
+                })
+                .unwrap();
+        }
+        assert_eq!(engine.in_flight(), 8);
+        assert!(engine
+            .submit(IoRequest {
+                op: IoOp::Write,
+                offset: 0
+            })
+            .is_err());
+        let mut completions = Vec::new();
+        engine.reap(8, &mut completions).unwrap();
+        assert_eq!(completions.len(), 8);
+        assert_eq!(engine.in_flight(), 0);
+        assert_eq!(engine.completed_ops(), 8);
+
+        test_support::exercise_engine(&mut engine, 64, 4096);
+    }
+
+    #[test]
+    fn test_create_engine_io_uring_or_fallback() {
+        let temp_dir = tempdir().unwrap();
+        let file = test_support::open_buffered(&temp_dir.path().join("engine.dat"));
+
+        let mut engine =
+            create_engine(IoEngineKind::IoUring, file, 4096, 4, MIN_ALIGNMENT).unwrap();
+        match engine.kind() {
+            IoEngineKind::IoUring => {
+                assert_eq!(engine.queue_depth(), 4);
+                assert_eq!(engine.fallback_reason(), None);
+            }
+            _ => {
+                assert_eq!(engine.queue_depth(), 1);
+                assert!(engine.fallback_reason().unwrap().contains("io_uring"));
+            }
+        }
+        test_support::exercise_engine(engine.as_mut(), 32, 4096);
+    }
+}
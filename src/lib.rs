@@ -6,7 +6,9 @@
 use std::fmt;
 
 // Public re-exports
+pub mod app;
 pub mod bench;
+pub mod cli;
 pub mod config;
 pub mod io;
 pub mod models;
@@ -1,10 +1,34 @@
+use diorb::cli::{self, Command};
 use diorb::config::BenchmarkConfig;
 use diorb::simple::{ask_config, detect_disks, run_speedtest};
 use diorb::Result;
 use std::path::PathBuf;
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match cli::parse_args(&args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("Run 'diorb --help' for usage.");
+            std::process::exit(2);
+        }
+    };
+
+    let outcome = match command {
+        Command::Interactive => interactive().await,
+        command => cli::execute(command).await,
+    };
+
+    if let Err(e) = outcome {
+        eprintln!("{}", diorb::error::user_friendly_message(&e));
+        std::process::exit(1);
+    }
+}
+
+/// Prompt-driven flow used when no arguments are given
+async fn interactive() -> Result<()> {
     // Detect disks and ask user to choose one
     let disks = detect_disks();
     println!("Available disks:");
@@ -0,0 +1,307 @@
+//! Streaming latency histogram
+//!
+//! Records latencies into log-linear buckets (in the style of HdrHistogram)
+//! so a benchmark can observe millions of operations in fixed memory.
+//! Every power-of-two range of nanoseconds is split into `SUB_BUCKETS`
+//! equal buckets, which bounds the relative error of any reported value
+//! to `1 / (2 * SUB_BUCKETS)`. Histograms from several workers merge
+//! exactly by adding bucket counts.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Bits of precision kept for every value
+const SUB_BUCKET_BITS: u32 = 7;
+
+/// Buckets per power-of-two range
+const SUB_BUCKETS: usize = 1 << SUB_BUCKET_BITS;
+
+/// Buckets needed to cover every `u64` nanosecond value
+const BUCKET_COUNT: usize = (64 - SUB_BUCKET_BITS as usize + 1) * SUB_BUCKETS;
+
+/// Fixed-size, mergeable histogram of operation latencies
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "SparseHistogram", into = "SparseHistogram")]
+pub struct LatencyHistogram {
+    counts: Vec<u64>,
+    count: u64,
+    min_nanos: u64,
+    max_nanos: u64,
+    sum_nanos: u128,
+}
+
+impl LatencyHistogram {
+    /// Create an empty histogram
+    pub fn new() -> Self {
+        Self {
+            counts: vec![0; BUCKET_COUNT],
+            count: 0,
+            min_nanos: u64::MAX,
+            max_nanos: 0,
+            sum_nanos: 0,
+        }
+    }
+
+    /// Record one operation's latency
+    pub fn record(&mut self, latency: Duration) {
+        let nanos = u64::try_from(latency.as_nanos()).unwrap_or(u64::MAX);
+        self.counts[bucket_index(nanos)] += 1;
+        self.count += 1;
+        self.min_nanos = self.min_nanos.min(nanos);
+        self.max_nanos = self.max_nanos.max(nanos);
+        self.sum_nanos += nanos as u128;
+    }
+
+    /// Add every observation from another histogram
+    pub fn merge(&mut self, other: &LatencyHistogram) {
+        for (count, other_count) in self.counts.iter_mut().zip(&other.counts) {
+            *count += other_count;
+        }
+        self.count += other.count;
+        self.min_nanos = self.min_nanos.min(other.min_nanos);
+        self.max_nanos = self.max_nanos.max(other.max_nanos);
+        self.sum_nanos += other.sum_nanos;
+    }
+
+    /// Number of recorded operations
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Check if nothing has been recorded
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Smallest recorded latency (exact)
+    pub fn min(&self) -> Duration {
+        if self.is_empty() {
+            Duration::ZERO
+        } else {
+            Duration::from_nanos(self.min_nanos)
+        }
+    }
+
+    /// Largest recorded latency (exact)
+    pub fn max(&self) -> Duration {
+        Duration::from_nanos(self.max_nanos)
+    }
+
+    /// Mean latency (exact)
+    pub fn mean(&self) -> Duration {
+        if self.is_empty() {
+            Duration::ZERO
+        } else {
+            Duration::from_nanos((self.sum_nanos / self.count as u128) as u64)
+        }
+    }
+
+    /// Latency below which `percentile` percent of operations completed
+    ///
+    /// Apart from the exact min and max, the result is the midpoint of the
+    /// bucket holding that rank, so it is within `1 / (2 * SUB_BUCKETS)` of
+    /// the true value.
+    pub fn value_at_percentile(&self, percentile: f64) -> Duration {
+        if self.is_empty() {
+            return Duration::ZERO;
+        }
+        let fraction = (percentile / 100.0).clamp(0.0, 1.0);
+        let rank = ((fraction * self.count as f64).ceil() as u64).clamp(1, self.count);
+        // The extremes are tracked exactly
+        if rank == 1 {
+            return self.min();
+        }
+        if rank == self.count {
+            return self.max();
+        }
+
+        let mut seen = 0u64;
+        for (index, &count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                let (lower, width) = bucket_range(index);
+                let midpoint = lower + (width - 1) / 2;
+                return Duration::from_nanos(midpoint.clamp(self.min_nanos, self.max_nanos));
+            }
+        }
+        self.max()
+    }
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Bucket holding a nanosecond value
+fn bucket_index(nanos: u64) -> usize {
+    if nanos < SUB_BUCKETS as u64 {
+        return nanos as usize;
+    }
+    // Keep the leading SUB_BUCKET_BITS + 1 bits; the top one selects the range
+    let exponent = 63 - nanos.leading_zeros();
+    let shift = exponent - SUB_BUCKET_BITS;
+    let group = (shift + 1) as usize;
+    let sub = (nanos >> shift) as usize - SUB_BUCKETS;
+    group * SUB_BUCKETS + sub
+}
+
+/// Lowest value and width in nanoseconds of a bucket
+fn bucket_range(index: usize) -> (u64, u64) {
+    let group = index / SUB_BUCKETS;
+    let sub = (index % SUB_BUCKETS) as u64;
+    if group == 0 {
+        (sub, 1)
+    } else {
+        let shift = group as u32 - 1;
+        ((SUB_BUCKETS as u64 + sub) << shift, 1 << shift)
+    }
+}
+
+/// Serialized form listing only non-empty buckets as `[index, count]` pairs
+#[derive(Serialize, Deserialize)]
+struct SparseHistogram {
+    buckets: Vec<(usize, u64)>,
+    min_nanos: u64,
+    max_nanos: u64,
+    sum_nanos: u128,
+}
+
+impl From<LatencyHistogram> for SparseHistogram {
+    fn from(histogram: LatencyHistogram) -> Self {
+        Self {
+            buckets: histogram
+                .counts
+                .iter()
+                .enumerate()
+                .filter(|(_, &count)| count > 0)
+                .map(|(index, &count)| (index, count))
+                .collect(),
+            min_nanos: histogram.min_nanos,
+            max_nanos: histogram.max_nanos,
+            sum_nanos: histogram.sum_nanos,
+        }
+    }
+}
+
+impl From<SparseHistogram> for LatencyHistogram {
+    fn from(sparse: SparseHistogram) -> Self {
+        let mut histogram = LatencyHistogram::new();
+        for (index, count) in sparse.buckets {
+            if let Some(slot) = histogram.counts.get_mut(index) {
+                *slot += count;
+                histogram.count += count;
+            }
+        }
+        histogram.min_nanos = sparse.min_nanos;
+        histogram.max_nanos = sparse.max_nanos;
+        histogram.sum_nanos = sparse.sum_nanos;
+        histogram
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_layout_is_contiguous() {
+        let mut expected_lower = 0u64;
+        for index in 0..BUCKET_COUNT {
+            let (lower, width) = bucket_range(index);
+            assert_eq!(lower, expected_lower, "gap before bucket {}", index);
+            assert_eq!(bucket_index(lower), index);
+            assert_eq!(bucket_index(lower + (width - 1)), index);
+            expected_lower = lower.wrapping_add(width);
+        }
+        assert_eq!(bucket_index(u64::MAX), BUCKET_COUNT - 1);
+    }
+
+    #[test]
+    fn test_relative_error_is_bounded() {
+        let max_error = 1.0 / (2 * SUB_BUCKETS) as f64;
+        for nanos in [
+            1u64,
+            127,
+            128,
+            1_000,
+            4_567,
+            99_999,
+            1_234_567,
+            987_654_321_000,
+        ] {
+            let mut histogram = LatencyHistogram::new();
+            histogram.record(Duration::from_nanos(1));
+            histogram.record(Duration::from_nanos(nanos));
+            histogram.record(Duration::from_nanos(u64::MAX / 2));
+            let reported = histogram.value_at_percentile(50.0).as_nanos() as f64;
+            let error = (reported - nanos as f64).abs() / nanos as f64;
+            assert!(error <= max_error, "{} reported as {}", nanos, reported);
+        }
+    }
+
+    #[test]
+    fn test_percentiles_match_sorted_samples() {
+        let mut histogram = LatencyHistogram::new();
+        for micros in 1..=10_000u64 {
+            histogram.record(Duration::from_micros(micros));
+        }
+        assert_eq!(histogram.count(), 10_000);
+        assert_eq!(histogram.min(), Duration::from_micros(1));
+        assert_eq!(histogram.max(), Duration::from_micros(10_000));
+        assert_eq!(histogram.mean(), Duration::from_nanos(5_000_500));
+
+        for (percentile, expected_micros) in [(50.0, 5_000.0), (99.0, 9_900.0), (99.9, 9_990.0)] {
+            let value = histogram.value_at_percentile(percentile).as_secs_f64() * 1e6;
+            assert!((value - expected_micros).abs() / expected_micros < 0.005);
+        }
+        assert_eq!(histogram.value_at_percentile(100.0), histogram.max());
+        assert_eq!(histogram.value_at_percentile(0.0), histogram.min());
+    }
+
+    #[test]
+    fn test_merge_equals_combined_recording() {
+        let mut a = LatencyHistogram::new();
+        let mut b = LatencyHistogram::new();
+        let mut all = LatencyHistogram::new();
+        for i in 0..1_000u64 {
+            let latency = Duration::from_nanos(i * i + 100);
+            if i % 3 == 0 {
+                a.record(latency)
+            } else {
+                b.record(latency)
+            }
+            all.record(latency);
+        }
+        a.merge(&b);
+        assert_eq!(a, all);
+
+        // Merging an empty histogram changes nothing
+        a.merge(&LatencyHistogram::new());
+        assert_eq!(a, all);
+    }
+
+    #[test]
+    fn test_empty_histogram() {
+        let histogram = LatencyHistogram::new();
+        assert!(histogram.is_empty());
+        assert_eq!(histogram.min(), Duration::ZERO);
+        assert_eq!(histogram.max(), Duration::ZERO);
+        assert_eq!(histogram.mean(), Duration::ZERO);
+        assert_eq!(histogram.value_at_percentile(99.0), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_serde_round_trip_is_sparse() {
+        let mut histogram = LatencyHistogram::new();
+        histogram.record(Duration::from_micros(250));
+        histogram.record(Duration::from_millis(3));
+        histogram.record(Duration::from_millis(3));
+
+        let json = serde_json::to_string(&histogram).unwrap();
+        assert!(json.len() < 200);
+        let deserialized: LatencyHistogram = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, histogram);
+    }
+}
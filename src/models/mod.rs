@@ -3,12 +3,29 @@
 //! Contains benchmark configuration structures, result data models,
 //! and performance metrics definitions.
 
+pub mod histogram;
+pub mod open_loop;
+pub mod percentile;
+pub mod repetition;
 pub mod result;
+pub mod saturation;
+pub mod steady_state;
+pub mod sweep;
+pub mod timeline;
 
 // Re-export commonly used types
+pub use histogram::LatencyHistogram;
+pub use open_loop::OpenLoopSummary;
+pub use percentile::Percentile;
+pub use repetition::{RepetitionSummary, SummaryStats};
+pub use saturation::{Knee, KneeReason, SaturationResult, SaturationStep};
+pub use steady_state::{SteadyState, SteadyStateDetector};
+pub use sweep::{SweepEntry, SweepMatrix, SweepMetric, SweepResult};
+pub use timeline::{Timeline, TimelineRecorder, TimelineSample};
 pub use result::{
     BenchmarkResult,
     PerformanceMetrics,
+    DirectionMetrics,
     LatencyStats,
     SystemInfo,
     StorageInfo,
@@ -0,0 +1,181 @@
+//! Outcome of rate-limited runs
+//!
+//! In a rate-limited (open-loop) run, latency counts from when each request
+//! was due rather than from when it was issued, so the main latency figures
+//! already include any time requests waited behind a slow device. An
+//! `OpenLoopSummary` keeps the target rate, how far issuing fell behind the
+//! schedule and the uncorrected service latency for comparison.
+
+use crate::config::ArrivalProcess;
+use crate::models::result::duration_serde;
+use crate::models::{LatencyHistogram, LatencyStats, Percentile};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Target load and schedule adherence of a rate-limited run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenLoopSummary {
+    /// Target operations per second, summed over workers
+    pub target_iops: f64,
+    /// How requests were spaced
+    pub arrivals: ArrivalProcess,
+    /// Requests issued
+    pub issued: u64,
+    /// Total time requests were issued after they were due, for merging across workers
+    #[serde(default, with = "duration_serde")]
+    pub total_issue_delay: Duration,
+    /// Average time requests were issued after they were due
+    #[serde(with = "duration_serde")]
+    pub mean_issue_delay: Duration,
+    /// Longest time a request was issued after it was due
+    #[serde(with = "duration_serde")]
+    pub max_issue_delay: Duration,
+    /// Latency from the actual issue time, without the coordinated-omission correction
+    pub service_latency: LatencyStats,
+    /// Full distribution of `service_latency`, for merging across workers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service_histogram: Option<LatencyHistogram>,
+}
+
+impl OpenLoopSummary {
+    /// Summarise a worker's run from its issue delays and service latencies
+    pub fn new(
+        target_iops: f64,
+        arrivals: ArrivalProcess,
+        total_issue_delay: Duration,
+        max_issue_delay: Duration,
+        service_histogram: LatencyHistogram,
+        percentiles: &[Percentile],
+    ) -> Self {
+        let issued = service_histogram.count();
+        Self {
+            target_iops,
+            arrivals,
+            issued,
+            total_issue_delay,
+            mean_issue_delay: mean(total_issue_delay, issued),
+            max_issue_delay,
+            service_latency: LatencyStats::from_histogram(&service_histogram, percentiles),
+            service_histogram: Some(service_histogram),
+        }
+    }
+
+    /// Achieved IOPS as a fraction of the target
+    ///
+    /// Well below 1.0 means the device could not sustain the target and the
+    /// corrected latencies mostly measure the backlog.
+    pub fn achieved_fraction(&self, achieved_iops: f64) -> f64 {
+        if self.target_iops > 0.0 {
+            achieved_iops / self.target_iops
+        } else {
+            0.0
+        }
+    }
+
+    /// Combine the summaries of workers running side by side
+    ///
+    /// Target rates add up like the workers' IOPS do; service latencies
+    /// merge through their histograms.
+    pub fn combine<'a>(
+        summaries: impl IntoIterator<Item = &'a OpenLoopSummary>,
+        percentiles: &[Percentile],
+    ) -> Option<OpenLoopSummary> {
+        let mut combined: Option<OpenLoopSummary> = None;
+        let mut merged = LatencyHistogram::new();
+        for summary in summaries {
+            if let Some(histogram) = &summary.service_histogram {
+                merged.merge(histogram);
+            }
+            let Some(combined) = combined.as_mut() else {
+                combined = Some(summary.clone());
+                continue;
+            };
+            combined.target_iops += summary.target_iops;
+            combined.issued += summary.issued;
+            combined.total_issue_delay += summary.total_issue_delay;
+            combined.max_issue_delay = combined.max_issue_delay.max(summary.max_issue_delay);
+        }
+        let mut combined = combined?;
+        combined.mean_issue_delay = mean(combined.total_issue_delay, combined.issued);
+        if !merged.is_empty() {
+            combined.service_latency = LatencyStats::from_histogram(&merged, percentiles);
+            combined.service_histogram = Some(merged);
+        }
+        Some(combined)
+    }
+
+    /// Drop the service latency histogram, keeping the summary statistics
+    pub fn without_histograms(mut self) -> Self {
+        self.service_histogram = None;
+        self
+    }
+}
+
+fn mean(total: Duration, count: u64) -> Duration {
+    if count == 0 {
+        Duration::ZERO
+    } else {
+        Duration::from_nanos((total.as_nanos() / count as u128) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(target_iops: f64, delays_us: &[u64], service_us: &[u64]) -> OpenLoopSummary {
+        let mut histogram = LatencyHistogram::new();
+        for &us in service_us {
+            histogram.record(Duration::from_micros(us));
+        }
+        let total: u64 = delays_us.iter().sum();
+        OpenLoopSummary::new(
+            target_iops,
+            ArrivalProcess::Fixed,
+            Duration::from_micros(total),
+            Duration::from_micros(delays_us.iter().copied().max().unwrap_or(0)),
+            histogram,
+            &Percentile::DEFAULTS,
+        )
+    }
+
+    #[test]
+    fn test_summary() {
+        let summary = summary(1000.0, &[0, 10, 50], &[100, 100, 400]);
+        assert_eq!(summary.issued, 3);
+        assert_eq!(summary.mean_issue_delay, Duration::from_micros(20));
+        assert_eq!(summary.max_issue_delay, Duration::from_micros(50));
+        assert_eq!(summary.service_latency.avg, Duration::from_micros(200));
+        assert!((summary.achieved_fraction(900.0) - 0.9).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_combine_workers() {
+        let a = summary(1000.0, &[0, 20], &[100, 100]);
+        let b = summary(500.0, &[100, 100], &[300, 300]);
+        let combined = OpenLoopSummary::combine([&a, &b], &Percentile::DEFAULTS).unwrap();
+        assert_eq!(combined.target_iops, 1500.0);
+        assert_eq!(combined.issued, 4);
+        assert_eq!(combined.mean_issue_delay, Duration::from_micros(55));
+        assert_eq!(combined.max_issue_delay, Duration::from_micros(100));
+        assert_eq!(combined.service_latency.avg, Duration::from_micros(200));
+        assert!(OpenLoopSummary::combine([], &Percentile::DEFAULTS).is_none());
+
+        // Means that do not divide evenly still combine exactly
+        let a = summary(1000.0, &[0, 0, 1], &[100, 100, 100]);
+        let b = summary(1000.0, &[0], &[100]);
+        let combined = OpenLoopSummary::combine([&a, &b], &Percentile::DEFAULTS).unwrap();
+        assert_eq!(combined.total_issue_delay, Duration::from_micros(1));
+        assert_eq!(combined.mean_issue_delay, Duration::from_nanos(250));
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let summary = summary(1000.0, &[5], &[100]).without_histograms();
+        let json = serde_json::to_string(&summary).unwrap();
+        let deserialized: OpenLoopSummary = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.issued, 1);
+        assert_eq!(deserialized.arrivals, ArrivalProcess::Fixed);
+        assert!(deserialized.service_histogram.is_none());
+    }
+}
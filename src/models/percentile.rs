@@ -0,0 +1,140 @@
+//! Latency percentile keys
+//!
+//! Percentiles are kept as a fixed-point count of thousandths of a percent,
+//! so tail values such as p99.9 and p99.99 can key ordered maps exactly
+//! instead of being rounded to whole percents.
+
+use crate::{DIOrbError, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Fixed-point steps per percent
+const SCALE: u32 = 1000;
+
+/// A latency percentile between 0 and 100, with up to three decimal places
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Percentile(u32);
+
+impl Percentile {
+    /// Median
+    pub const P50: Percentile = Percentile(50 * SCALE);
+    /// 95th percentile
+    pub const P95: Percentile = Percentile(95 * SCALE);
+    /// 99th percentile
+    pub const P99: Percentile = Percentile(99 * SCALE);
+    /// 99.9th percentile
+    pub const P99_9: Percentile = Percentile(99_900);
+    /// 99.99th percentile
+    pub const P99_99: Percentile = Percentile(99_990);
+
+    /// Percentiles reported when none are configured
+    pub const DEFAULTS: [Percentile; 5] =
+        [Self::P50, Self::P95, Self::P99, Self::P99_9, Self::P99_99];
+
+    /// Create a percentile from a value between 0 and 100
+    ///
+    /// Values are rounded to the nearest thousandth of a percent.
+    pub fn new(value: f64) -> Result<Self> {
+        if !(0.0..=100.0).contains(&value) {
+            return Err(DIOrbError::ConfigError(format!(
+                "Percentile must be between 0 and 100, got {}",
+                value
+            )));
+        }
+        Ok(Self((value * SCALE as f64).round() as u32))
+    }
+
+    /// Percentile as a value between 0 and 100
+    pub fn value(self) -> f64 {
+        self.0 as f64 / SCALE as f64
+    }
+
+    /// Short label such as `p99.9`
+    pub fn label(self) -> String {
+        format!("p{}", self)
+    }
+}
+
+impl fmt::Display for Percentile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let whole = self.0 / SCALE;
+        let fraction = self.0 % SCALE;
+        if fraction == 0 {
+            write!(f, "{}", whole)
+        } else {
+            let digits = format!("{:03}", fraction);
+            write!(f, "{}.{}", whole, digits.trim_end_matches('0'))
+        }
+    }
+}
+
+impl FromStr for Percentile {
+    type Err = DIOrbError;
+
+    /// Parse `99.9`, `p99.9` or `P99.9`
+    fn from_str(s: &str) -> Result<Self> {
+        let trimmed = s.trim();
+        let number = trimmed
+            .strip_prefix('p')
+            .or_else(|| trimmed.strip_prefix('P'))
+            .unwrap_or(trimmed);
+        let value: f64 = number
+            .parse()
+            .map_err(|_| DIOrbError::ConfigError(format!("Invalid percentile: {}", s)))?;
+        Self::new(value)
+    }
+}
+
+impl Serialize for Percentile {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        self.value().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Percentile {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let value = f64::deserialize(deserializer)?;
+        Self::new(value).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display() {
+        for (input, expected) in [
+            ("50", "50"),
+            ("99.9", "99.9"),
+            ("p99.99", "99.99"),
+            ("P99.999", "99.999"),
+            ("0", "0"),
+            ("100", "100"),
+        ] {
+            assert_eq!(input.parse::<Percentile>().unwrap().to_string(), expected);
+        }
+        assert_eq!(Percentile::P99_9.label(), "p99.9");
+        assert!("101".parse::<Percentile>().is_err());
+        assert!("-1".parse::<Percentile>().is_err());
+        assert!("p".parse::<Percentile>().is_err());
+    }
+
+    #[test]
+    fn test_ordering_distinguishes_tail() {
+        assert!(Percentile::P99 < Percentile::P99_9);
+        assert!(Percentile::P99_9 < Percentile::P99_99);
+        assert_eq!(Percentile::new(99.9).unwrap(), Percentile::P99_9);
+        assert_eq!(Percentile::P99_99.value(), 99.99);
+    }
+
+    #[test]
+    fn test_serde_as_number() {
+        let json = serde_json::to_string(&Percentile::DEFAULTS).unwrap();
+        assert_eq!(json, "[50.0,95.0,99.0,99.9,99.99]");
+        let parsed: Vec<Percentile> = serde_json::from_str("[50, 99.9]").unwrap();
+        assert_eq!(parsed, vec![Percentile::P50, Percentile::P99_9]);
+        assert!(serde_json::from_str::<Percentile>("150").is_err());
+    }
+}
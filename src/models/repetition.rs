@@ -0,0 +1,208 @@
+//! Statistics over repeated runs
+//!
+//! A single run says little about repeatability. When a configuration is run
+//! several times, the runs are grouped into one result whose
+//! `RepetitionSummary` holds each run's metrics and the spread of throughput,
+//! IOPS and latency across them.
+
+use crate::models::PerformanceMetrics;
+use serde::{Deserialize, Serialize};
+
+/// Two-sided 95% Student's t critical values for 1 to 30 degrees of freedom
+const T_95: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+    2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+    2.052, 2.048, 2.045, 2.042,
+];
+
+/// Normal approximation used beyond the table
+const Z_95: f64 = 1.96;
+
+/// Mean, spread and 95% confidence interval of one metric over several runs
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SummaryStats {
+    /// Arithmetic mean
+    pub mean: f64,
+    /// Sample standard deviation (zero for a single run)
+    pub std_dev: f64,
+    /// Coefficient of variation, the standard deviation relative to the mean
+    pub cv: f64,
+    /// Smallest value
+    pub min: f64,
+    /// Largest value
+    pub max: f64,
+    /// Lower bound of the 95% confidence interval for the mean
+    pub ci95_low: f64,
+    /// Upper bound of the 95% confidence interval for the mean
+    pub ci95_high: f64,
+}
+
+/// Repeated runs of one configuration, grouped into a single result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepetitionSummary {
+    /// Metrics of each run in order, without latency histograms
+    pub runs: Vec<PerformanceMetrics>,
+    /// Throughput in MB/s across runs
+    pub throughput_mbps: SummaryStats,
+    /// IOPS across runs
+    pub iops: SummaryStats,
+    /// Average latency in microseconds across runs
+    pub latency_avg_us: SummaryStats,
+    /// 99th percentile latency in microseconds across runs
+    pub latency_p99_us: SummaryStats,
+    /// Throughput coefficient of variation the runs were repeated until, if any
+    #[serde(default)]
+    pub target_cv: Option<f64>,
+    /// Whether throughput stayed within the accuracy threshold for its storage class
+    pub meets_accuracy: bool,
+}
+
+impl SummaryStats {
+    /// Summarise a set of values, or `None` if there are none
+    pub fn from_values(values: &[f64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        let n = values.len();
+        let mean = values.iter().sum::<f64>() / n as f64;
+        let std_dev = if n > 1 {
+            let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+            variance.sqrt()
+        } else {
+            0.0
+        };
+        let cv = if mean > 0.0 { std_dev / mean } else { 0.0 };
+        let margin = if n > 1 {
+            let t = T_95.get(n - 2).copied().unwrap_or(Z_95);
+            t * std_dev / (n as f64).sqrt()
+        } else {
+            0.0
+        };
+        Some(Self {
+            mean,
+            std_dev,
+            cv,
+            min: values.iter().copied().fold(f64::INFINITY, f64::min),
+            max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            ci95_low: mean - margin,
+            ci95_high: mean + margin,
+        })
+    }
+
+    /// Half the width of the 95% confidence interval
+    pub fn ci95_margin(&self) -> f64 {
+        (self.ci95_high - self.ci95_low) / 2.0
+    }
+}
+
+impl RepetitionSummary {
+    /// Summarise the metrics of repeated runs
+    ///
+    /// Returns `None` if there are no runs. Latency histograms are dropped
+    /// from the stored runs, since the grouped result merges them.
+    pub fn from_runs(
+        runs: Vec<PerformanceMetrics>,
+        target_cv: Option<f64>,
+        meets_accuracy: bool,
+    ) -> Option<Self> {
+        let stats = |metric: fn(&PerformanceMetrics) -> f64| {
+            let values: Vec<f64> = runs.iter().map(metric).collect();
+            SummaryStats::from_values(&values)
+        };
+        let throughput_mbps = stats(|m| m.throughput_mbps)?;
+        let iops = stats(|m| m.iops)?;
+        let latency_avg_us = stats(|m| m.latency.avg.as_secs_f64() * 1e6)?;
+        let latency_p99_us = stats(|m| m.latency.p99().as_secs_f64() * 1e6)?;
+        Some(Self {
+            runs: runs
+                .into_iter()
+                .map(PerformanceMetrics::without_histograms)
+                .collect(),
+            throughput_mbps,
+            iops,
+            latency_avg_us,
+            latency_p99_us,
+            target_cv,
+            meets_accuracy,
+        })
+    }
+
+    /// Whether the throughput coefficient of variation got below the target
+    ///
+    /// `None` when the runs were not repeated until a target.
+    pub fn converged(&self) -> Option<bool> {
+        self.target_cv
+            .map(|target| self.throughput_mbps.cv <= target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::LatencyStats;
+    use std::time::Duration;
+
+    fn run(throughput_mbps: f64) -> PerformanceMetrics {
+        PerformanceMetrics {
+            throughput_mbps,
+            iops: throughput_mbps * 256.0,
+            latency: LatencyStats::new(
+                Duration::from_micros(10),
+                Duration::from_micros(100),
+                Duration::from_micros(900),
+            ),
+            ..PerformanceMetrics::default()
+        }
+    }
+
+    #[test]
+    fn test_summary_stats() {
+        let stats = SummaryStats::from_values(&[98.0, 100.0, 102.0]).unwrap();
+        assert_eq!(stats.mean, 100.0);
+        assert_eq!(stats.std_dev, 2.0);
+        assert_eq!(stats.cv, 0.02);
+        assert_eq!((stats.min, stats.max), (98.0, 102.0));
+        // t(2) = 4.303, margin = 4.303 * 2 / sqrt(3)
+        assert!((stats.ci95_margin() - 4.969).abs() < 0.001);
+        assert!(stats.ci95_low < 98.0 && stats.ci95_high > 102.0);
+
+        assert!(SummaryStats::from_values(&[]).is_none());
+    }
+
+    #[test]
+    fn test_single_value_has_no_spread() {
+        let stats = SummaryStats::from_values(&[42.0]).unwrap();
+        assert_eq!(stats.std_dev, 0.0);
+        assert_eq!(stats.cv, 0.0);
+        assert_eq!((stats.ci95_low, stats.ci95_high), (42.0, 42.0));
+    }
+
+    #[test]
+    fn test_large_samples_use_normal_approximation() {
+        let values: Vec<f64> = (0..40)
+            .map(|i| if i % 2 == 0 { 99.0 } else { 101.0 })
+            .collect();
+        let stats = SummaryStats::from_values(&values).unwrap();
+        let expected = Z_95 * stats.std_dev / (40.0f64).sqrt();
+        assert!((stats.ci95_margin() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_repetition_summary() {
+        let summary =
+            RepetitionSummary::from_runs(vec![run(98.0), run(100.0), run(102.0)], Some(0.05), true)
+                .unwrap();
+        assert_eq!(summary.runs.len(), 3);
+        assert_eq!(summary.throughput_mbps.mean, 100.0);
+        assert_eq!(summary.iops.mean, 25_600.0);
+        assert_eq!(summary.latency_avg_us.mean, 100.0);
+        assert_eq!(summary.latency_p99_us.max, 900.0);
+        assert_eq!(summary.converged(), Some(true));
+
+        let json = serde_json::to_string(&summary).unwrap();
+        let deserialized: RepetitionSummary = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.throughput_mbps, summary.throughput_mbps);
+
+        assert!(RepetitionSummary::from_runs(Vec::new(), None, true).is_none());
+    }
+}
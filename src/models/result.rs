@@ -3,10 +3,17 @@
 //! Contains structures for storing and serializing benchmark results,
 //! performance metrics, and latency statistics.
 
-use crate::config::BenchmarkConfig;
+use crate::config::{BenchmarkConfig, QueueDepthSource};
+use crate::io::system::merge_mount_options;
+use crate::io::{DirectIoMode, FilesystemSpace, IOMetrics, KernelInfo, SystemRoots};
+use crate::util::units::format_bytes;
+use crate::models::{
+    LatencyHistogram, OpenLoopSummary, Percentile, RepetitionSummary, SteadyState, Timeline,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::path::Path;
 use std::time::Duration;
 
 /// Complete benchmark result containing configuration, metrics, and metadata
@@ -20,6 +27,30 @@ pub struct BenchmarkResult {
     pub metrics: PerformanceMetrics,
     /// System information at time of benchmark
     pub system_info: SystemInfo,
+    /// How the measured I/O reached the device (`None` for results saved before this was recorded)
+    #[serde(default)]
+    pub direct_io_mode: Option<DirectIoMode>,
+    /// Why the configured engine was replaced by the one in `config`, after a fallback
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engine_fallback: Option<String>,
+    /// Where the queue depth in `config` came from, for engines that keep several requests in flight
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue_depth_source: Option<QueueDepthSource>,
+    /// Metrics of each worker when several ran side by side
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub worker_metrics: Vec<PerformanceMetrics>,
+    /// Throughput and latency per sampling interval, when sampling was enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeline: Option<Timeline>,
+    /// Outcome of steady-state detection, for runs that waited for steady state
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub steady_state: Option<SteadyState>,
+    /// Statistics over the runs grouped into this result, when the configuration was repeated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repetitions: Option<RepetitionSummary>,
+    /// Target load and schedule adherence, for rate-limited runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_loop: Option<OpenLoopSummary>,
 }
 
 /// Performance metrics collected during benchmark execution
@@ -34,12 +65,45 @@ pub struct PerformanceMetrics {
     pub throughput_mbps: f64,
     /// Input/output operations per second
     pub iops: f64,
+    /// Completed I/O operations (0 for results saved before this was recorded)
+    #[serde(default)]
+    pub operations: u64,
     /// Latency statistics for I/O operations
     pub latency: LatencyStats,
+    /// Average number of requests actually in flight, when an I/O engine measured it
+    #[serde(default)]
+    pub achieved_queue_depth: Option<f64>,
+    /// Full latency distribution that `latency` was computed from, for merging across workers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_histogram: Option<LatencyHistogram>,
+    /// Read side of a random or mixed workload
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read: Option<DirectionMetrics>,
+    /// Write side of a random or mixed workload
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub write: Option<DirectionMetrics>,
+}
+
+/// Metrics for one direction (reads or writes) of a workload
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DirectionMetrics {
+    /// Bytes transferred in this direction
+    pub bytes_processed: u64,
+    /// Completed operations in this direction
+    pub operations: u64,
+    /// Throughput in megabytes per second over the whole run
+    pub throughput_mbps: f64,
+    /// Operations per second over the whole run
+    pub iops: f64,
+    /// Latency statistics for this direction
+    pub latency: LatencyStats,
+    /// Full latency distribution, for merging across workers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_histogram: Option<LatencyHistogram>,
 }
 
 /// Latency statistics with min/avg/max and percentiles
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct LatencyStats {
     /// Minimum latency observed
     #[serde(with = "duration_serde")]
@@ -50,9 +114,9 @@ pub struct LatencyStats {
     /// Maximum latency observed
     #[serde(with = "duration_serde")]
     pub max: Duration,
-    /// Latency percentiles (50th, 95th, 99th, etc.)
+    /// Latency percentiles (50th, 99th, 99.9th, etc.)
     #[serde(with = "percentiles_serde")]
-    pub percentiles: HashMap<u8, Duration>,
+    pub percentiles: BTreeMap<Percentile, Duration>,
 }
 
 /// System information captured at benchmark time
@@ -62,6 +126,9 @@ pub struct SystemInfo {
     pub os: String,
     /// CPU information
     pub cpu: String,
+    /// Number of logical CPUs
+    #[serde(default)]
+    pub cpu_count: usize,
     /// Total system memory in bytes
     pub memory_total: u64,
     /// Available system memory in bytes at benchmark time
@@ -81,16 +148,46 @@ pub struct StorageInfo {
     pub total_space: u64,
     /// Available storage space in bytes
     pub available_space: u64,
+    /// Options the filesystem is mounted with, e.g. `noatime` or `compress=zstd:3`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mount_options: Vec<String>,
+    /// Kind of device, when it could be detected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_type: Option<StorageType>,
+    /// Model string reported by the device
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Whether the device has spinning media
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rotational: Option<bool>,
+    /// Logical block size of the device in bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logical_block_size: Option<u64>,
+    /// Physical block size of the device in bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub physical_block_size: Option<u64>,
+    /// Active I/O scheduler of the device
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduler: Option<String>,
 }
 
 impl BenchmarkResult {
     /// Create a new benchmark result with detected system info
     pub fn new(config: BenchmarkConfig, metrics: PerformanceMetrics) -> Self {
+        let system_info = SystemInfo::detect_for_path(&config.disk_path);
         Self {
             timestamp: Utc::now(),
             config,
             metrics,
-            system_info: SystemInfo::detect(),
+            system_info,
+            direct_io_mode: None,
+            engine_fallback: None,
+            queue_depth_source: None,
+            worker_metrics: Vec::new(),
+            timeline: None,
+            steady_state: None,
+            repetitions: None,
+            open_loop: None,
         }
     }
 
@@ -105,19 +202,72 @@ impl BenchmarkResult {
             config,
             metrics,
             system_info,
+            direct_io_mode: None,
+            engine_fallback: None,
+            queue_depth_source: None,
+            worker_metrics: Vec::new(),
+            timeline: None,
+            steady_state: None,
+            repetitions: None,
+            open_loop: None,
         }
     }
 
+    /// Record how the measured I/O reached the device
+    pub fn with_direct_io_mode(mut self, mode: DirectIoMode) -> Self {
+        self.direct_io_mode = Some(mode);
+        self
+    }
+
+    /// Attach the per-interval samples of the run
+    pub fn with_timeline(mut self, timeline: Timeline) -> Self {
+        self.timeline = Some(timeline);
+        self
+    }
+
+    /// Attach the statistics over the runs grouped into this result
+    pub fn with_repetitions(mut self, repetitions: RepetitionSummary) -> Self {
+        self.repetitions = Some(repetitions);
+        self
+    }
+
+    /// Attach the outcome of steady-state detection
+    pub fn with_steady_state(mut self, steady_state: SteadyState) -> Self {
+        self.steady_state = Some(steady_state);
+        self
+    }
+
+    /// Attach the target load and schedule adherence of a rate-limited run
+    pub fn with_open_loop(mut self, open_loop: OpenLoopSummary) -> Self {
+        self.open_loop = Some(open_loop);
+        self
+    }
+
+    /// Check whether the measurement may have been served by the page cache
+    pub fn used_page_cache(&self) -> bool {
+        self.direct_io_mode
+            .is_some_and(|mode| mode != DirectIoMode::Direct)
+    }
+
     /// Get a human-readable summary of the benchmark result
     pub fn summary(&self) -> String {
-        format!(
+        let summary = format!(
             "{} - {} - {:.2} MB/s - {:.0} IOPS - {:.2}ms avg latency",
             self.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
             self.config.mode.description(),
             self.metrics.throughput_mbps,
             self.metrics.iops,
             self.metrics.latency.avg.as_secs_f64() * 1000.0
-        )
+        );
+        match &self.repetitions {
+            Some(repetitions) => format!(
+                "{} - {} runs, CV {:.1}%",
+                summary,
+                repetitions.runs.len(),
+                repetitions.throughput_mbps.cv * 100.0
+            ),
+            None => summary,
+        }
     }
 
     /// Check if this result meets accuracy requirements based on storage type
@@ -133,46 +283,139 @@ impl BenchmarkResult {
             .collect();
 
         let avg_throughput = throughputs.iter().sum::<f64>() / throughputs.len() as f64;
-        let max_deviation = throughputs
-            .iter()
-            .map(|&t| ((t - avg_throughput) / avg_throughput).abs())
-            .fold(0.0, f64::max);
-
-        // Determine accuracy threshold based on storage type (inferred from performance)
-        let accuracy_threshold = if avg_throughput > 1000.0 {
-            0.03 // NVMe: ±3%
-        } else if avg_throughput > 100.0 {
-            0.05 // SATA SSD: ±5%
-        } else {
-            0.08 // HDD: ±8%
+        let threshold = match self.system_info.storage_info.storage_type {
+            Some(storage_type) => storage_type.accuracy_threshold(),
+            None => accuracy_threshold(avg_throughput),
         };
+        max_relative_deviation(&throughputs) <= threshold
+    }
+
+    /// Storage type of the device the benchmark ran on
+    ///
+    /// Uses the detected device when known, otherwise infers it from the
+    /// measured throughput and latency.
+    pub fn storage_type(&self) -> StorageType {
+        self.system_info.storage_info.storage_type.unwrap_or_else(|| {
+            StorageType::infer_from_performance(
+                self.metrics.throughput_mbps,
+                self.metrics.latency.avg,
+            )
+        })
+    }
+}
 
-        max_deviation <= accuracy_threshold
+/// Allowed relative deviation between measurements of the same throughput
+///
+/// The storage type is inferred from the throughput itself, since faster
+/// devices are expected to give more repeatable results.
+pub fn accuracy_threshold(throughput_mbps: f64) -> f64 {
+    if throughput_mbps > 1000.0 {
+        0.03 // NVMe: ±3%
+    } else if throughput_mbps > 100.0 {
+        0.05 // SATA SSD: ±5%
+    } else {
+        0.08 // HDD: ±8%
     }
 }
 
+/// Largest deviation of any value from the mean, relative to the mean
+///
+/// Returns infinity when the mean is zero, so idle periods never look stable.
+pub fn max_relative_deviation(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    if mean <= 0.0 {
+        return f64::INFINITY;
+    }
+    values
+        .iter()
+        .map(|&v| ((v - mean) / mean).abs())
+        .fold(0.0, f64::max)
+}
+
 impl PerformanceMetrics {
     /// Create new performance metrics
-    pub fn new(bytes_processed: u64, elapsed_time: Duration, latency: LatencyStats) -> Self {
-        let elapsed_secs = elapsed_time.as_secs_f64();
-        let throughput_mbps = if elapsed_secs > 0.0 {
-            (bytes_processed as f64) / (1024.0 * 1024.0) / elapsed_secs
-        } else {
-            0.0
-        };
-
-        let iops = if elapsed_secs > 0.0 && !latency.avg.is_zero() {
-            1.0 / latency.avg.as_secs_f64()
-        } else {
-            0.0
-        };
+    ///
+    /// Throughput and IOPS are derived like `IOMetrics`: bytes and completed
+    /// operations over wall-clock time, whatever the concurrency.
+    pub fn new(
+        bytes_processed: u64,
+        elapsed_time: Duration,
+        operations: u64,
+        latency: LatencyStats,
+    ) -> Self {
+        let io_metrics = IOMetrics::new(bytes_processed, elapsed_time, operations);
 
         Self {
             bytes_processed,
             elapsed_time,
-            throughput_mbps,
-            iops,
+            throughput_mbps: io_metrics.throughput_mbps,
+            iops: io_metrics.iops,
+            operations,
             latency,
+            achieved_queue_depth: None,
+            latency_histogram: None,
+            read: None,
+            write: None,
+        }
+    }
+
+    /// Create performance metrics from a recorded latency distribution
+    ///
+    /// Every recorded latency is one completed operation.
+    pub fn from_histogram(
+        bytes_processed: u64,
+        elapsed_time: Duration,
+        histogram: LatencyHistogram,
+        percentiles: &[Percentile],
+    ) -> Self {
+        let latency = LatencyStats::from_histogram(&histogram, percentiles);
+        let operations = histogram.count();
+        Self {
+            latency_histogram: Some(histogram),
+            ..Self::new(bytes_processed, elapsed_time, operations, latency)
+        }
+    }
+
+    /// Create performance metrics for a workload mixing reads and writes
+    ///
+    /// The overall figures cover both directions; each direction is also
+    /// kept separately, as long as it saw at least one operation.
+    pub fn from_directions(
+        elapsed_time: Duration,
+        read: DirectionMetrics,
+        write: DirectionMetrics,
+        percentiles: &[Percentile],
+    ) -> Self {
+        let mut histogram = LatencyHistogram::new();
+        for direction in [&read, &write] {
+            if let Some(direction_histogram) = &direction.latency_histogram {
+                histogram.merge(direction_histogram);
+            }
+        }
+        let bytes_processed = read.bytes_processed + write.bytes_processed;
+        Self {
+            read: (read.operations > 0).then_some(read),
+            write: (write.operations > 0).then_some(write),
+            ..Self::from_histogram(bytes_processed, elapsed_time, histogram, percentiles)
+        }
+    }
+
+    /// The same metrics without latency histograms, for breakdowns kept next to a merged total
+    pub fn without_histograms(self) -> Self {
+        let strip = |direction: Option<DirectionMetrics>| {
+            direction.map(|d| DirectionMetrics {
+                latency_histogram: None,
+                ..d
+            })
+        };
+        Self {
+            latency_histogram: None,
+            read: strip(self.read),
+            write: strip(self.write),
+            ..self
         }
     }
 
@@ -209,23 +452,69 @@ impl Default for PerformanceMetrics {
             elapsed_time: Duration::default(),
             throughput_mbps: 0.0,
             iops: 0.0,
+            operations: 0,
             latency: LatencyStats {
                 min: Duration::default(),
                 avg: Duration::default(),
                 max: Duration::default(),
-                percentiles: HashMap::new(),
+                percentiles: BTreeMap::new(),
             },
+            achieved_queue_depth: None,
+            latency_histogram: None,
+            read: None,
+            write: None,
         }
     }
 }
 
+impl DirectionMetrics {
+    /// Create direction metrics from a recorded latency distribution
+    pub fn from_histogram(
+        bytes_processed: u64,
+        elapsed_time: Duration,
+        histogram: LatencyHistogram,
+        percentiles: &[Percentile],
+    ) -> Self {
+        let operations = histogram.count();
+        let io_metrics = IOMetrics::new(bytes_processed, elapsed_time, operations);
+
+        Self {
+            bytes_processed,
+            operations,
+            throughput_mbps: io_metrics.throughput_mbps,
+            iops: io_metrics.iops,
+            latency: LatencyStats::from_histogram(&histogram, percentiles),
+            latency_histogram: Some(histogram),
+        }
+    }
+
+    /// Combine the same direction from several workers running side by side
+    pub fn combine<'a>(
+        directions: impl IntoIterator<Item = &'a DirectionMetrics>,
+        elapsed_time: Duration,
+        percentiles: &[Percentile],
+    ) -> Option<Self> {
+        let mut bytes_processed = 0;
+        let mut histogram = LatencyHistogram::new();
+        let mut any = false;
+        for direction in directions {
+            any = true;
+            bytes_processed += direction.bytes_processed;
+            if let Some(direction_histogram) = &direction.latency_histogram {
+                histogram.merge(direction_histogram);
+            }
+        }
+        any.then(|| Self::from_histogram(bytes_processed, elapsed_time, histogram, percentiles))
+    }
+}
+
 impl LatencyStats {
     /// Create new latency statistics
     pub fn new(min: Duration, avg: Duration, max: Duration) -> Self {
-        let mut percentiles = HashMap::new();
-        percentiles.insert(50, avg); // Use avg as 50th percentile approximation
-        percentiles.insert(95, max); // Use max as 95th percentile approximation
-        percentiles.insert(99, max); // Use max as 99th percentile approximation
+        let mut percentiles = BTreeMap::new();
+        percentiles.insert(Percentile::P50, avg); // Use avg as 50th percentile approximation
+        percentiles.insert(Percentile::P95, max); // Use max as 95th percentile approximation
+        percentiles.insert(Percentile::P99, max); // Use max as 99th percentile approximation
 
         Self {
             min,
@@ -240,7 +529,7 @@ impl LatencyStats {
         min: Duration,
         avg: Duration,
         max: Duration,
-        percentiles: HashMap<u8, Duration>,
+        percentiles: BTreeMap<Percentile, Duration>,
     ) -> Self {
         Self {
             min,
@@ -272,14 +561,38 @@ impl LatencyStats {
         avg_within_range && spread_reasonable
     }
 
+    /// Get a percentile latency, if it was computed
+    pub fn percentile(&self, percentile: Percentile) -> Option<Duration> {
+        self.percentiles.get(&percentile).copied()
+    }
+
     /// Get the 95th percentile latency
     pub fn p95(&self) -> Duration {
-        self.percentiles.get(&95).copied().unwrap_or(self.max)
+        self.percentile(Percentile::P95).unwrap_or(self.max)
     }
 
     /// Get the 99th percentile latency
     pub fn p99(&self) -> Duration {
-        self.percentiles.get(&99).copied().unwrap_or(self.max)
+        self.percentile(Percentile::P99).unwrap_or(self.max)
+    }
+
+    /// Create latency statistics from a streaming histogram, computing the given percentiles
+    pub fn from_histogram(histogram: &LatencyHistogram, percentiles: &[Percentile]) -> Self {
+        if histogram.is_empty() {
+            return Self::default();
+        }
+
+        let percentiles = percentiles
+            .iter()
+            .map(|&p| (p, histogram.value_at_percentile(p.value())))
+            .collect();
+
+        Self {
+            min: histogram.min(),
+            avg: histogram.mean(),
+            max: histogram.max(),
+            percentiles,
+        }
     }
 
     /// Create latency statistics from a list of samples
@@ -296,10 +609,13 @@ impl LatencyStats {
             sorted.iter().map(|d| d.as_nanos()).sum::<u128>() / sorted.len() as u128;
         let avg = Duration::from_nanos(avg_nanos as u64);
 
-        let mut percentiles = HashMap::new();
-        percentiles.insert(50, sorted[sorted.len() * 50 / 100]);
-        percentiles.insert(95, sorted[sorted.len() * 95 / 100]);
-        percentiles.insert(99, sorted[sorted.len() * 99 / 100]);
+        let percentiles = Percentile::DEFAULTS
+            .iter()
+            .map(|&p| {
+                let index = (p.value() / 100.0 * sorted.len() as f64) as usize;
+                (p, sorted[index.min(sorted.len() - 1)])
+            })
+            .collect();
 
         Self {
             min,
@@ -310,33 +626,51 @@ impl LatencyStats {
     }
 }
 
-impl Default for LatencyStats {
+impl Default for SystemInfo {
     fn default() -> Self {
         Self {
-            min: Duration::default(),
-            avg: Duration::default(),
-            max: Duration::default(),
-            percentiles: HashMap::new(),
+            os: "Unknown".to_string(),
+            cpu: "Unknown CPU".to_string(),
+            cpu_count: 0,
+            memory_total: 0,
+            memory_available: 0,
+            storage_info: StorageInfo::detect_default(),
         }
     }
 }
 
-impl Default for SystemInfo {
-    fn default() -> Self {
+impl SystemInfo {
+    /// Create system info by detecting current system
+    pub fn detect() -> Self {
+        Self::detect_with(&SystemRoots::default())
+    }
+
+    /// Detect system info, reading procfs under `roots`
+    pub fn detect_with(roots: &SystemRoots) -> Self {
+        let cpu = roots.cpu_info().ok().flatten();
+        let memory = roots.memory_info().ok().flatten();
         Self {
             os: detect_os(),
-            cpu: detect_cpu(),
-            memory_total: detect_memory_total(),
-            memory_available: detect_memory_available(),
+            cpu: cpu
+                .as_ref()
+                .map_or_else(|| "Unknown CPU".to_string(), |cpu| cpu.model.clone()),
+            cpu_count: cpu.map_or_else(
+                || std::thread::available_parallelism().map_or(1, |count| count.get()),
+                |cpu| cpu.count,
+            ),
+            memory_total: memory.map_or(0, |memory| memory.total),
+            memory_available: memory.map_or(0, |memory| memory.available),
             storage_info: StorageInfo::detect_default(),
         }
     }
-}
 
-impl SystemInfo {
-    /// Create system info by detecting current system
-    pub fn detect() -> Self {
-        Self::default()
+    /// Create system info with storage info for the device holding `path`
+    pub fn detect_for_path(path: &Path) -> Self {
+        let roots = SystemRoots::default();
+        Self {
+            storage_info: StorageInfo::detect_with(&roots, path),
+            ..Self::detect_with(&roots)
+        }
     }
 }
 
@@ -348,18 +682,92 @@ impl StorageInfo {
             filesystem: "Unknown".to_string(),
             total_space: 0,
             available_space: 0,
+            ..Self::default()
         }
     }
 
     /// Detect storage info for a specific path
-    pub fn detect_for_path(_path: &std::path::Path) -> Self {
-        // TODO: Implement platform-specific storage detection
-        Self::detect_default()
+    pub fn detect_for_path(path: &Path) -> Self {
+        Self::detect_with(&SystemRoots::default(), path)
+    }
+
+    /// Detect storage info for a path, reading procfs and sysfs under `roots`
+    pub fn detect_with(roots: &SystemRoots, path: &Path) -> Self {
+        let mut info = Self::detect_default();
+        if let Ok(space) = FilesystemSpace::of(path) {
+            info.total_space = space.total;
+            info.available_space = space.available;
+        }
+        let Ok(Some(mount)) = roots.mount_for_path(path) else {
+            return info;
+        };
+        info.device = mount.source.clone();
+        info.filesystem = mount.fs_type.clone();
+        info.mount_options = merge_mount_options(&mount.mount_options, &mount.super_options);
+
+        if let Some(device) = roots.block_device(&mount) {
+            info.device = device.dev_path().display().to_string();
+            info.storage_type = StorageType::from_device(device.storage_type());
+            info.model = device.model;
+            info.rotational = device.rotational;
+            info.logical_block_size = device.logical_block_size;
+            info.physical_block_size = device.physical_block_size;
+            info.scheduler = device.scheduler;
+        }
+        info
+    }
+
+    /// Filesystem type, free space and mount options, e.g. `ext4, 120.0 GiB free of 500.0 GiB (rw, noatime)`
+    pub fn filesystem_description(&self) -> String {
+        let mut description = self.filesystem.clone();
+        if self.total_space > 0 {
+            description = format!(
+                "{}, {} free of {}",
+                description,
+                format_bytes(self.available_space),
+                format_bytes(self.total_space)
+            );
+        }
+        if !self.mount_options.is_empty() {
+            description = format!("{} ({})", description, self.mount_options.join(", "));
+        }
+        description
+    }
+
+    /// One-line description, e.g. `/dev/nvme0n1 (NVMe, Samsung SSD 980, 512 B sectors, scheduler none)`
+    pub fn description(&self) -> String {
+        let mut details: Vec<String> = Vec::new();
+        if let Some(storage_type) = self.storage_type {
+            details.push(storage_type.description().to_string());
+        }
+        if let Some(model) = &self.model {
+            details.push(model.clone());
+        }
+        match (self.logical_block_size, self.physical_block_size) {
+            (Some(logical), Some(physical)) if logical != physical => details.push(format!(
+                "{}/{} sectors",
+                format_bytes(logical),
+                format_bytes(physical)
+            )),
+            (Some(size), _) | (None, Some(size)) => {
+                details.push(format!("{} sectors", format_bytes(size)))
+            }
+            (None, None) => {}
+        }
+        if let Some(scheduler) = &self.scheduler {
+            details.push(format!("scheduler {}", scheduler));
+        }
+
+        if details.is_empty() {
+            self.device.clone()
+        } else {
+            format!("{} ({})", self.device, details.join(", "))
+        }
     }
 }
 
 /// Storage type enumeration for accuracy validation
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum StorageType {
     /// Solid State Drive (SATA)
     Ssd,
@@ -370,6 +778,34 @@ pub enum StorageType {
 }
 
 impl StorageType {
+    /// Accuracy class of a detected device kind, if it is known
+    pub fn from_device(kind: crate::io::StorageType) -> Option<Self> {
+        match kind {
+            crate::io::StorageType::NVMe => Some(StorageType::Nvme),
+            crate::io::StorageType::SSD => Some(StorageType::Ssd),
+            crate::io::StorageType::HDD => Some(StorageType::Hdd),
+            crate::io::StorageType::Unknown => None,
+        }
+    }
+
+    /// Short human-readable name
+    pub fn description(&self) -> &'static str {
+        match self {
+            StorageType::Nvme => "NVMe",
+            StorageType::Ssd => "SSD",
+            StorageType::Hdd => "HDD",
+        }
+    }
+
+    /// Allowed relative deviation between repeated throughput measurements
+    pub fn accuracy_threshold(&self) -> f64 {
+        match self {
+            StorageType::Nvme => 0.03,
+            StorageType::Ssd => 0.05,
+            StorageType::Hdd => 0.08,
+        }
+    }
+
     /// Infer storage type from performance characteristics
     pub fn infer_from_performance(throughput_mbps: f64, avg_latency: Duration) -> Self {
         let latency_ms = avg_latency.as_secs_f64() * 1000.0;
@@ -386,26 +822,14 @@ impl StorageType {
 
 // Helper functions for system detection
 fn detect_os() -> String {
-    format!("{} {}", std::env::consts::OS, std::env::consts::ARCH)
-}
-
-fn detect_cpu() -> String {
-    // TODO: Implement CPU detection
-    "Unknown CPU".to_string()
-}
-
-fn detect_memory_total() -> u64 {
-    // TODO: Implement memory detection
-    0
-}
-
-fn detect_memory_available() -> u64 {
-    // TODO: Implement memory detection
-    0
+    match KernelInfo::detect() {
+        Some(kernel) => kernel.description(),
+        None => format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+    }
 }
 
 // Custom serde modules for Duration serialization
-mod duration_serde {
+pub(crate) mod duration_serde {
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
     use std::time::Duration;
 
@@ -425,37 +849,65 @@ mod duration_serde {
     }
 }
 
-mod percentiles_serde {
+/// Optional durations, stored like `duration_serde` or as null
+pub(crate) mod option_duration_serde {
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
-    use std::collections::HashMap;
+    use std::time::Duration;
+
+    pub fn serialize<S>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        duration.map(|d| d.as_nanos()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let nanos = Option::<u128>::deserialize(deserializer)?;
+        Ok(nanos.map(|nanos| Duration::from_nanos(nanos as u64)))
+    }
+}
+
+/// Percentiles are stored as an object keyed by the percentile as a string
+/// (`{"50": ns, "99.9": ns}`), which also reads results saved when the keys
+/// were whole-number `u8` percents
+mod percentiles_serde {
+    use crate::models::Percentile;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::BTreeMap;
     use std::time::Duration;
 
     pub fn serialize<S>(
-        percentiles: &HashMap<u8, Duration>,
+        percentiles: &BTreeMap<Percentile, Duration>,
         serializer: S,
     ) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let nanos_map: HashMap<u8, u128> = percentiles
+        let nanos_map: BTreeMap<String, u128> = percentiles
             .iter()
-            .map(|(&k, &v)| (k, v.as_nanos()))
+            .map(|(k, v)| (k.to_string(), v.as_nanos()))
             .collect();
         nanos_map.serialize(serializer)
     }
 
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<HashMap<u8, Duration>, D::Error>
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<BTreeMap<Percentile, Duration>, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let nanos_map = HashMap::<u8, u128>::deserialize(deserializer)?;
-        let duration_map = nanos_map
+        let nanos_map = BTreeMap::<String, u128>::deserialize(deserializer)?;
+        nanos_map
             .into_iter()
-            .map(|(k, v)| (k, Duration::from_nanos(v as u64)))
-            .collect();
-        Ok(duration_map)
+            .map(|(k, v)| {
+                let percentile = k.parse::<Percentile>().map_err(D::Error::custom)?;
+                Ok((percentile, Duration::from_nanos(v as u64)))
+            })
+            .collect()
     }
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -467,10 +919,10 @@ mod tests {
     }
 
     fn create_test_latency_stats() -> LatencyStats {
-        let mut percentiles = HashMap::new();
-        percentiles.insert(50, Duration::from_millis(5));
-        percentiles.insert(95, Duration::from_millis(15));
-        percentiles.insert(99, Duration::from_millis(25));
+        let mut percentiles = BTreeMap::new();
+        percentiles.insert(Percentile::P50, Duration::from_millis(5));
+        percentiles.insert(Percentile::P95, Duration::from_millis(15));
+        percentiles.insert(Percentile::P99, Duration::from_millis(25));
 
         LatencyStats {
             min: Duration::from_millis(1),
@@ -484,6 +936,7 @@ mod tests {
         PerformanceMetrics::new(
             1024 * 1024 * 1024, // 1 GiB
             Duration::from_secs(10),
+            16 * 1024, // 64 KiB blocks
             create_test_latency_stats(),
         )
     }
@@ -492,6 +945,7 @@ mod tests {
         SystemInfo {
             os: "Linux x86_64".to_string(),
             cpu: "Intel Core i7-9700K".to_string(),
+            cpu_count: 8,
             memory_total: 16 * 1024 * 1024 * 1024,    // 16 GiB
             memory_available: 8 * 1024 * 1024 * 1024, // 8 GiB
             storage_info: StorageInfo {
@@ -499,6 +953,13 @@ mod tests {
                 filesystem: "ext4".to_string(),
                 total_space: 1024 * 1024 * 1024 * 1024, // 1 TiB
                 available_space: 512 * 1024 * 1024 * 1024, // 512 GiB
+                mount_options: vec!["rw".to_string(), "noatime".to_string()],
+                storage_type: Some(StorageType::Nvme),
+                model: Some("Samsung SSD 980 PRO 1TB".to_string()),
+                rotational: Some(false),
+                logical_block_size: Some(512),
+                physical_block_size: Some(512),
+                scheduler: Some("none".to_string()),
             },
         }
     }
@@ -539,14 +1000,16 @@ mod tests {
         let elapsed_time = Duration::from_secs(10);
         let latency = create_test_latency_stats();
 
-        let metrics = PerformanceMetrics::new(bytes_processed, elapsed_time, latency);
+        let metrics = PerformanceMetrics::new(bytes_processed, elapsed_time, 16 * 1024, latency);
 
         // Throughput should be ~102.4 MB/s (1 GiB / 10 seconds)
         assert!((metrics.throughput_mbps - 102.4).abs() < 0.1);
 
-        // IOPS should be 1 / avg_latency (1 / 0.005s = 200 IOPS)
-        assert!((metrics.iops - 200.0).abs() < 1.0);
+        // IOPS is completed operations over wall time (16384 ops / 10s), not
+        // 1 / avg_latency, which would only hold for one request at a time
+        assert!((metrics.iops - 1638.4).abs() < 0.001);
 
+        assert_eq!(metrics.operations, 16 * 1024);
         assert_eq!(metrics.bytes_processed, bytes_processed);
         assert_eq!(metrics.elapsed_time, elapsed_time);
     }
@@ -554,7 +1017,7 @@ mod tests {
     #[test]
     fn test_performance_metrics_zero_time() {
         let metrics =
-            PerformanceMetrics::new(1024, Duration::from_secs(0), create_test_latency_stats());
+            PerformanceMetrics::new(1024, Duration::from_secs(0), 1, create_test_latency_stats());
 
         assert_eq!(metrics.throughput_mbps, 0.0);
         assert_eq!(metrics.iops, 0.0);
@@ -580,9 +1043,9 @@ mod tests {
         assert_eq!(latency.min, Duration::from_millis(1));
         assert_eq!(latency.avg, Duration::from_millis(5));
         assert_eq!(latency.max, Duration::from_millis(30));
-        assert!(latency.percentiles.contains_key(&50));
-        assert!(latency.percentiles.contains_key(&95));
-        assert!(latency.percentiles.contains_key(&99));
+        assert!(latency.percentiles.contains_key(&Percentile::P50));
+        assert!(latency.percentiles.contains_key(&Percentile::P95));
+        assert!(latency.percentiles.contains_key(&Percentile::P99));
     }
 
     #[test]
@@ -638,6 +1101,22 @@ mod tests {
         // HDD characteristics
         let hdd_type = StorageType::infer_from_performance(100.0, Duration::from_millis(15));
         assert!(matches!(hdd_type, StorageType::Hdd));
+
+        // A detected device wins over what the numbers suggest
+        let mut result = BenchmarkResult::with_system_info(
+            create_test_config(),
+            create_test_performance_metrics(),
+            create_test_system_info(),
+        );
+        assert_eq!(result.storage_type(), StorageType::Nvme);
+        result.system_info.storage_info.storage_type = None;
+        assert_eq!(
+            result.storage_type(),
+            StorageType::infer_from_performance(
+                result.metrics.throughput_mbps,
+                result.metrics.latency.avg
+            )
+        );
     }
 
     #[test]
@@ -650,12 +1129,13 @@ mod tests {
 
         // Test with insufficient data
         assert!(base_result.meets_accuracy_requirements(&[]));
-        assert!(base_result.meets_accuracy_requirements(&[base_result.clone()]));
+        assert!(base_result.meets_accuracy_requirements(std::slice::from_ref(&base_result)));
 
         // Test with consistent results (should pass)
         let consistent_metrics = PerformanceMetrics::new(
             1024 * 1024 * 1024,
             Duration::from_secs(10),
+            16 * 1024,
             create_test_latency_stats(),
         );
         let consistent_result = BenchmarkResult::with_system_info(
@@ -671,6 +1151,7 @@ mod tests {
         let inconsistent_metrics = PerformanceMetrics::new(
             1024 * 1024 * 1024,
             Duration::from_secs(5), // Much faster, will cause high deviation
+            16 * 1024,
             create_test_latency_stats(),
         );
         let inconsistent_result = BenchmarkResult::with_system_info(
@@ -686,9 +1167,45 @@ mod tests {
 
     #[test]
     fn test_system_info_detection() {
+        // The default is a placeholder that never looks at the host
+        let placeholder = SystemInfo::default();
+        assert_eq!(placeholder.cpu_count, 0);
+        assert_eq!(placeholder.memory_total, 0);
+        assert_eq!(placeholder.storage_info.total_space, 0);
+
         let system_info = SystemInfo::detect();
         assert!(!system_info.os.is_empty());
         assert!(!system_info.cpu.is_empty());
+        assert!(system_info.cpu_count >= 1);
+
+        let root = tempfile::TempDir::new().unwrap();
+        let roots = SystemRoots::new(root.path().join("proc"), root.path().join("sys"));
+        std::fs::create_dir_all(&roots.proc).unwrap();
+        std::fs::write(
+            roots.proc.join("cpuinfo"),
+            "processor\t: 0\nmodel name\t: Test CPU\nprocessor\t: 1\nmodel name\t: Test CPU\n",
+        )
+        .unwrap();
+        std::fs::write(
+            roots.proc.join("meminfo"),
+            "MemTotal: 2048 kB\nMemAvailable: 1024 kB\n",
+        )
+        .unwrap();
+        let system_info = SystemInfo::detect_with(&roots);
+        assert_eq!(system_info.cpu, "Test CPU");
+        assert_eq!(system_info.cpu_count, 2);
+        assert_eq!(system_info.memory_total, 2048 * 1024);
+        assert_eq!(system_info.memory_available, 1024 * 1024);
+    }
+
+    #[test]
+    fn test_filesystem_description() {
+        let storage_info = create_test_system_info().storage_info;
+        assert_eq!(
+            storage_info.filesystem_description(),
+            "ext4, 512.0 GiB free of 1.0 TiB (rw, noatime)"
+        );
+        assert_eq!(StorageInfo::detect_default().filesystem_description(), "Unknown");
     }
 
     #[test]
@@ -696,6 +1213,36 @@ mod tests {
         let storage_info = StorageInfo::detect_default();
         assert_eq!(storage_info.device, "Unknown");
         assert_eq!(storage_info.filesystem, "Unknown");
+
+        // Without a readable mount table nothing more is known
+        let empty = tempfile::TempDir::new().unwrap();
+        let roots = SystemRoots::new(empty.path().join("proc"), empty.path().join("sys"));
+        let storage_info = StorageInfo::detect_with(&roots, empty.path());
+        assert_eq!(storage_info.device, "Unknown");
+        assert_eq!(storage_info.storage_type, None);
+        assert_eq!(storage_info.description(), "Unknown");
+    }
+
+    #[test]
+    fn test_storage_info_description() {
+        let storage_info = create_test_system_info().storage_info;
+        assert_eq!(
+            storage_info.description(),
+            "/dev/nvme0n1 (NVMe, Samsung SSD 980 PRO 1TB, 512 B sectors, scheduler none)"
+        );
+        let storage_info = StorageInfo {
+            device: "/dev/sda".to_string(),
+            storage_type: Some(StorageType::Hdd),
+            logical_block_size: Some(512),
+            physical_block_size: Some(4096),
+            ..StorageInfo::default()
+        };
+        assert_eq!(storage_info.description(), "/dev/sda (HDD, 512 B/4.0 KiB sectors)");
+        assert_eq!(
+            StorageType::from_device(crate::io::StorageType::SSD),
+            Some(StorageType::Ssd)
+        );
+        assert_eq!(StorageType::from_device(crate::io::StorageType::Unknown), None);
     }
 
     #[test]
@@ -723,6 +1270,33 @@ mod tests {
         assert_eq!(result.timestamp, deserialized.timestamp);
     }
 
+    #[test]
+    fn test_direct_io_mode_round_trip() {
+        let result = BenchmarkResult::with_system_info(
+            create_test_config(),
+            create_test_performance_metrics(),
+            create_test_system_info(),
+        );
+        assert_eq!(result.direct_io_mode, None);
+        assert!(!result.used_page_cache());
+
+        let result = result.with_direct_io_mode(DirectIoMode::BufferedFsync);
+        assert!(result.used_page_cache());
+
+        let json = serde_json::to_string(&result).unwrap();
+        let deserialized: BenchmarkResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            deserialized.direct_io_mode,
+            Some(DirectIoMode::BufferedFsync)
+        );
+
+        // Results saved before the mode was recorded still load
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value.as_object_mut().unwrap().remove("direct_io_mode");
+        let legacy: BenchmarkResult = serde_json::from_value(value).unwrap();
+        assert_eq!(legacy.direct_io_mode, None);
+    }
+
     #[test]
     fn test_duration_serde() {
         let original_duration = Duration::from_nanos(123456789);
@@ -733,18 +1307,36 @@ mod tests {
 
     #[test]
     fn test_percentiles_serde() {
-        let mut percentiles = HashMap::new();
-        percentiles.insert(50, Duration::from_millis(5));
-        percentiles.insert(95, Duration::from_millis(15));
-        percentiles.insert(99, Duration::from_millis(25));
+        let mut latency = create_test_latency_stats();
+        latency
+            .percentiles
+            .insert(Percentile::P99_9, Duration::from_millis(28));
+        latency
+            .percentiles
+            .insert(Percentile::P99_99, Duration::from_millis(29));
+
+        let serialized = serde_json::to_string(&latency).unwrap();
+        assert!(serialized.contains("\"99.9\":28000000"));
+        assert!(serialized.contains("\"99.99\":29000000"));
+        let deserialized: LatencyStats = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.percentiles, latency.percentiles);
+    }
 
-        let serialized = serde_json::to_string(&percentiles).unwrap();
-        let deserialized: HashMap<u8, Duration> = serde_json::from_str(&serialized).unwrap();
+    #[test]
+    fn test_percentiles_serde_reads_u8_keys() {
+        // Results saved before fractional percentiles used whole-number keys
+        let json = r#"{"min":1000,"avg":5000,"max":30000,"percentiles":{"50":5000,"95":15000,"99":25000}}"#;
+        let latency: LatencyStats = serde_json::from_str(json).unwrap();
+        assert_eq!(latency.percentiles.len(), 3);
+        assert_eq!(
+            latency.percentile(Percentile::P50),
+            Some(Duration::from_micros(5))
+        );
+        assert_eq!(latency.p99(), Duration::from_micros(25));
+        assert_eq!(latency.percentile(Percentile::P99_9), None);
 
-        assert_eq!(percentiles.len(), deserialized.len());
-        for (&key, &value) in &percentiles {
-            assert_eq!(Some(&value), deserialized.get(&key));
-        }
+        let bad = r#"{"min":0,"avg":0,"max":0,"percentiles":{"250":1}}"#;
+        assert!(serde_json::from_str::<LatencyStats>(bad).is_err());
     }
 
     #[test]
@@ -786,11 +1378,138 @@ mod tests {
         assert_eq!(stats.p95(), Duration::from_micros(300));
     }
 
+    #[test]
+    fn test_latency_stats_from_histogram() {
+        let samples = vec![
+            Duration::from_micros(100),
+            Duration::from_micros(200),
+            Duration::from_micros(300),
+        ];
+        let mut histogram = LatencyHistogram::new();
+        for sample in &samples {
+            histogram.record(*sample);
+        }
+
+        let stats = LatencyStats::from_histogram(&histogram, &Percentile::DEFAULTS);
+        let expected = LatencyStats::from_samples(&samples);
+        assert_eq!(stats.min, expected.min);
+        assert_eq!(stats.avg, expected.avg);
+        assert_eq!(stats.max, expected.max);
+        assert_eq!(stats.p99(), expected.p99());
+        assert_eq!(stats.percentile(Percentile::P99_99), Some(expected.max));
+        let p50 = stats.percentiles[&Percentile::P50].as_nanos() as f64;
+        assert!((p50 - 200_000.0).abs() / 200_000.0 < 0.005);
+
+        assert_eq!(
+            LatencyStats::from_histogram(&LatencyHistogram::new(), &Percentile::DEFAULTS).max,
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn test_performance_metrics_from_histogram() {
+        let mut histogram = LatencyHistogram::new();
+        for _ in 0..400 {
+            histogram.record(Duration::from_millis(5));
+        }
+        let metrics = PerformanceMetrics::from_histogram(
+            4 * 1024 * 1024,
+            Duration::from_secs(2),
+            histogram,
+            &[Percentile::P99_9],
+        );
+        assert!((metrics.throughput_mbps - 2.0).abs() < 0.001);
+        assert!((metrics.iops - 200.0).abs() < 0.001);
+        assert_eq!(metrics.latency.avg, Duration::from_millis(5));
+        assert_eq!(
+            metrics
+                .latency
+                .percentiles
+                .keys()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![Percentile::P99_9]
+        );
+        assert_eq!(metrics.latency_histogram.as_ref().unwrap().count(), 400);
+
+        let json = serde_json::to_string(&metrics).unwrap();
+        let deserialized: PerformanceMetrics = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.latency_histogram, metrics.latency_histogram);
+    }
+
+    #[test]
+    fn test_performance_metrics_from_directions() {
+        let elapsed = Duration::from_secs(2);
+        let mut reads = LatencyHistogram::new();
+        let mut writes = LatencyHistogram::new();
+        for _ in 0..300 {
+            reads.record(Duration::from_micros(100));
+        }
+        for _ in 0..100 {
+            writes.record(Duration::from_millis(2));
+        }
+        let metrics = PerformanceMetrics::from_directions(
+            elapsed,
+            DirectionMetrics::from_histogram(300 * 4096, elapsed, reads, &Percentile::DEFAULTS),
+            DirectionMetrics::from_histogram(100 * 4096, elapsed, writes, &Percentile::DEFAULTS),
+            &Percentile::DEFAULTS,
+        );
+
+        assert_eq!(metrics.bytes_processed, 400 * 4096);
+        assert!((metrics.iops - 200.0).abs() < 0.001);
+        let read = metrics.read.as_ref().unwrap();
+        let write = metrics.write.as_ref().unwrap();
+        assert_eq!(read.operations, 300);
+        assert!((read.iops - 150.0).abs() < 0.001);
+        assert_eq!(read.latency.max, Duration::from_micros(100));
+        assert_eq!(write.operations, 100);
+        assert!((write.iops - 50.0).abs() < 0.001);
+        assert_eq!(write.latency.min, Duration::from_millis(2));
+        assert_eq!(metrics.latency.max, Duration::from_millis(2));
+
+        let json = serde_json::to_string(&metrics).unwrap();
+        let deserialized: PerformanceMetrics = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.write.unwrap().bytes_processed, 100 * 4096);
+
+        // A read-only run has no write side
+        let read_only = PerformanceMetrics::from_directions(
+            elapsed,
+            read.clone(),
+            DirectionMetrics::from_histogram(0, elapsed, LatencyHistogram::new(), &[]),
+            &Percentile::DEFAULTS,
+        );
+        assert!(read_only.read.is_some());
+        assert!(read_only.write.is_none());
+        let json = serde_json::to_string(&read_only).unwrap();
+        assert!(!json.contains("\"write\""));
+    }
+
+    #[test]
+    fn test_direction_metrics_combine() {
+        let elapsed = Duration::from_secs(1);
+        let worker = |micros: u64| {
+            let mut histogram = LatencyHistogram::new();
+            for _ in 0..50 {
+                histogram.record(Duration::from_micros(micros));
+            }
+            DirectionMetrics::from_histogram(50 * 4096, elapsed, histogram, &Percentile::DEFAULTS)
+        };
+        let workers = [worker(100), worker(300)];
+
+        let combined = DirectionMetrics::combine(&workers, elapsed, &Percentile::DEFAULTS).unwrap();
+        assert_eq!(combined.operations, 100);
+        assert_eq!(combined.bytes_processed, 100 * 4096);
+        assert!((combined.iops - 100.0).abs() < 0.001);
+        assert_eq!(combined.latency.avg, Duration::from_micros(200));
+        assert!(DirectionMetrics::combine(&[], elapsed, &Percentile::DEFAULTS).is_none());
+    }
+
     #[test]
     fn test_performance_metrics_validate_throughput() {
         let metrics = PerformanceMetrics::new(
             2 * 1024 * 1024,
             Duration::from_secs(2),
+            32,
             create_test_latency_stats(),
         );
         assert!(metrics.validate_throughput());
@@ -0,0 +1,297 @@
+//! Results of saturation runs
+//!
+//! A saturation run records throughput and latency at each offered load
+//! level. Past some level, more load stops buying throughput and only
+//! lengthens queues, so latency climbs much faster than IOPS. The last level
+//! before that happens is the knee: the most load the storage takes before
+//! latency degrades.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::config::{BenchmarkConfig, LoadLadder, SaturationConfig};
+use crate::models::{BenchmarkResult, Percentile};
+
+/// Result of one load level in a saturation run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaturationStep {
+    /// Offered load, in the ladder's unit
+    pub level: f64,
+    /// Result of the run at this level
+    pub result: BenchmarkResult,
+}
+
+/// Why the knee sits where it does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KneeReason {
+    /// Latency grew sharply faster than IOPS at the next step
+    LatencyJump,
+    /// Latency passed the limit at the next step
+    LatencyLimit,
+    /// Latency never degraded, so the knee lies at or beyond the highest level
+    EndOfLadder,
+}
+
+/// Last load level before latency degrades sharply
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Knee {
+    /// Index of the knee in the steps
+    pub step: usize,
+    /// Why the knee sits there
+    pub reason: KneeReason,
+}
+
+/// Steps of a saturation run and the knee found in them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaturationResult {
+    /// Time the run finished
+    pub timestamp: DateTime<Utc>,
+    /// Configuration the load levels were applied to
+    pub base: BenchmarkConfig,
+    /// Load levels and stopping rules
+    pub config: SaturationConfig,
+    /// One entry per load level run, lowest first
+    pub steps: Vec<SaturationStep>,
+    /// Knee of the curve (`None` when even the lowest level passed the latency limit)
+    pub knee: Option<Knee>,
+}
+
+impl KneeReason {
+    /// Human-readable explanation
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::LatencyJump => "latency rose sharply at the next step",
+            Self::LatencyLimit => "latency passed the limit at the next step",
+            Self::EndOfLadder => "latency did not degrade within the tested range",
+        }
+    }
+}
+
+impl SaturationStep {
+    /// Median latency
+    pub fn p50(&self) -> Duration {
+        self.latency_at(Percentile::P50)
+    }
+
+    /// 99th percentile latency
+    pub fn p99(&self) -> Duration {
+        self.latency_at(Percentile::P99)
+    }
+
+    /// Achieved operations per second
+    pub fn iops(&self) -> f64 {
+        self.result.metrics.iops
+    }
+
+    /// Latency at `percentile`, read from the histogram if it was not reported
+    fn latency_at(&self, percentile: Percentile) -> Duration {
+        let metrics = &self.result.metrics;
+        metrics
+            .latency
+            .percentile(percentile)
+            .or_else(|| {
+                let histogram = metrics.latency_histogram.as_ref()?;
+                Some(histogram.value_at_percentile(percentile.value()))
+            })
+            .unwrap_or(metrics.latency.avg)
+    }
+}
+
+impl SaturationResult {
+    /// Group the steps of a finished run and find the knee
+    pub fn new(
+        base: BenchmarkConfig,
+        config: SaturationConfig,
+        steps: Vec<SaturationStep>,
+    ) -> Self {
+        let knee = find_knee(&config, &steps);
+        Self {
+            timestamp: Utc::now(),
+            base,
+            config,
+            steps,
+            knee,
+        }
+    }
+
+    /// Step at the knee
+    pub fn knee_step(&self) -> Option<&SaturationStep> {
+        self.steps.get(self.knee?.step)
+    }
+
+    /// Whether the run ended early because latency passed the limit
+    pub fn stopped_at_limit(&self) -> bool {
+        self.steps
+            .last()
+            .is_some_and(|step| self.config.passes_limit(step.p99()))
+    }
+
+    /// Queue depth at the knee, when the run raised queue depth
+    pub fn measured_queue_depth(&self) -> Option<usize> {
+        match self.config.ladder {
+            LoadLadder::QueueDepths(_) => self.knee_step()?.result.config.queue_depth,
+            _ => None,
+        }
+    }
+
+    /// One-line summary for history listings
+    pub fn summary(&self) -> String {
+        let summary = format!(
+            "{} - {} saturation - {} steps by {}",
+            self.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
+            self.base.mode.description(),
+            self.steps.len(),
+            self.config.ladder.description()
+        );
+        match self.knee_step() {
+            Some(knee) => format!(
+                "{} - knee at {} {} ({:.0} IOPS, p99 {:.2}ms)",
+                summary,
+                self.config.ladder.description(),
+                self.config.ladder.format_level(knee.level),
+                knee.iops(),
+                knee.p99().as_secs_f64() * 1000.0
+            ),
+            None => format!("{} - no level within the latency limit", summary),
+        }
+    }
+}
+
+/// Find the last step before latency degrades sharply or passes the limit
+///
+/// Between two steps, latency degrades sharply when p99 latency grows by a
+/// larger factor than `knee_factor` times the growth in IOPS. Below
+/// saturation extra load mostly turns into IOPS; past it, it only queues.
+fn find_knee(config: &SaturationConfig, steps: &[SaturationStep]) -> Option<Knee> {
+    for (index, step) in steps.iter().enumerate() {
+        if config.passes_limit(step.p99()) {
+            return index.checked_sub(1).map(|step| Knee {
+                step,
+                reason: KneeReason::LatencyLimit,
+            });
+        }
+        let Some(previous) = index.checked_sub(1).map(|i| &steps[i]) else {
+            continue;
+        };
+        let latency_growth =
+            step.p99().as_secs_f64() / previous.p99().as_secs_f64().max(f64::EPSILON);
+        let iops_growth = step.iops() / previous.iops().max(f64::EPSILON);
+        if latency_growth > config.knee_factor * iops_growth {
+            return Some(Knee {
+                step: index - 1,
+                reason: KneeReason::LatencyJump,
+            });
+        }
+    }
+    Some(Knee {
+        step: steps.len().checked_sub(1)?,
+        reason: KneeReason::EndOfLadder,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{LatencyStats, PerformanceMetrics, SystemInfo};
+
+    fn step(queue_depth: usize, iops: f64, p99_us: u64) -> SaturationStep {
+        let p99 = Duration::from_micros(p99_us);
+        let latency = LatencyStats::with_percentiles(
+            p99 / 4,
+            p99 / 2,
+            p99 * 2,
+            [(Percentile::P50, p99 / 2), (Percentile::P99, p99)]
+                .into_iter()
+                .collect(),
+        );
+        let metrics = PerformanceMetrics {
+            iops,
+            latency,
+            ..PerformanceMetrics::default()
+        };
+        let config = BenchmarkConfig::random_read_write().with_queue_depth(queue_depth);
+        SaturationStep {
+            level: queue_depth as f64,
+            result: BenchmarkResult::with_system_info(config, metrics, SystemInfo::default()),
+        }
+    }
+
+    fn result(steps: Vec<SaturationStep>) -> SaturationResult {
+        SaturationResult::new(
+            BenchmarkConfig::random_read_write(),
+            SaturationConfig::queue_depths(vec![1, 2, 4, 8, 16, 32]),
+            steps,
+        )
+    }
+
+    #[test]
+    fn test_knee_where_latency_outgrows_iops() {
+        let result = result(vec![
+            step(1, 10_000.0, 100),
+            step(2, 19_000.0, 110),
+            step(4, 35_000.0, 130),
+            step(8, 60_000.0, 180),
+            // Saturated: IOPS flat while latency doubles
+            step(16, 62_000.0, 360),
+            step(32, 62_500.0, 720),
+        ]);
+        assert_eq!(
+            result.knee,
+            Some(Knee {
+                step: 3,
+                reason: KneeReason::LatencyJump
+            })
+        );
+        assert_eq!(result.measured_queue_depth(), Some(8));
+        assert_eq!(result.knee_step().unwrap().p50(), Duration::from_micros(90));
+        assert!(!result.stopped_at_limit());
+        assert!(result.summary().contains("knee at Queue depth 8"));
+    }
+
+    #[test]
+    fn test_knee_at_latency_limit() {
+        // The default limit is 100ms
+        let result = result(vec![
+            step(1, 100.0, 8_000),
+            step(2, 190.0, 12_000),
+            step(4, 300.0, 150_000),
+        ]);
+        assert_eq!(
+            result.knee,
+            Some(Knee {
+                step: 1,
+                reason: KneeReason::LatencyLimit
+            })
+        );
+        assert!(result.stopped_at_limit());
+
+        let over_from_start = self::result(vec![step(1, 100.0, 200_000)]);
+        assert_eq!(over_from_start.knee, None);
+        assert_eq!(over_from_start.measured_queue_depth(), None);
+        assert!(over_from_start.summary().contains("no level within"));
+    }
+
+    #[test]
+    fn test_knee_beyond_ladder() {
+        let result = result(vec![step(1, 10_000.0, 100), step(2, 20_000.0, 100)]);
+        assert_eq!(
+            result.knee,
+            Some(Knee {
+                step: 1,
+                reason: KneeReason::EndOfLadder
+            })
+        );
+        assert_eq!(self::result(Vec::new()).knee, None, "no steps, no knee");
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let result = result(vec![step(1, 10_000.0, 100), step(2, 12_000.0, 400)]);
+        let json = serde_json::to_string(&result).unwrap();
+        let deserialized: SaturationResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.knee, result.knee);
+        assert_eq!(deserialized.steps.len(), 2);
+        assert_eq!(deserialized.config, result.config);
+    }
+}
@@ -0,0 +1,266 @@
+//! Steady-state detection
+//!
+//! SSDs deliver very different performance fresh out of the box, after
+//! their write cache fills and once garbage collection settles. Following
+//! the SNIA approach, a run is in steady state once the chosen metric stays
+//! inside a tolerance band around its mean over a rolling window of timeline
+//! samples.
+
+use crate::config::{SteadyStateConfig, SteadyStateMetric};
+use crate::models::result::{
+    accuracy_threshold, duration_serde, max_relative_deviation, option_duration_serde,
+};
+use crate::models::TimelineSample;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Outcome of steady-state detection for a run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SteadyState {
+    /// Metric that had to settle
+    pub metric: SteadyStateMetric,
+    /// Rolling window the metric had to stay inside the band for
+    #[serde(with = "duration_serde")]
+    pub window: Duration,
+    /// Allowed deviation from the window mean as a fraction
+    pub tolerance: f64,
+    /// Time since the start of the measurement at which the window was steady
+    /// (`None` if the run hit its max duration first)
+    #[serde(with = "option_duration_serde")]
+    pub reached_at: Option<Duration>,
+    /// Mean of the metric over the last window checked
+    pub window_mean: Option<f64>,
+}
+
+/// Checks the latest timeline samples of a run for steady state
+#[derive(Debug, Clone)]
+pub struct SteadyStateDetector {
+    config: SteadyStateConfig,
+    interval: Duration,
+    window_samples: usize,
+    checked: usize,
+    last: Option<(f64, f64)>,
+    reached_at: Option<Duration>,
+}
+
+impl SteadyState {
+    /// Whether the run reached steady state
+    pub fn reached(&self) -> bool {
+        self.reached_at.is_some()
+    }
+
+    /// Short description such as `reached after 42s`
+    pub fn description(&self) -> String {
+        match self.reached_at {
+            Some(at) => format!("reached after {:.1}s", at.as_secs_f64()),
+            None => "not reached".to_string(),
+        }
+    }
+
+    /// Combine the outcomes of workers running side by side
+    ///
+    /// Each worker settles on its own, so the run is steady only once every
+    /// worker is, at the time the last one got there. Window means add up
+    /// like the workers' throughput does.
+    pub fn combine<'a>(outcomes: impl IntoIterator<Item = &'a SteadyState>) -> Option<SteadyState> {
+        let mut combined: Option<SteadyState> = None;
+        for outcome in outcomes {
+            let Some(combined) = combined.as_mut() else {
+                combined = Some(outcome.clone());
+                continue;
+            };
+            combined.reached_at = combined
+                .reached_at
+                .zip(outcome.reached_at)
+                .map(|(a, b)| a.max(b));
+            combined.tolerance = combined.tolerance.max(outcome.tolerance);
+            combined.window_mean = match (combined.window_mean, outcome.window_mean) {
+                (Some(a), Some(b)) => Some(a + b),
+                _ => None,
+            };
+        }
+        combined
+    }
+}
+
+impl SteadyStateDetector {
+    /// Create a detector for samples taken every `interval`
+    pub fn new(config: SteadyStateConfig, interval: Duration) -> Self {
+        let interval = interval.max(Duration::from_millis(1));
+        let window_samples = config.window.as_nanos().div_ceil(interval.as_nanos()) as usize;
+        Self {
+            config,
+            interval,
+            window_samples: window_samples.max(2),
+            checked: 0,
+            last: None,
+            reached_at: None,
+        }
+    }
+
+    /// Check the samples recorded so far, returning true once steady state is reached
+    ///
+    /// Only samples that closed since the previous call are new; the window
+    /// is re-evaluated when one arrives. Once reached, the outcome is fixed.
+    pub fn check(&mut self, samples: &[TimelineSample]) -> bool {
+        if self.reached_at.is_some() || samples.len() == self.checked {
+            return self.reached_at.is_some();
+        }
+        self.checked = samples.len();
+
+        // A trailing partial interval would make the last value look low
+        let full: Vec<&TimelineSample> = samples
+            .iter()
+            .filter(|sample| sample.duration >= self.interval)
+            .collect();
+        if full.len() < self.window_samples {
+            return false;
+        }
+        let window = &full[full.len() - self.window_samples..];
+        let values: Vec<f64> = window
+            .iter()
+            .map(|sample| match self.config.metric {
+                SteadyStateMetric::Throughput => sample.throughput_mbps(),
+                SteadyStateMetric::Iops => sample.iops(),
+            })
+            .collect();
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let mean_throughput =
+            window.iter().map(|s| s.throughput_mbps()).sum::<f64>() / window.len() as f64;
+        let tolerance = self
+            .config
+            .tolerance
+            .unwrap_or_else(|| accuracy_threshold(mean_throughput));
+        self.last = Some((mean, tolerance));
+
+        if max_relative_deviation(&values) <= tolerance {
+            let end = window.last().unwrap();
+            self.reached_at = Some(end.offset + end.duration);
+        }
+        self.reached_at.is_some()
+    }
+
+    /// Outcome of the detection so far
+    pub fn outcome(&self) -> SteadyState {
+        SteadyState {
+            metric: self.config.metric,
+            window: self.config.window,
+            tolerance: self.last.map_or_else(
+                || self.config.tolerance.unwrap_or(0.0),
+                |(_, tolerance)| tolerance,
+            ),
+            reached_at: self.reached_at,
+            window_mean: self.last.map(|(mean, _)| mean),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVAL: Duration = Duration::from_secs(1);
+
+    /// One full one-second sample per throughput value, in MB/s
+    fn samples(throughputs: &[f64]) -> Vec<TimelineSample> {
+        throughputs
+            .iter()
+            .enumerate()
+            .map(|(i, &mbps)| TimelineSample {
+                offset: INTERVAL * i as u32,
+                duration: INTERVAL,
+                bytes: (mbps * 1024.0 * 1024.0) as u64,
+                operations: (mbps * 256.0) as u64,
+                p50: Duration::from_micros(100),
+                p99: Duration::from_micros(500),
+            })
+            .collect()
+    }
+
+    fn config(tolerance: Option<f64>) -> SteadyStateConfig {
+        SteadyStateConfig {
+            window: Duration::from_secs(3),
+            tolerance,
+            ..SteadyStateConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_reaches_steady_state_after_settling() {
+        let history = samples(&[900.0, 600.0, 410.0, 400.0, 395.0, 405.0]);
+        let mut detector = SteadyStateDetector::new(config(Some(0.05)), INTERVAL);
+
+        // Fed one sample at a time, as during a run
+        let reached: Vec<bool> = (1..=history.len())
+            .map(|n| detector.check(&history[..n]))
+            .collect();
+        assert_eq!(reached, vec![false, false, false, false, true, true]);
+
+        let outcome = detector.outcome();
+        assert!(outcome.reached());
+        assert_eq!(outcome.reached_at, Some(Duration::from_secs(5)));
+        let mean = outcome.window_mean.unwrap();
+        assert!((mean - 401.666).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_not_reached_while_fluctuating() {
+        let history = samples(&[400.0, 300.0, 400.0, 300.0, 400.0]);
+        let mut detector = SteadyStateDetector::new(config(Some(0.05)), INTERVAL);
+        assert!(!detector.check(&history));
+        let outcome = detector.outcome();
+        assert!(!outcome.reached());
+        assert_eq!(outcome.description(), "not reached");
+    }
+
+    #[test]
+    fn test_default_tolerance_follows_accuracy_threshold() {
+        // 4% spread: steady for a SATA SSD (±5%) but not for NVMe (±3%)
+        let mut sata = SteadyStateDetector::new(config(None), INTERVAL);
+        assert!(sata.check(&samples(&[400.0, 375.0, 400.0])));
+        assert_eq!(sata.outcome().tolerance, 0.05);
+
+        let mut nvme = SteadyStateDetector::new(config(None), INTERVAL);
+        assert!(!nvme.check(&samples(&[2000.0, 1875.0, 2000.0])));
+        assert_eq!(nvme.outcome().tolerance, 0.03);
+    }
+
+    #[test]
+    fn test_partial_interval_ignored() {
+        let mut history = samples(&[400.0, 400.0, 400.0]);
+        history.push(TimelineSample {
+            duration: Duration::from_millis(100),
+            bytes: 1024,
+            ..history[2].clone()
+        });
+        let mut detector = SteadyStateDetector::new(config(Some(0.05)), INTERVAL);
+        assert!(detector.check(&history));
+    }
+
+    #[test]
+    fn test_combine_waits_for_every_worker() {
+        let steady = |at: Option<u64>| SteadyState {
+            metric: SteadyStateMetric::Throughput,
+            window: Duration::from_secs(3),
+            tolerance: 0.05,
+            reached_at: at.map(Duration::from_secs),
+            window_mean: Some(100.0),
+        };
+        let combined = SteadyState::combine([&steady(Some(5)), &steady(Some(8))]).unwrap();
+        assert_eq!(combined.reached_at, Some(Duration::from_secs(8)));
+        assert_eq!(combined.window_mean, Some(200.0));
+
+        let combined = SteadyState::combine([&steady(Some(5)), &steady(None)]).unwrap();
+        assert!(!combined.reached());
+        assert!(SteadyState::combine([]).is_none());
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let mut detector = SteadyStateDetector::new(config(Some(0.05)), INTERVAL);
+        detector.check(&samples(&[400.0, 400.0, 400.0]));
+        let outcome = detector.outcome();
+        let json = serde_json::to_string(&outcome).unwrap();
+        assert_eq!(serde_json::from_str::<SteadyState>(&json).unwrap(), outcome);
+    }
+}
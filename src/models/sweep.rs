@@ -0,0 +1,369 @@
+//! Results of parameter sweeps
+//!
+//! A sweep runs one configuration per combination of swept values. The
+//! results are kept together in a `SweepResult`, which saves and exports as
+//! one object and can be laid out as matrices such as IOPS by block size and
+//! thread count.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{BenchmarkConfig, SweepConfig, SweepParameter, SweepPoint};
+use crate::models::{BenchmarkResult, PerformanceMetrics};
+use crate::util::units::{format_iops, format_latency, format_throughput};
+
+/// Metric shown in the cells of a sweep matrix
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SweepMetric {
+    /// Throughput in MB/s
+    #[default]
+    Throughput,
+    /// Operations per second
+    Iops,
+    /// Average latency
+    AvgLatency,
+    /// 99th percentile latency
+    P99Latency,
+}
+
+/// Result of one configuration in a sweep
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SweepEntry {
+    /// Parameter values the configuration was run with
+    pub point: SweepPoint,
+    /// Result of the run
+    pub result: BenchmarkResult,
+}
+
+/// Results of every configuration in a sweep, grouped together
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SweepResult {
+    /// Time the sweep finished
+    pub timestamp: DateTime<Utc>,
+    /// Configuration the swept values were applied to
+    pub base: BenchmarkConfig,
+    /// Swept values
+    pub sweep: SweepConfig,
+    /// One entry per configuration, in expansion order
+    pub entries: Vec<SweepEntry>,
+}
+
+/// One metric of a sweep laid out by two of its parameters
+///
+/// Rows follow the first swept parameter and columns the second. With a
+/// single swept parameter there are no columns and each row holds one cell.
+/// A third swept parameter is held at `fixed`, giving one matrix per value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepMatrix {
+    /// Metric in the cells
+    pub metric: SweepMetric,
+    /// Parameter held at one value for this matrix, if three were swept
+    pub fixed: Option<(SweepParameter, u64)>,
+    /// Parameter varying down the rows
+    pub row_parameter: SweepParameter,
+    /// Row values
+    pub rows: Vec<u64>,
+    /// Parameter varying across the columns, if two or more were swept
+    pub column_parameter: Option<SweepParameter>,
+    /// Column values
+    pub columns: Vec<u64>,
+    /// Cell values by row then column (`None` where no run matched)
+    pub cells: Vec<Vec<Option<f64>>>,
+}
+
+impl SweepMetric {
+    /// Every metric
+    pub const ALL: [SweepMetric; 4] = [
+        Self::Throughput,
+        Self::Iops,
+        Self::AvgLatency,
+        Self::P99Latency,
+    ];
+
+    /// Human-readable name
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Throughput => "Throughput",
+            Self::Iops => "IOPS",
+            Self::AvgLatency => "Average latency",
+            Self::P99Latency => "p99 latency",
+        }
+    }
+
+    /// Value of the metric, with latencies in microseconds
+    pub fn value(&self, metrics: &PerformanceMetrics) -> f64 {
+        match self {
+            Self::Throughput => metrics.throughput_mbps,
+            Self::Iops => metrics.iops,
+            Self::AvgLatency => metrics.latency.avg.as_secs_f64() * 1e6,
+            Self::P99Latency => metrics.latency.p99().as_secs_f64() * 1e6,
+        }
+    }
+
+    /// Format a value returned by `value`
+    pub fn format(&self, value: f64) -> String {
+        match self {
+            Self::Throughput => format_throughput(value),
+            Self::Iops => format_iops(value),
+            Self::AvgLatency | Self::P99Latency => {
+                format_latency(std::time::Duration::from_secs_f64(value.max(0.0) / 1e6))
+            }
+        }
+    }
+
+    /// Whether a higher value is better
+    pub fn higher_is_better(&self) -> bool {
+        matches!(self, Self::Throughput | Self::Iops)
+    }
+}
+
+impl SweepResult {
+    /// Group the entries of a finished sweep
+    pub fn new(base: BenchmarkConfig, sweep: SweepConfig, entries: Vec<SweepEntry>) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            base,
+            sweep,
+            entries,
+        }
+    }
+
+    /// One-line summary for history listings
+    pub fn summary(&self) -> String {
+        let parameters: Vec<&str> = self
+            .sweep
+            .parameters()
+            .iter()
+            .map(SweepParameter::description)
+            .collect();
+        let summary = format!(
+            "{} - {} sweep - {} configs by {}",
+            self.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
+            self.base.mode.description(),
+            self.entries.len(),
+            parameters.join(" x ")
+        );
+        match self.best(SweepMetric::Throughput) {
+            Some(best) => format!(
+                "{} - best {:.2} MB/s at {}",
+                summary,
+                best.result.metrics.throughput_mbps,
+                best.point.description(&self.sweep.parameters())
+            ),
+            None => summary,
+        }
+    }
+
+    /// Entry with the best value of `metric`
+    pub fn best(&self, metric: SweepMetric) -> Option<&SweepEntry> {
+        self.entries.iter().max_by(|a, b| {
+            let (a, b) = (
+                metric.value(&a.result.metrics),
+                metric.value(&b.result.metrics),
+            );
+            if metric.higher_is_better() {
+                a.total_cmp(&b)
+            } else {
+                b.total_cmp(&a)
+            }
+        })
+    }
+
+    /// Lay `metric` out as matrices over the swept parameters
+    pub fn matrices(&self, metric: SweepMetric) -> Vec<SweepMatrix> {
+        let parameters = self.sweep.parameters();
+        let Some(&row_parameter) = parameters.first() else {
+            return Vec::new();
+        };
+        let column_parameter = parameters.get(1).copied();
+        let fixed_parameter = parameters.get(2).copied();
+
+        let values = |parameter: Option<SweepParameter>| -> Vec<u64> {
+            let Some(parameter) = parameter else {
+                return Vec::new();
+            };
+            let mut values: Vec<u64> = self
+                .entries
+                .iter()
+                .filter_map(|entry| parameter.value(&entry.point))
+                .collect();
+            values.sort_unstable();
+            values.dedup();
+            values
+        };
+        let rows = values(Some(row_parameter));
+        let columns = values(column_parameter);
+        let fixed_values: Vec<Option<u64>> = match fixed_parameter {
+            Some(parameter) => values(Some(parameter)).into_iter().map(Some).collect(),
+            None => vec![None],
+        };
+
+        let cell = |row: u64, column: Option<u64>, fixed: Option<u64>| {
+            let matches = |parameter: Option<SweepParameter>, value: Option<u64>, point| {
+                parameter.is_none_or(|parameter| parameter.value(point) == value)
+            };
+            self.entries
+                .iter()
+                .find(|entry| {
+                    row_parameter.value(&entry.point) == Some(row)
+                        && matches(column_parameter, column, &entry.point)
+                        && matches(fixed_parameter, fixed, &entry.point)
+                })
+                .map(|entry| metric.value(&entry.result.metrics))
+        };
+
+        fixed_values
+            .into_iter()
+            .map(|fixed| SweepMatrix {
+                metric,
+                fixed: fixed_parameter.zip(fixed),
+                row_parameter,
+                rows: rows.clone(),
+                column_parameter,
+                columns: columns.clone(),
+                cells: rows
+                    .iter()
+                    .map(|&row| {
+                        if columns.is_empty() {
+                            vec![cell(row, None, fixed)]
+                        } else {
+                            columns
+                                .iter()
+                                .map(|&column| cell(row, Some(column), fixed))
+                                .collect()
+                        }
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+impl SweepMatrix {
+    /// Title such as `IOPS by Block size x Threads (Queue depth 8)`
+    pub fn title(&self) -> String {
+        let mut title = format!(
+            "{} by {}",
+            self.metric.description(),
+            self.row_parameter.description()
+        );
+        if let Some(column) = self.column_parameter {
+            title.push_str(&format!(" x {}", column.description()));
+        }
+        if let Some((parameter, value)) = self.fixed {
+            title.push_str(&format!(
+                " ({} {})",
+                parameter.description(),
+                parameter.format_value(value)
+            ));
+        }
+        title
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SystemInfo;
+
+    fn entry(block_size: u64, thread_count: usize, queue_depth: Option<usize>) -> SweepEntry {
+        let point = SweepPoint {
+            block_size,
+            thread_count,
+            queue_depth,
+        };
+        // IOPS grow with threads and queue depth, throughput with block size
+        let iops = 1000.0 * thread_count as f64 * queue_depth.unwrap_or(1) as f64;
+        let metrics = PerformanceMetrics {
+            iops,
+            throughput_mbps: iops * block_size as f64 / (1024.0 * 1024.0),
+            ..PerformanceMetrics::default()
+        };
+        SweepEntry {
+            point,
+            result: BenchmarkResult::with_system_info(
+                BenchmarkConfig::default(),
+                metrics,
+                SystemInfo::default(),
+            ),
+        }
+    }
+
+    fn sweep_result(sweep: SweepConfig) -> SweepResult {
+        let base = BenchmarkConfig::random_read_write();
+        let entries = sweep
+            .expand(&base)
+            .iter()
+            .map(|config| {
+                let point = SweepPoint::of(config);
+                entry(point.block_size, point.thread_count, point.queue_depth)
+            })
+            .collect();
+        SweepResult::new(base, sweep, entries)
+    }
+
+    #[test]
+    fn test_matrix_by_two_parameters() {
+        let result = sweep_result(
+            SweepConfig::default()
+                .with_block_sizes(vec![4096, 65536])
+                .with_thread_counts(vec![1, 4]),
+        );
+        let matrices = result.matrices(SweepMetric::Iops);
+        assert_eq!(matrices.len(), 1);
+        let matrix = &matrices[0];
+        assert_eq!(matrix.title(), "IOPS by Block size x Threads");
+        assert_eq!(matrix.rows, vec![4096, 65536]);
+        assert_eq!(matrix.columns, vec![1, 4]);
+        assert_eq!(
+            matrix.cells,
+            vec![
+                vec![Some(1000.0), Some(4000.0)],
+                vec![Some(1000.0), Some(4000.0)]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_third_parameter_splits_matrices() {
+        let result = sweep_result(
+            SweepConfig::default()
+                .with_block_sizes(vec![4096])
+                .with_thread_counts(vec![1, 2])
+                .with_queue_depths(vec![1, 8]),
+        );
+        let matrices = result.matrices(SweepMetric::Iops);
+        assert_eq!(matrices.len(), 2);
+        assert_eq!(matrices[1].fixed, Some((SweepParameter::QueueDepth, 8)));
+        assert_eq!(
+            matrices[1].title(),
+            "IOPS by Block size x Threads (Queue depth 8)"
+        );
+        assert_eq!(matrices[1].cells, vec![vec![Some(8000.0), Some(16000.0)]]);
+    }
+
+    #[test]
+    fn test_single_parameter_and_best() {
+        let result = sweep_result(SweepConfig::default().with_thread_counts(vec![1, 2, 8]));
+        let matrix = &result.matrices(SweepMetric::Iops)[0];
+        assert_eq!(matrix.column_parameter, None);
+        assert_eq!(
+            matrix.cells,
+            vec![vec![Some(1000.0)], vec![Some(2000.0)], vec![Some(8000.0)]]
+        );
+
+        let best = result.best(SweepMetric::Iops).unwrap();
+        assert_eq!(best.point.thread_count, 8);
+        assert!(result.summary().contains("3 configs by Threads"));
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let result = sweep_result(SweepConfig::default().with_block_sizes(vec![4096, 8192]));
+        let json = serde_json::to_string(&result).unwrap();
+        let deserialized: SweepResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.sweep, result.sweep);
+        assert_eq!(deserialized.entries.len(), 2);
+        assert_eq!(deserialized.entries[1].point.block_size, 8192);
+    }
+}
@@ -0,0 +1,295 @@
+//! Per-interval benchmark samples
+//!
+//! Whole-run aggregates hide stalls, garbage collection pauses and write
+//! cache exhaustion. A `TimelineRecorder` splits a run into fixed intervals
+//! and keeps the bytes, operations and latency percentiles of each one, so
+//! the result can be charted over time.
+
+use crate::models::result::duration_serde;
+use crate::models::LatencyHistogram;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Activity during one sampling interval
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimelineSample {
+    /// Start of the interval, relative to the start of the measurement
+    #[serde(with = "duration_serde")]
+    pub offset: Duration,
+    /// Length of the interval (the last one is usually shorter)
+    #[serde(with = "duration_serde")]
+    pub duration: Duration,
+    /// Bytes transferred by operations completing in the interval
+    pub bytes: u64,
+    /// Operations completing in the interval
+    pub operations: u64,
+    /// Median latency of those operations
+    #[serde(with = "duration_serde")]
+    pub p50: Duration,
+    /// 99th percentile latency of those operations
+    #[serde(with = "duration_serde")]
+    pub p99: Duration,
+}
+
+/// Samples taken at a fixed interval over a benchmark run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Timeline {
+    /// Length of each sampling interval
+    #[serde(with = "duration_serde")]
+    pub interval: Duration,
+    /// Samples in time order, including intervals where nothing completed
+    pub samples: Vec<TimelineSample>,
+}
+
+/// Collects completed operations into per-interval samples
+#[derive(Debug)]
+pub struct TimelineRecorder {
+    interval: Duration,
+    start: Instant,
+    window_start: Duration,
+    bytes: u64,
+    latency: LatencyHistogram,
+    samples: Vec<TimelineSample>,
+}
+
+impl TimelineSample {
+    /// Throughput over the interval in MB/s
+    pub fn throughput_mbps(&self) -> f64 {
+        let secs = self.duration.as_secs_f64();
+        if secs > 0.0 {
+            (self.bytes as f64) / (1024.0 * 1024.0) / secs
+        } else {
+            0.0
+        }
+    }
+
+    /// Operations per second over the interval
+    pub fn iops(&self) -> f64 {
+        let secs = self.duration.as_secs_f64();
+        if secs > 0.0 {
+            self.operations as f64 / secs
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Timeline {
+    /// Combine the timelines of workers running side by side
+    ///
+    /// Samples are matched by interval. Bytes and operations add up; the
+    /// latency percentiles cannot be merged exactly without each interval's
+    /// distribution, so p99 is the worst worker's (an upper bound on the
+    /// combined value) and p50 the operation-weighted mean of the medians.
+    pub fn combine<'a>(timelines: impl IntoIterator<Item = &'a Timeline>) -> Option<Timeline> {
+        let mut combined: Option<Timeline> = None;
+        for timeline in timelines {
+            let Some(combined) = combined.as_mut() else {
+                combined = Some(timeline.clone());
+                continue;
+            };
+            for (index, sample) in timeline.samples.iter().enumerate() {
+                match combined.samples.get_mut(index) {
+                    Some(total) => {
+                        let operations = total.operations + sample.operations;
+                        if operations > 0 {
+                            let weighted = total.p50.as_nanos() * total.operations as u128
+                                + sample.p50.as_nanos() * sample.operations as u128;
+                            total.p50 =
+                                Duration::from_nanos((weighted / operations as u128) as u64);
+                        }
+                        total.p99 = total.p99.max(sample.p99);
+                        total.duration = total.duration.max(sample.duration);
+                        total.bytes += sample.bytes;
+                        total.operations = operations;
+                    }
+                    None => combined.samples.push(sample.clone()),
+                }
+            }
+        }
+        combined
+    }
+
+    /// Lowest and highest interval throughput in MB/s, ignoring a trailing partial interval
+    pub fn throughput_range(&self) -> Option<(f64, f64)> {
+        let full = self
+            .samples
+            .iter()
+            .filter(|s| s.duration >= self.interval)
+            .map(TimelineSample::throughput_mbps);
+        full.fold(None, |range, mbps| match range {
+            None => Some((mbps, mbps)),
+            Some((min, max)) => Some((f64::min(min, mbps), f64::max(max, mbps))),
+        })
+    }
+}
+
+impl TimelineRecorder {
+    /// Start recording intervals of `interval` length from now
+    pub fn new(interval: Duration) -> Self {
+        Self::starting_at(interval, Instant::now())
+    }
+
+    /// Start recording intervals of `interval` length from `start`
+    pub fn starting_at(interval: Duration, start: Instant) -> Self {
+        Self {
+            interval: interval.max(Duration::from_millis(1)),
+            start,
+            window_start: Duration::ZERO,
+            bytes: 0,
+            latency: LatencyHistogram::new(),
+            samples: Vec::new(),
+        }
+    }
+
+    /// Intervals closed so far
+    pub fn samples(&self) -> &[TimelineSample] {
+        &self.samples
+    }
+
+    /// Record an operation completing now
+    pub fn record(&mut self, bytes: u64, latency: Duration) {
+        self.record_at(Instant::now(), bytes, latency);
+    }
+
+    /// Record an operation completing at `now`
+    pub fn record_at(&mut self, now: Instant, bytes: u64, latency: Duration) {
+        self.advance_to(now.saturating_duration_since(self.start));
+        self.bytes += bytes;
+        self.latency.record(latency);
+    }
+
+    /// Close the last interval now and return the samples
+    pub fn finish(self) -> Timeline {
+        self.finish_at(Instant::now())
+    }
+
+    /// Close the last interval at `now` and return the samples
+    pub fn finish_at(mut self, now: Instant) -> Timeline {
+        let elapsed = now.saturating_duration_since(self.start);
+        self.advance_to(elapsed);
+        if elapsed > self.window_start || !self.latency.is_empty() {
+            self.close_window(elapsed.saturating_sub(self.window_start));
+        }
+        Timeline {
+            interval: self.interval,
+            samples: self.samples,
+        }
+    }
+
+    /// Close every interval that ended before `elapsed`, including empty ones
+    fn advance_to(&mut self, elapsed: Duration) {
+        while elapsed >= self.window_start + self.interval {
+            self.close_window(self.interval);
+        }
+    }
+
+    fn close_window(&mut self, duration: Duration) {
+        let latency = std::mem::take(&mut self.latency);
+        self.samples.push(TimelineSample {
+            offset: self.window_start,
+            duration,
+            bytes: std::mem::take(&mut self.bytes),
+            operations: latency.count(),
+            p50: latency.value_at_percentile(50.0),
+            p99: latency.value_at_percentile(99.0),
+        });
+        self.window_start += duration;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVAL: Duration = Duration::from_millis(100);
+
+    fn at(start: Instant, millis: u64) -> Instant {
+        start + Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_records_into_intervals() {
+        let start = Instant::now();
+        let mut recorder = TimelineRecorder::starting_at(INTERVAL, start);
+        for millis in [10, 20, 30] {
+            recorder.record_at(at(start, millis), 4096, Duration::from_micros(100));
+        }
+        recorder.record_at(at(start, 150), 4096, Duration::from_millis(5));
+        let timeline = recorder.finish_at(at(start, 250));
+
+        assert_eq!(timeline.interval, INTERVAL);
+        assert_eq!(timeline.samples.len(), 3);
+        let first = &timeline.samples[0];
+        assert_eq!(first.offset, Duration::ZERO);
+        assert_eq!(first.bytes, 3 * 4096);
+        assert_eq!(first.operations, 3);
+        assert_eq!(first.p99, Duration::from_micros(100));
+        assert!((first.iops() - 30.0).abs() < 0.001);
+
+        let second = &timeline.samples[1];
+        assert_eq!(second.offset, INTERVAL);
+        assert_eq!(second.operations, 1);
+        assert_eq!(second.p50, Duration::from_millis(5));
+
+        // The trailing partial interval is kept with its real length
+        let last = &timeline.samples[2];
+        assert_eq!(last.offset, Duration::from_millis(200));
+        assert_eq!(last.duration, Duration::from_millis(50));
+        assert_eq!(last.operations, 0);
+    }
+
+    #[test]
+    fn test_stalls_show_as_empty_intervals() {
+        let start = Instant::now();
+        let mut recorder = TimelineRecorder::starting_at(INTERVAL, start);
+        recorder.record_at(at(start, 50), 4096, Duration::from_micros(100));
+        // Nothing completes for 300ms, then one very slow operation
+        recorder.record_at(at(start, 420), 4096, Duration::from_millis(370));
+        let timeline = recorder.finish_at(at(start, 500));
+
+        let operations: Vec<u64> = timeline.samples.iter().map(|s| s.operations).collect();
+        assert_eq!(operations, vec![1, 0, 0, 0, 1]);
+        assert_eq!(timeline.samples[4].p99, Duration::from_millis(370));
+        assert_eq!(timeline.throughput_range().unwrap().0, 0.0);
+    }
+
+    #[test]
+    fn test_combine_worker_timelines() {
+        let start = Instant::now();
+        let mut fast = TimelineRecorder::starting_at(INTERVAL, start);
+        let mut slow = TimelineRecorder::starting_at(INTERVAL, start);
+        for millis in 0..9 {
+            fast.record_at(at(start, millis * 10), 4096, Duration::from_micros(100));
+        }
+        slow.record_at(at(start, 50), 4096, Duration::from_millis(2));
+        slow.record_at(at(start, 150), 4096, Duration::from_millis(2));
+        let fast = fast.finish_at(at(start, 100));
+        let slow = slow.finish_at(at(start, 200));
+
+        let combined = Timeline::combine([&fast, &slow]).unwrap();
+        assert_eq!(combined.samples.len(), 2);
+        let first = &combined.samples[0];
+        assert_eq!(first.operations, 10);
+        assert_eq!(first.bytes, 10 * 4096);
+        assert_eq!(first.p99, Duration::from_millis(2));
+        // (9 * 100us + 1 * 2ms) / 10, within histogram precision
+        let p50_micros = first.p50.as_secs_f64() * 1e6;
+        assert!((p50_micros - 290.0).abs() < 1.0);
+        assert_eq!(combined.samples[1].operations, 1);
+
+        assert!(Timeline::combine([]).is_none());
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let start = Instant::now();
+        let mut recorder = TimelineRecorder::starting_at(INTERVAL, start);
+        recorder.record_at(at(start, 10), 512, Duration::from_micros(80));
+        let timeline = recorder.finish_at(at(start, 100));
+
+        let json = serde_json::to_string(&timeline).unwrap();
+        let deserialized: Timeline = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, timeline);
+    }
+}
@@ -2,7 +2,7 @@ use std::io::{self, Write};
 use std::path::PathBuf;
 
 use crate::bench::sequential::{ProgressUpdate, SequentialBenchmark};
-use crate::config::{BenchmarkConfig, BenchmarkMode};
+use crate::config::BenchmarkConfig;
 use crate::models::BenchmarkResult;
 use crate::Result;
 
@@ -146,24 +146,20 @@ pub fn parse_duration(input: &str) -> Result<Duration, String> {
     let parts: Vec<&str> = input.split_whitespace().collect();
     
     for part in parts {
-        if part.ends_with("ms") {
-            let num_str = &part[..part.len() - 2];
+        if let Some(num_str) = part.strip_suffix("ms") {
             let millis: u64 = num_str.parse()
                 .map_err(|_| format!("Invalid milliseconds: {}", num_str))?;
             total_millis += millis;
-        } else if part.ends_with('s') {
-            let num_str = &part[..part.len() - 1];
+        } else if let Some(num_str) = part.strip_suffix('s') {
             let secs: f64 = num_str.parse()
                 .map_err(|_| format!("Invalid seconds: {}", num_str))?;
             total_secs += secs as u64;
             total_millis += (secs.fract() * 1000.0) as u64;
-        } else if part.ends_with('m') {
-            let num_str = &part[..part.len() - 1];
+        } else if let Some(num_str) = part.strip_suffix('m') {
             let mins: u64 = num_str.parse()
                 .map_err(|_| format!("Invalid minutes: {}", num_str))?;
             total_secs += mins * 60;
-        } else if part.ends_with('h') {
-            let num_str = &part[..part.len() - 1];
+        } else if let Some(num_str) = part.strip_suffix('h') {
             let hours: u64 = num_str.parse()
                 .map_err(|_| format!("Invalid hours: {}", num_str))?;
             total_secs += hours * 3600;
@@ -262,7 +258,7 @@ pub fn format_iops(iops: f64) -> String {
 /// use diorb::util::units::format_latency;
 /// 
 /// assert_eq!(format_latency(Duration::from_millis(5)), "5.00ms");
-/// assert_eq!(format_latency(Duration::from_micros(500)), "0.50ms");
+/// assert_eq!(format_latency(Duration::from_micros(500)), "500μs");
 /// ```
 pub fn format_latency(duration: Duration) -> String {
     let micros = duration.as_micros();